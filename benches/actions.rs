@@ -0,0 +1,51 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use wolfengate::domain::actors::actor::{AccelerationStats, PlayerStats, SpeedStats};
+use wolfengate::domain::actors::shoot::{AnimationStep, WeaponConfiguration};
+use wolfengate::domain::control::actions::{ActionStateBuilder, Actions, LinearActionState};
+use wolfengate::domain::maths::ANGLE_RIGHT;
+use wolfengate::domain::topology::door::LateralOpening;
+use wolfengate::domain::topology::index::TextureIndex;
+use wolfengate::domain::topology::map::{Map, MapConfiguration, SpawnPoint, Tile};
+
+const MAP_SIZE: usize = 256;
+
+fn big_map() -> Map {
+    let stats = PlayerStats::new(AccelerationStats::new(1.0), AccelerationStats::new(1.0), SpeedStats::new(1.0));
+    let animation = AnimationStep::new(0.1, TextureIndex::new(0));
+    let weapon = WeaponConfiguration::new(TextureIndex::new(0), animation, animation, animation, 1);
+    let door = ActionStateBuilder::new(Box::new(LinearActionState::new(SpeedStats::new(1.0), Box::new(LateralOpening::default()))));
+
+    let mut configuration = MapConfiguration::new(TextureIndex::new(0), stats, weapon);
+    configuration.add('#', Tile::SOLID(TextureIndex::new(0)));
+    configuration.add('D', Tile::DYNAMIC(TextureIndex::new(0), TextureIndex::new(0), door));
+    configuration.add(' ', Tile::NOTHING);
+    configuration.add_spawn('r', SpawnPoint::new(ANGLE_RIGHT));
+
+    let mut lines = vec![];
+    for y in 0..MAP_SIZE {
+        let line: String = (0..MAP_SIZE)
+            .map(|x| match (x, y) {
+                (1, 1) => 'r',
+                _ if x % 32 == 16 && y % 32 == 16 => 'D',
+                _ if x % 2 == 0 => '#',
+                _ => ' ',
+            })
+            .collect();
+        lines.push(line);
+    }
+
+    Map::new(&lines.join("\n"), configuration).unwrap()
+}
+
+fn notify_elapsed(c: &mut Criterion) {
+    let map = big_map();
+    let mut actions = Actions::new(&map);
+
+    c.bench_function("actions notify_elapsed on a 256x256 map", |b| {
+        b.iter(|| actions.notify_elapsed(black_box(16000)))
+    });
+}
+
+criterion_group!(benches, notify_elapsed);
+criterion_main!(benches);
@@ -0,0 +1,50 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use wolfengate::domain::actors::actor::{AccelerationStats, PlayerStats, SpeedStats};
+use wolfengate::domain::actors::shoot::{AnimationStep, WeaponConfiguration};
+use wolfengate::domain::level::Level;
+use wolfengate::domain::maths::{ANGLE_90, ANGLE_RIGHT};
+use wolfengate::domain::topology::index::TextureIndex;
+use wolfengate::domain::topology::map::{EnemyType, Map, MapConfiguration, SpawnPoint, Tile};
+use wolfengate::domain::ui::view::ViewScreen;
+
+const MAP_SIZE: usize = 64;
+
+fn crowded_map() -> Map {
+    let stats = PlayerStats::new(AccelerationStats::new(1.0), AccelerationStats::new(1.0), SpeedStats::new(1.0));
+    let animation = AnimationStep::new(0.1, TextureIndex::new(0));
+    let weapon = WeaponConfiguration::new(TextureIndex::new(0), animation, animation, animation, 1);
+
+    let mut configuration = MapConfiguration::new(TextureIndex::new(0), stats, weapon);
+    configuration.add('#', Tile::SOLID(TextureIndex::new(0)));
+    configuration.add(' ', Tile::NOTHING);
+    configuration.add_enemy('E', EnemyType::new(TextureIndex::new(1), TextureIndex::new(1), 100));
+    configuration.add_spawn('r', SpawnPoint::new(ANGLE_RIGHT));
+
+    let middle = MAP_SIZE / 2;
+    let mut lines = vec![];
+    for y in 0..MAP_SIZE {
+        let line: String = (0..MAP_SIZE)
+            .map(|x| match (x, y) {
+                _ if x == middle && y == middle => 'r',
+                _ if x == 0 || y == 0 || x == MAP_SIZE - 1 || y == MAP_SIZE - 1 => '#',
+                _ if x % 3 == 0 && y % 3 == 0 => 'E',
+                _ => ' ',
+            })
+            .collect();
+        lines.push(line);
+    }
+
+    Map::new(&lines.join("\n"), configuration).unwrap()
+}
+
+fn generate_actions(c: &mut Criterion) {
+    let level = Level::new(ViewScreen::new(240, 320, ANGLE_90), crowded_map()).unwrap();
+
+    c.bench_function("level generate_actions with hundreds of enemies", |b| {
+        b.iter(|| level.generate_actions())
+    });
+}
+
+criterion_group!(benches, generate_actions);
+criterion_main!(benches);
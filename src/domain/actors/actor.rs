@@ -1,15 +1,26 @@
-use crate::domain::actors::physics::{Acceleration, Speed};
+use crate::domain::actors::physics::{Acceleration, Knockback, Speed};
+use crate::domain::actors::projectile::Projectile;
 use crate::domain::control::force::Force;
-use crate::domain::maths::{Angle, ANGLE_RIGHT};
+use crate::domain::maths::{Angle, ANGLE_RIGHT, Move, signed_angle, Vector};
 use crate::domain::topology::coord::Position;
 use crate::domain::topology::index::TextureIndex;
 
+const KNOCKBACK_DURATION_IN_MICROSECONDS: u128 = 200000;
+const DEFAULT_ENEMY_MELEE_DAMAGE: u32 = 10;
+const DEFAULT_ENEMY_MELEE_COOLDOWN_IN_SECONDS: f32 = 1.0;
+const DEFAULT_ENEMY_MELEE_RANGE: f32 = 0.8;
+const DEFAULT_PLAYER_HEALTH: u32 = 100;
+pub const DEFAULT_COLLISION_RADIUS: f32 = 0.1;
+const CROUCH_SPEED_FACTOR: f32 = 0.75;
+
 #[derive(Copy, Clone)]
 pub struct Player {
     position: Position,
     inertia: Speed,
     orientation: Angle,
     stats: PlayerStats,
+    stamina: f32,
+    health: u32,
 }
 
 #[derive(Copy, Clone)]
@@ -17,6 +28,35 @@ pub struct PlayerStats {
     acceleration: AccelerationStats,
     deceleration: AccelerationStats,
     max_speed: SpeedStats,
+    run_factor: f32,
+    stamina: StaminaStats,
+    health: u32,
+    collision_radius: f32,
+}
+
+#[derive(Copy, Clone)]
+pub struct StaminaStats {
+    maximum: f32,
+    drain: SpeedStats,
+    regeneration: SpeedStats,
+}
+
+#[derive(Copy, Clone)]
+pub struct Despawn {
+    distance: f32,
+    grace_in_microseconds: u128,
+}
+
+#[derive(Copy, Clone)]
+pub struct AimAssist {
+    window: Angle,
+    strength: f32,
+}
+
+#[derive(Copy, Clone)]
+pub struct WallBump {
+    speed: SpeedStats,
+    damage: u32,
 }
 
 #[derive(Copy, Clone)]
@@ -35,6 +75,52 @@ pub struct Enemy {
     health: u32,
     texture: TextureIndex,
     texture_dead: TextureIndex,
+    knockback: Knockback,
+    state: EnemyState,
+    ranged_attack: Option<RangedAttack>,
+    cooldown_in_microseconds: u128,
+    melee_attack: MeleeAttack,
+    melee_cooldown_in_microseconds: u128,
+    unseen_in_microseconds: u128,
+    orientation: Angle,
+    perception: Option<Perception>,
+    aware: bool,
+    origin: EnemyOrigin,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum EnemyOrigin {
+    Map(usize),
+    Spawner(usize),
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Perception {
+    sight_range: f32,
+    sight_cone: Angle,
+    hearing_range: f32,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum EnemyState {
+    Idle,
+    Attack,
+}
+
+#[derive(Copy, Clone)]
+pub struct RangedAttack {
+    texture: TextureIndex,
+    speed: SpeedStats,
+    damage: u32,
+    cooldown_in_microseconds: u128,
+    range: f32,
+}
+
+#[derive(Copy, Clone)]
+pub struct MeleeAttack {
+    damage: u32,
+    cooldown_in_microseconds: u128,
+    range: f32,
 }
 
 impl Player {
@@ -44,6 +130,8 @@ impl Player {
             inertia: Speed::new(ANGLE_RIGHT, 0.0),
             orientation,
             stats,
+            stamina: stats.stamina().maximum(),
+            health: stats.health(),
         }
     }
 
@@ -63,12 +151,48 @@ impl Player {
         self.inertia
     }
 
+    pub fn stamina(&self) -> f32 {
+        self.stamina
+    }
+
+    pub fn can_run(&self) -> bool {
+        self.stamina > 0.0
+    }
+
+    pub fn health(&self) -> u32 {
+        self.health
+    }
+
+    pub fn damage(&self, damage: u32) -> Self {
+        Self {
+            inertia: self.inertia,
+            orientation: self.orientation,
+            stats: self.stats,
+            position: self.position,
+            stamina: self.stamina,
+            health: self.health.saturating_sub(damage),
+        }
+    }
+
+    pub fn with_health(&self, health: u32) -> Self {
+        Self {
+            inertia: self.inertia,
+            orientation: self.orientation,
+            stats: self.stats,
+            position: self.position,
+            stamina: self.stamina,
+            health,
+        }
+    }
+
     pub fn with_inertia(&self, inertia: Speed) -> Self {
         Self {
             inertia,
             orientation: self.orientation,
             stats: self.stats,
             position: self.position,
+            stamina: self.stamina,
+            health: self.health,
         }
     }
 
@@ -78,6 +202,37 @@ impl Player {
             orientation: self.orientation,
             stats: self.stats,
             position,
+            stamina: self.stamina,
+            health: self.health,
+        }
+    }
+
+    pub fn with_stats(&self, stats: PlayerStats) -> Self {
+        Self {
+            inertia: self.inertia,
+            orientation: self.orientation,
+            stats,
+            position: self.position,
+            stamina: self.stamina,
+            health: self.health,
+        }
+    }
+
+    pub fn consume_stamina(&self, running: bool, microseconds_elapsed: u128) -> Self {
+        let stamina_stats = self.stats.stamina();
+        let stamina = if running {
+            self.stamina - stamina_stats.drain().to_units(microseconds_elapsed)
+        } else {
+            self.stamina + stamina_stats.regeneration().to_units(microseconds_elapsed)
+        };
+
+        Self {
+            inertia: self.inertia,
+            orientation: self.orientation,
+            stats: self.stats,
+            position: self.position,
+            stamina: stamina.clamp(0.0, stamina_stats.maximum()),
+            health: self.health,
         }
     }
 
@@ -92,6 +247,8 @@ impl Player {
             inertia: self.inertia,
             orientation: angle,
             stats: self.stats,
+            stamina: self.stamina,
+            health: self.health,
         }
     }
 
@@ -124,6 +281,8 @@ impl Player {
             inertia: full_inertia,
             orientation: self.orientation,
             stats: self.stats,
+            stamina: self.stamina,
+            health: self.health,
         }
     }
 }
@@ -138,6 +297,70 @@ impl PlayerStats {
             acceleration,
             deceleration,
             max_speed,
+            run_factor: 1.0,
+            stamina: StaminaStats::default(),
+            health: DEFAULT_PLAYER_HEALTH,
+            collision_radius: DEFAULT_COLLISION_RADIUS,
+        }
+    }
+
+    pub fn with_running(&self, run_factor: f32, stamina: StaminaStats) -> Self {
+        Self {
+            acceleration: self.acceleration,
+            deceleration: self.deceleration,
+            max_speed: self.max_speed,
+            run_factor,
+            stamina,
+            health: self.health,
+            collision_radius: self.collision_radius,
+        }
+    }
+
+    pub fn with_health(&self, health: u32) -> Self {
+        Self {
+            acceleration: self.acceleration,
+            deceleration: self.deceleration,
+            max_speed: self.max_speed,
+            run_factor: self.run_factor,
+            stamina: self.stamina,
+            health,
+            collision_radius: self.collision_radius,
+        }
+    }
+
+    pub fn with_collision_radius(&self, collision_radius: f32) -> Self {
+        Self {
+            acceleration: self.acceleration,
+            deceleration: self.deceleration,
+            max_speed: self.max_speed,
+            run_factor: self.run_factor,
+            stamina: self.stamina,
+            health: self.health,
+            collision_radius,
+        }
+    }
+
+    pub fn running(&self) -> Self {
+        Self {
+            acceleration: AccelerationStats::new(self.acceleration.units_per_seconds_square * self.run_factor),
+            deceleration: self.deceleration,
+            max_speed: SpeedStats::new(self.max_speed.units_per_seconds * self.run_factor),
+            run_factor: self.run_factor,
+            stamina: self.stamina,
+            health: self.health,
+            collision_radius: self.collision_radius,
+        }
+    }
+
+    pub fn crouching(&self) -> Self {
+        Self {
+            acceleration: self.acceleration,
+            deceleration: self.deceleration,
+            max_speed: SpeedStats::new(self.max_speed.units_per_seconds * CROUCH_SPEED_FACTOR),
+            run_factor: self.run_factor,
+            stamina: self.stamina,
+            health: self.health,
+            collision_radius: self.collision_radius,
         }
     }
 
@@ -152,17 +375,416 @@ impl PlayerStats {
     pub fn max_speed(&self) -> SpeedStats {
         self.max_speed
     }
+
+    pub fn run_factor(&self) -> f32 {
+        self.run_factor
+    }
+
+    pub fn stamina(&self) -> StaminaStats {
+        self.stamina
+    }
+
+    pub fn health(&self) -> u32 {
+        self.health
+    }
+
+    pub fn collision_radius(&self) -> f32 {
+        self.collision_radius
+    }
+}
+
+impl StaminaStats {
+    pub fn new(maximum: f32, drain: SpeedStats, regeneration: SpeedStats) -> Self {
+        Self { maximum, drain, regeneration }
+    }
+
+    pub fn maximum(&self) -> f32 {
+        self.maximum
+    }
+
+    pub fn drain(&self) -> SpeedStats {
+        self.drain
+    }
+
+    pub fn regeneration(&self) -> SpeedStats {
+        self.regeneration
+    }
+}
+
+impl Default for StaminaStats {
+    fn default() -> Self {
+        Self::new(1.0, SpeedStats::new(0.0), SpeedStats::new(0.0))
+    }
+}
+
+impl Despawn {
+    pub fn new(distance: f32, grace_in_seconds: f32) -> Self {
+        Self {
+            distance,
+            grace_in_microseconds: (grace_in_seconds * 1000000.0) as u128,
+        }
+    }
+
+    pub fn distance(&self) -> f32 {
+        self.distance
+    }
+
+    pub fn grace_in_microseconds(&self) -> u128 {
+        self.grace_in_microseconds
+    }
+}
+
+impl AimAssist {
+    pub fn new(window: Angle, strength: f32) -> Self {
+        Self { window, strength }
+    }
+
+    pub fn window(&self) -> Angle {
+        self.window
+    }
+
+    // A strength of 1 turns the aim right onto the target
+    pub fn bend(&self, aim: Angle, target: Angle) -> Angle {
+        aim.add(aim.shortest_difference(target).multiplication(self.strength))
+    }
+}
+
+impl WallBump {
+    pub fn new(speed: SpeedStats, damage: u32) -> Self {
+        Self { speed, damage }
+    }
+
+    pub fn damage(&self) -> u32 {
+        self.damage
+    }
+
+    pub fn is_bump(&self, blocked_distance: f32, microseconds_elapsed: u128) -> bool {
+        microseconds_elapsed > 0 && blocked_distance >= self.speed.to_units(microseconds_elapsed)
+    }
 }
 
 impl Enemy {
     pub fn new(texture: TextureIndex, texture_dead: TextureIndex, position: Position, health: u32) -> Self {
-        Self { position, texture, texture_dead, health }
+        Self {
+            position,
+            texture,
+            texture_dead,
+            health,
+            knockback: Knockback::none(),
+            state: EnemyState::Idle,
+            ranged_attack: None,
+            cooldown_in_microseconds: 0,
+            melee_attack: MeleeAttack::default(),
+            melee_cooldown_in_microseconds: 0,
+            unseen_in_microseconds: 0,
+            orientation: ANGLE_RIGHT,
+            perception: None,
+            aware: true,
+            origin: EnemyOrigin::Map(0),
+        }
     }
 
     pub fn position(&self) -> Position {
         self.position
     }
 
+    pub fn with_position(&self, position: Position) -> Self {
+        Self {
+            position,
+            texture: self.texture,
+            texture_dead: self.texture_dead,
+            health: self.health,
+            knockback: self.knockback,
+            state: self.state,
+            ranged_attack: self.ranged_attack,
+            cooldown_in_microseconds: self.cooldown_in_microseconds,
+            melee_attack: self.melee_attack,
+            melee_cooldown_in_microseconds: self.melee_cooldown_in_microseconds,
+            unseen_in_microseconds: self.unseen_in_microseconds,
+            orientation: self.orientation,
+            perception: self.perception,
+            aware: self.aware,
+            origin: self.origin,
+        }
+    }
+
+    pub fn with_health(&self, health: u32) -> Self {
+        Self {
+            position: self.position,
+            texture: self.texture,
+            texture_dead: self.texture_dead,
+            health,
+            knockback: self.knockback,
+            state: self.state,
+            ranged_attack: self.ranged_attack,
+            cooldown_in_microseconds: self.cooldown_in_microseconds,
+            melee_attack: self.melee_attack,
+            melee_cooldown_in_microseconds: self.melee_cooldown_in_microseconds,
+            unseen_in_microseconds: self.unseen_in_microseconds,
+            orientation: self.orientation,
+            perception: self.perception,
+            aware: self.aware,
+            origin: self.origin,
+        }
+    }
+
+    pub fn with_ranged_attack(&self, ranged_attack: RangedAttack) -> Self {
+        Self {
+            position: self.position,
+            texture: self.texture,
+            texture_dead: self.texture_dead,
+            health: self.health,
+            knockback: self.knockback,
+            state: self.state,
+            ranged_attack: Some(ranged_attack),
+            cooldown_in_microseconds: self.cooldown_in_microseconds,
+            melee_attack: self.melee_attack,
+            melee_cooldown_in_microseconds: self.melee_cooldown_in_microseconds,
+            unseen_in_microseconds: self.unseen_in_microseconds,
+            orientation: self.orientation,
+            perception: self.perception,
+            aware: self.aware,
+            origin: self.origin,
+        }
+    }
+    pub fn with_melee_attack(&self, melee_attack: MeleeAttack) -> Self {
+        Self {
+            position: self.position,
+            texture: self.texture,
+            texture_dead: self.texture_dead,
+            health: self.health,
+            knockback: self.knockback,
+            state: self.state,
+            ranged_attack: self.ranged_attack,
+            cooldown_in_microseconds: self.cooldown_in_microseconds,
+            melee_attack,
+            melee_cooldown_in_microseconds: self.melee_cooldown_in_microseconds,
+            unseen_in_microseconds: self.unseen_in_microseconds,
+            orientation: self.orientation,
+            perception: self.perception,
+            aware: self.aware,
+            origin: self.origin,
+        }
+    }
+
+    pub fn knock_back(&self, orientation: Angle, distance: f32) -> Self {
+        Self {
+            position: self.position,
+            texture: self.texture,
+            texture_dead: self.texture_dead,
+            health: self.health,
+            knockback: Knockback::new(orientation, distance, KNOCKBACK_DURATION_IN_MICROSECONDS),
+            state: self.state,
+            ranged_attack: self.ranged_attack,
+            cooldown_in_microseconds: self.cooldown_in_microseconds,
+            melee_attack: self.melee_attack,
+            melee_cooldown_in_microseconds: self.melee_cooldown_in_microseconds,
+            unseen_in_microseconds: self.unseen_in_microseconds,
+            orientation: self.orientation,
+            perception: self.perception,
+            aware: self.aware,
+            origin: self.origin,
+        }
+    }
+
+    pub fn knockback_move(&self, microseconds_elapsed: u128) -> (Move, Self) {
+        let (moves, knockback) = self.knockback.elapsed(microseconds_elapsed);
+        let enemy = Self {
+            position: self.position,
+            texture: self.texture,
+            texture_dead: self.texture_dead,
+            health: self.health,
+            knockback,
+            state: self.state,
+            ranged_attack: self.ranged_attack,
+            cooldown_in_microseconds: self.cooldown_in_microseconds,
+            melee_attack: self.melee_attack,
+            melee_cooldown_in_microseconds: self.melee_cooldown_in_microseconds,
+            unseen_in_microseconds: self.unseen_in_microseconds,
+            orientation: self.orientation,
+            perception: self.perception,
+            aware: self.aware,
+            origin: self.origin,
+        };
+
+        (moves, enemy)
+    }
+
+    pub fn update_state(&self, target: Position) -> Self {
+        let in_range = self.ranged_attack
+            .map(|ranged_attack| self.position.distance(&target) <= ranged_attack.range())
+            .unwrap_or(false);
+        let state = if self.aware && in_range && !self.is_dead() { EnemyState::Attack } else { EnemyState::Idle };
+        let orientation = match state {
+            EnemyState::Attack => signed_angle(self.position, target).unwrap_or(self.orientation),
+            EnemyState::Idle => self.orientation,
+        };
+
+        Self {
+            position: self.position,
+            texture: self.texture,
+            texture_dead: self.texture_dead,
+            health: self.health,
+            knockback: self.knockback,
+            state,
+            ranged_attack: self.ranged_attack,
+            cooldown_in_microseconds: self.cooldown_in_microseconds,
+            melee_attack: self.melee_attack,
+            melee_cooldown_in_microseconds: self.melee_cooldown_in_microseconds,
+            unseen_in_microseconds: self.unseen_in_microseconds,
+            orientation,
+            perception: self.perception,
+            aware: self.aware,
+            origin: self.origin,
+        }
+    }
+
+    pub fn with_perception(&self, perception: Perception) -> Self {
+        Self {
+            position: self.position,
+            texture: self.texture,
+            texture_dead: self.texture_dead,
+            health: self.health,
+            knockback: self.knockback,
+            state: self.state,
+            ranged_attack: self.ranged_attack,
+            cooldown_in_microseconds: self.cooldown_in_microseconds,
+            melee_attack: self.melee_attack,
+            melee_cooldown_in_microseconds: self.melee_cooldown_in_microseconds,
+            unseen_in_microseconds: self.unseen_in_microseconds,
+            orientation: self.orientation,
+            perception: Some(perception),
+            aware: false,
+            origin: self.origin,
+        }
+    }
+
+    pub fn with_orientation(&self, orientation: Angle) -> Self {
+        Self {
+            position: self.position,
+            texture: self.texture,
+            texture_dead: self.texture_dead,
+            health: self.health,
+            knockback: self.knockback,
+            state: self.state,
+            ranged_attack: self.ranged_attack,
+            cooldown_in_microseconds: self.cooldown_in_microseconds,
+            melee_attack: self.melee_attack,
+            melee_cooldown_in_microseconds: self.melee_cooldown_in_microseconds,
+            unseen_in_microseconds: self.unseen_in_microseconds,
+            orientation,
+            perception: self.perception,
+            aware: self.aware,
+            origin: self.origin,
+        }
+    }
+
+    pub fn with_origin(&self, origin: EnemyOrigin) -> Self {
+        Self {
+            position: self.position,
+            texture: self.texture,
+            texture_dead: self.texture_dead,
+            health: self.health,
+            knockback: self.knockback,
+            state: self.state,
+            ranged_attack: self.ranged_attack,
+            cooldown_in_microseconds: self.cooldown_in_microseconds,
+            melee_attack: self.melee_attack,
+            melee_cooldown_in_microseconds: self.melee_cooldown_in_microseconds,
+            unseen_in_microseconds: self.unseen_in_microseconds,
+            orientation: self.orientation,
+            perception: self.perception,
+            aware: self.aware,
+            origin,
+        }
+    }
+
+    pub fn alert(&self) -> Self {
+        Self {
+            position: self.position,
+            texture: self.texture,
+            texture_dead: self.texture_dead,
+            health: self.health,
+            knockback: self.knockback,
+            state: self.state,
+            ranged_attack: self.ranged_attack,
+            cooldown_in_microseconds: self.cooldown_in_microseconds,
+            melee_attack: self.melee_attack,
+            melee_cooldown_in_microseconds: self.melee_cooldown_in_microseconds,
+            unseen_in_microseconds: self.unseen_in_microseconds,
+            orientation: self.orientation,
+            perception: self.perception,
+            aware: true,
+            origin: self.origin,
+        }
+    }
+
+    pub fn fire(&self, target: Position, microseconds_elapsed: u128) -> (Option<Projectile>, Self) {
+        let cooldown = self.cooldown_in_microseconds.saturating_sub(microseconds_elapsed);
+
+        let projectile = match (self.state, self.ranged_attack) {
+            (EnemyState::Attack, Some(ranged_attack)) if cooldown == 0 => signed_angle(self.position, target)
+                .map(|orientation| ranged_attack.projectile(self.position, orientation)),
+            _ => None,
+        };
+
+        let cooldown_in_microseconds = match (projectile, self.ranged_attack) {
+            (Some(_), Some(ranged_attack)) => ranged_attack.cooldown_in_microseconds(),
+            _ => cooldown,
+        };
+
+        let enemy = Self {
+            position: self.position,
+            texture: self.texture,
+            texture_dead: self.texture_dead,
+            health: self.health,
+            knockback: self.knockback,
+            state: self.state,
+            ranged_attack: self.ranged_attack,
+            cooldown_in_microseconds,
+            melee_attack: self.melee_attack,
+            melee_cooldown_in_microseconds: self.melee_cooldown_in_microseconds,
+            unseen_in_microseconds: self.unseen_in_microseconds,
+            orientation: self.orientation,
+            perception: self.perception,
+            aware: self.aware,
+            origin: self.origin,
+        };
+
+        (projectile, enemy)
+    }
+
+    pub fn strike(&self, target: Position, microseconds_elapsed: u128) -> (Option<u32>, Self) {
+        let cooldown = self.melee_cooldown_in_microseconds.saturating_sub(microseconds_elapsed);
+        let in_reach = !self.is_dead() && self.aware && self.position.distance(&target) < self.melee_attack.range();
+
+        let (damage, melee_cooldown_in_microseconds) = if in_reach && cooldown == 0 {
+            (Some(self.melee_attack.damage()), self.melee_attack.cooldown_in_microseconds())
+        } else {
+            (None, cooldown)
+        };
+
+        let enemy = Self {
+            position: self.position,
+            texture: self.texture,
+            texture_dead: self.texture_dead,
+            health: self.health,
+            knockback: self.knockback,
+            state: self.state,
+            ranged_attack: self.ranged_attack,
+            cooldown_in_microseconds: self.cooldown_in_microseconds,
+            melee_attack: self.melee_attack,
+            melee_cooldown_in_microseconds,
+            unseen_in_microseconds: self.unseen_in_microseconds,
+            orientation: self.orientation,
+            perception: self.perception,
+            aware: self.aware,
+            origin: self.origin,
+        };
+
+        (damage, enemy)
+    }
+
     pub fn damage(&self, damage: u32) -> Self {
         let new_health = if self.health < damage { 0 } else { self.health - damage };
         Self {
@@ -170,11 +792,68 @@ impl Enemy {
             texture: self.texture,
             texture_dead: self.texture_dead,
             health: new_health,
+            knockback: self.knockback,
+            state: self.state,
+            ranged_attack: self.ranged_attack,
+            cooldown_in_microseconds: self.cooldown_in_microseconds,
+            melee_attack: self.melee_attack,
+            melee_cooldown_in_microseconds: self.melee_cooldown_in_microseconds,
+            unseen_in_microseconds: self.unseen_in_microseconds,
+            orientation: self.orientation,
+            perception: self.perception,
+            aware: self.aware,
+            origin: self.origin,
         }
     }
 
+    pub fn notify_unseen(&self, unseen: bool, microseconds_elapsed: u128) -> Self {
+        let unseen_in_microseconds = if unseen { self.unseen_in_microseconds + microseconds_elapsed } else { 0 };
+
+        Self {
+            position: self.position,
+            texture: self.texture,
+            texture_dead: self.texture_dead,
+            health: self.health,
+            knockback: self.knockback,
+            state: self.state,
+            ranged_attack: self.ranged_attack,
+            cooldown_in_microseconds: self.cooldown_in_microseconds,
+            melee_attack: self.melee_attack,
+            melee_cooldown_in_microseconds: self.melee_cooldown_in_microseconds,
+            unseen_in_microseconds,
+            orientation: self.orientation,
+            perception: self.perception,
+            aware: self.aware,
+            origin: self.origin,
+        }
+    }
+
+    pub fn unseen_in_microseconds(&self) -> u128 {
+        self.unseen_in_microseconds
+    }
+
     pub fn is_dead(&self) -> bool {
-        self.health <= 0
+        self.health == 0
+    }
+
+    pub fn state(&self) -> EnemyState {
+        self.state
+    }
+
+    pub fn orientation(&self) -> Angle {
+        self.orientation
+    }
+
+    pub fn perception(&self) -> Option<Perception> {
+        self.perception
+    }
+
+    pub fn is_aware(&self) -> bool {
+        self.aware
+    }
+
+    pub fn origin(&self) -> EnemyOrigin {
+        self.origin
     }
 
     pub fn texture(&self) -> TextureIndex {
@@ -190,6 +869,83 @@ impl Enemy {
     }
 }
 
+impl Perception {
+    pub fn new(sight_range: f32, sight_cone: Angle, hearing_range: f32) -> Self {
+        Self {
+            sight_range,
+            sight_cone,
+            hearing_range,
+        }
+    }
+
+    pub fn can_see(&self, from: Position, orientation: Angle, target: Position) -> bool {
+        if from.distance(&target) > self.sight_range {
+            return false;
+        }
+
+        Vector::from_angle(orientation)
+            .angle(Vector::new(from, target))
+            .map(|angle| angle.to_radiant() <= self.sight_cone.to_radiant() / 2.0)
+            .unwrap_or(true)
+    }
+
+    pub fn can_hear(&self, from: Position, sound: Position) -> bool {
+        from.distance(&sound) <= self.hearing_range
+    }
+}
+
+impl RangedAttack {
+    pub fn new(texture: TextureIndex, speed: SpeedStats, damage: u32, cooldown_in_seconds: f32, range: f32) -> Self {
+        Self {
+            texture,
+            speed,
+            damage,
+            cooldown_in_microseconds: (cooldown_in_seconds * 1000000.0) as u128,
+            range,
+        }
+    }
+
+    pub fn range(&self) -> f32 {
+        self.range
+    }
+
+    pub fn cooldown_in_microseconds(&self) -> u128 {
+        self.cooldown_in_microseconds
+    }
+
+    pub fn projectile(&self, position: Position, orientation: Angle) -> Projectile {
+        Projectile::new(position, orientation, self.speed, self.damage, self.texture)
+    }
+}
+
+impl MeleeAttack {
+    pub fn new(damage: u32, cooldown_in_seconds: f32, range: f32) -> Self {
+        Self {
+            damage,
+            cooldown_in_microseconds: (cooldown_in_seconds * 1000000.0) as u128,
+            range,
+        }
+    }
+
+    pub fn damage(&self) -> u32 {
+        self.damage
+    }
+
+    pub fn range(&self) -> f32 {
+        self.range
+    }
+
+    pub fn cooldown_in_microseconds(&self) -> u128 {
+        self.cooldown_in_microseconds
+    }
+}
+
+impl Default for MeleeAttack {
+    fn default() -> Self {
+        Self::new(DEFAULT_ENEMY_MELEE_DAMAGE, DEFAULT_ENEMY_MELEE_COOLDOWN_IN_SECONDS, DEFAULT_ENEMY_MELEE_RANGE)
+    }
+}
+
 impl AccelerationStats {
     pub fn new(units_per_seconds_square: f32) -> Self {
         Self {
@@ -223,7 +979,7 @@ impl SpeedStats {
 #[cfg(test)]
 mod actor_test {
     use spectral::prelude::*;
-    use crate::domain::actors::actor::{AccelerationStats, PlayerStats, SpeedStats};
+    use crate::domain::actors::actor::{AccelerationStats, PlayerStats, SpeedStats, StaminaStats};
     use crate::domain::actors::physics::Speed;
     use crate::domain::control::force::Force;
 
@@ -314,12 +1070,43 @@ mod actor_test {
         assert_that!(after_move.position.x()).is_close_to(1.0, 0.001);
         assert_that!(after_move.position.y()).is_equal_to(4.0);
     }
+
+    #[test]
+    fn running_should_multiply_acceleration_and_maximum_speed() {
+        let stats = PlayerStats::new(AccelerationStats::new(2.0), AccelerationStats::new(1.0), SpeedStats::new(5.0))
+            .with_running(1.5, StaminaStats::default());
+
+        let running = stats.running();
+
+        assert_that!(running.max_speed().units_per_seconds()).is_equal_to(7.5);
+        assert_that!(running.acceleration().to_acceleration(ANGLE_0).to_speed(1000000).units_per_seconds()).is_equal_to(3.0);
+        assert_that!(running.deceleration().to_speed_stats(1000000).units_per_seconds()).is_equal_to(1.0);
+    }
+
+    #[test]
+    fn stamina_should_drain_while_running_and_regenerate_otherwise() {
+        let stamina = StaminaStats::new(10.0, SpeedStats::new(4.0), SpeedStats::new(1.0));
+        let stats = PlayerStats::new(AccelerationStats::new(2.0), AccelerationStats::new(1.0), SpeedStats::new(5.0))
+            .with_running(1.5, stamina);
+        let player = Player::new(Position::new(1.0, 2.0), ANGLE_RIGHT, stats);
+
+        let drained = player.consume_stamina(true, 2000000);
+        assert_that!(drained.stamina()).is_equal_to(2.0);
+
+        let exhausted = drained.consume_stamina(true, 2000000);
+        assert_that!(exhausted.stamina()).is_equal_to(0.0);
+        assert_that!(exhausted.can_run()).is_false();
+
+        let rested = exhausted.consume_stamina(false, 3000000);
+        assert_that!(rested.stamina()).is_equal_to(3.0);
+        assert_that!(rested.consume_stamina(false, 60000000).stamina()).is_equal_to(10.0);
+    }
 }
 
 #[cfg(test)]
 mod enemy_test {
     use spectral::prelude::*;
-    use crate::domain::actors::actor::Enemy;
+    use crate::domain::actors::actor::{Enemy, EnemyState, RangedAttack, SpeedStats};
     use crate::domain::topology::coord::Position;
     use crate::domain::topology::index::TextureIndex;
 
@@ -355,6 +1142,33 @@ mod enemy_test {
         assert_that!(enemy.is_dead()).is_true();
     }
 
+    #[test]
+    fn ranged_enemy_should_attack_a_target_in_range() {
+        let enemy = build_enemy(100).with_ranged_attack(build_ranged_attack());
+
+        assert_that!(enemy.update_state(Position::new(3.0, 0.0)).state()).is_equal_to(EnemyState::Attack);
+        assert_that!(enemy.update_state(Position::new(30.0, 0.0)).state()).is_equal_to(EnemyState::Idle);
+    }
+
+    #[test]
+    fn attacking_enemy_should_fire_on_its_cooldown() {
+        let enemy = build_enemy(100)
+            .with_ranged_attack(build_ranged_attack())
+            .update_state(Position::new(3.0, 0.0));
+
+        let (first, enemy) = enemy.fire(Position::new(3.0, 0.0), 16000);
+        let (during_cooldown, enemy) = enemy.fire(Position::new(3.0, 0.0), 500000);
+        let (after_cooldown, _) = enemy.fire(Position::new(3.0, 0.0), 500000);
+
+        assert_that!(first.is_some()).is_true();
+        assert_that!(during_cooldown.is_none()).is_true();
+        assert_that!(after_cooldown.is_some()).is_true();
+    }
+
+    fn build_ranged_attack() -> RangedAttack {
+        RangedAttack::new(TextureIndex::new(1), SpeedStats::new(5.0), 10, 1.0, 5.0)
+    }
+
     fn build_enemy(health: u32) -> Enemy {
         Enemy::new(TextureIndex::new(0), TextureIndex::new(0), Position::new(0.0, 0.0), health)
     }
@@ -1,3 +1,6 @@
 pub  mod actor;
+pub mod pickup;
+pub mod projectile;
 pub mod shoot;
+pub mod spawner;
 mod physics;
@@ -1,5 +1,5 @@
 use crate::domain::actors::actor::SpeedStats;
-use crate::domain::maths::{Angle, Move};
+use crate::domain::maths::{Angle, ANGLE_RIGHT, Move};
 
 #[derive(Copy, Clone)]
 pub struct Acceleration {
@@ -13,6 +13,14 @@ pub struct Speed {
     units_per_seconds: f32,
 }
 
+#[derive(Copy, Clone)]
+pub struct Knockback {
+    orientation: Angle,
+    distance: f32,
+    elapsed_in_microseconds: u128,
+    duration_in_microseconds: u128,
+}
+
 impl Speed {
     pub fn new(orientation: Angle, units_per_seconds: f32) -> Self {
         Self {
@@ -105,6 +113,44 @@ impl Acceleration {
     }
 }
 
+impl Knockback {
+    pub fn new(orientation: Angle, distance: f32, duration_in_microseconds: u128) -> Self {
+        Self {
+            orientation,
+            distance,
+            elapsed_in_microseconds: 0,
+            duration_in_microseconds,
+        }
+    }
+
+    pub fn none() -> Self {
+        Self::new(ANGLE_RIGHT, 0.0, 0)
+    }
+
+    pub fn elapsed(&self, microseconds_elapsed: u128) -> (Move, Self) {
+        let elapsed_in_microseconds = (self.elapsed_in_microseconds + microseconds_elapsed).min(self.duration_in_microseconds);
+        let distance = self.travelled(elapsed_in_microseconds) - self.travelled(self.elapsed_in_microseconds);
+
+        let next = Self {
+            orientation: self.orientation,
+            distance: self.distance,
+            elapsed_in_microseconds,
+            duration_in_microseconds: self.duration_in_microseconds,
+        };
+
+        (Move::new(self.orientation, distance), next)
+    }
+
+    fn travelled(&self, elapsed_in_microseconds: u128) -> f32 {
+        if self.duration_in_microseconds == 0 {
+            return 0.0;
+        }
+
+        let remaining = 1.0 - elapsed_in_microseconds as f32 / self.duration_in_microseconds as f32;
+        self.distance * (1.0 - remaining * remaining)
+    }
+}
+
 #[cfg(test)]
 mod fn_speed_stats {
     use spectral::prelude::*;
@@ -152,4 +198,40 @@ mod fn_speed_stats {
 
         assert_that!(result.units_per_seconds()).is_equal_to(10.0);
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod knockback_test {
+    use spectral::prelude::*;
+
+    use crate::domain::actors::physics::Knockback;
+    use crate::domain::maths::ANGLE_RIGHT;
+
+    #[test]
+    fn knockback_should_travel_full_distance_over_its_duration() {
+        let knockback = Knockback::new(ANGLE_RIGHT, 2.0, 100000);
+
+        let (first, knockback) = knockback.elapsed(30000);
+        let (second, knockback) = knockback.elapsed(30000);
+        let (third, _) = knockback.elapsed(100000);
+
+        assert_that!(first.distance() + second.distance() + third.distance()).is_close_to(2.0, 0.001);
+    }
+
+    #[test]
+    fn knockback_should_decay_over_time() {
+        let knockback = Knockback::new(ANGLE_RIGHT, 2.0, 100000);
+
+        let (first, knockback) = knockback.elapsed(10000);
+        let (second, _) = knockback.elapsed(10000);
+
+        assert_that!(second.distance()).is_less_than(first.distance());
+    }
+
+    #[test]
+    fn no_knockback_should_not_move() {
+        let (moves, _) = Knockback::none().elapsed(10000);
+
+        assert_that!(moves.distance()).is_equal_to(0.0);
+    }
+}
@@ -0,0 +1,51 @@
+use crate::domain::actors::shoot::WeaponConfiguration;
+use crate::domain::topology::coord::Position;
+use crate::domain::topology::index::TextureIndex;
+use crate::domain::topology::map::KeyColor;
+
+#[derive(Clone)]
+pub enum PickupKind {
+    Weapon(Box<WeaponConfiguration>),
+    Health(u32),
+    Ammo(u32),
+    Key(KeyColor),
+}
+
+#[derive(Clone)]
+pub struct PickupType {
+    texture: TextureIndex,
+    kind: PickupKind,
+}
+
+#[derive(Clone)]
+pub struct Pickup {
+    pickup_type: PickupType,
+    position: Position,
+}
+
+impl PickupType {
+    pub fn new(texture: TextureIndex, kind: PickupKind) -> Self {
+        Self { texture, kind }
+    }
+
+    pub fn build(&self, position: Position) -> Pickup {
+        Pickup {
+            pickup_type: self.clone(),
+            position,
+        }
+    }
+}
+
+impl Pickup {
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    pub fn texture(&self) -> TextureIndex {
+        self.pickup_type.texture
+    }
+
+    pub fn kind(&self) -> &PickupKind {
+        &self.pickup_type.kind
+    }
+}
@@ -0,0 +1,114 @@
+use crate::domain::actors::actor::SpeedStats;
+use crate::domain::maths::{Angle, Move};
+use crate::domain::topology::coord::Position;
+use crate::domain::topology::index::TextureIndex;
+
+#[derive(Copy, Clone)]
+pub struct Projectile {
+    position: Position,
+    orientation: Angle,
+    speed: SpeedStats,
+    damage: u32,
+    texture: TextureIndex,
+    friendly: bool,
+    knockback: f32,
+}
+
+impl Projectile {
+    pub fn new(position: Position, orientation: Angle, speed: SpeedStats, damage: u32, texture: TextureIndex) -> Self {
+        Self {
+            position,
+            orientation,
+            speed,
+            damage,
+            texture,
+            friendly: false,
+            knockback: 0.0,
+        }
+    }
+
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    pub fn orientation(&self) -> Angle {
+        self.orientation
+    }
+
+    pub fn damage(&self) -> u32 {
+        self.damage
+    }
+
+    pub fn texture(&self) -> TextureIndex {
+        self.texture
+    }
+
+    pub fn friendly(&self) -> bool {
+        self.friendly
+    }
+
+    pub fn knockback(&self) -> f32 {
+        self.knockback
+    }
+
+    pub fn next_move(&self, microseconds_elapsed: u128) -> Move {
+        Move::new(self.orientation, self.speed.to_units(microseconds_elapsed))
+    }
+
+    pub fn with_position(&self, position: Position) -> Self {
+        Self {
+            position,
+            orientation: self.orientation,
+            speed: self.speed,
+            damage: self.damage,
+            texture: self.texture,
+            friendly: self.friendly,
+            knockback: self.knockback,
+        }
+    }
+
+    pub fn with_friendly(&self, friendly: bool) -> Self {
+        Self {
+            position: self.position,
+            orientation: self.orientation,
+            speed: self.speed,
+            damage: self.damage,
+            texture: self.texture,
+            friendly,
+            knockback: self.knockback,
+        }
+    }
+
+    pub fn with_knockback(&self, knockback: f32) -> Self {
+        Self {
+            position: self.position,
+            orientation: self.orientation,
+            speed: self.speed,
+            damage: self.damage,
+            texture: self.texture,
+            friendly: self.friendly,
+            knockback,
+        }
+    }
+}
+
+#[cfg(test)]
+mod projectile_test {
+    use spectral::prelude::*;
+
+    use crate::domain::actors::actor::SpeedStats;
+    use crate::domain::actors::projectile::Projectile;
+    use crate::domain::maths::ANGLE_UP;
+    use crate::domain::topology::coord::Position;
+    use crate::domain::topology::index::TextureIndex;
+
+    #[test]
+    fn projectile_should_move_toward_its_orientation() {
+        let projectile = Projectile::new(Position::new(1.0, 1.0), ANGLE_UP, SpeedStats::new(4.0), 10, TextureIndex::new(0));
+
+        let position = projectile.position().apply_force(projectile.next_move(500000));
+
+        assert_that!(position.x()).is_close_to(1.0, 0.001);
+        assert_that!(position.y()).is_close_to(3.0, 0.001);
+    }
+}
@@ -1,4 +1,11 @@
+use std::f32::consts::PI;
+
+use crate::domain::maths::Angle;
 use crate::domain::topology::index::TextureIndex;
+use crate::domain::ui::view_model::ViewModel;
+
+const DEFAULT_MELEE_RANGE: f32 = 0.5;
+const DEFAULT_HITSCAN_RADIUS: f32 = 0.5;
 
 #[derive(Clone, Copy)]
 pub struct AnimationStep {
@@ -6,6 +13,13 @@ pub struct AnimationStep {
     texture: TextureIndex,
 }
 
+#[derive(Clone, Copy, Debug)]
+pub enum WeaponKind {
+    Melee { range: f32, angle: Angle },
+    Hitscan { range: f32, radius: f32 },
+    Projectile { speed: f32, texture: TextureIndex },
+}
+
 #[derive(Clone, Copy)]
 pub struct WeaponConfiguration {
     startup: AnimationStep,
@@ -13,6 +27,11 @@ pub struct WeaponConfiguration {
     recovery: AnimationStep,
     default: TextureIndex,
     damage: u32,
+    knockback: f32,
+    view_model: ViewModel,
+    muzzle_flash: Option<TextureIndex>,
+    kind: WeaponKind,
+    ammo: Option<u32>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -29,6 +48,7 @@ pub struct Weapon {
     configuration: WeaponConfiguration,
     already_hit: bool,
     elapsed_in_microseconds: u128,
+    ammo: Option<u32>,
 }
 
 impl WeaponConfiguration {
@@ -39,6 +59,86 @@ impl WeaponConfiguration {
             active,
             recovery,
             damage,
+            knockback: 0.0,
+            view_model: ViewModel::fullscreen(),
+            muzzle_flash: None,
+            kind: WeaponKind::default(),
+            ammo: None,
+        }
+    }
+
+    pub fn with_knockback(&self, knockback: f32) -> Self {
+        Self {
+            default: self.default,
+            startup: self.startup,
+            active: self.active,
+            recovery: self.recovery,
+            damage: self.damage,
+            knockback,
+            view_model: self.view_model,
+            muzzle_flash: self.muzzle_flash,
+            kind: self.kind,
+            ammo: self.ammo,
+        }
+    }
+
+    pub fn with_view_model(&self, view_model: ViewModel) -> Self {
+        Self {
+            default: self.default,
+            startup: self.startup,
+            active: self.active,
+            recovery: self.recovery,
+            damage: self.damage,
+            knockback: self.knockback,
+            view_model,
+            muzzle_flash: self.muzzle_flash,
+            kind: self.kind,
+            ammo: self.ammo,
+        }
+    }
+
+    pub fn with_muzzle_flash(&self, muzzle_flash: TextureIndex) -> Self {
+        Self {
+            default: self.default,
+            startup: self.startup,
+            active: self.active,
+            recovery: self.recovery,
+            damage: self.damage,
+            knockback: self.knockback,
+            view_model: self.view_model,
+            muzzle_flash: Some(muzzle_flash),
+            kind: self.kind,
+            ammo: self.ammo,
+        }
+    }
+
+    pub fn with_kind(&self, kind: WeaponKind) -> Self {
+        Self {
+            default: self.default,
+            startup: self.startup,
+            active: self.active,
+            recovery: self.recovery,
+            damage: self.damage,
+            knockback: self.knockback,
+            view_model: self.view_model,
+            muzzle_flash: self.muzzle_flash,
+            kind,
+            ammo: self.ammo,
+        }
+    }
+
+    pub fn with_ammo(&self, ammo: u32) -> Self {
+        Self {
+            default: self.default,
+            startup: self.startup,
+            active: self.active,
+            recovery: self.recovery,
+            damage: self.damage,
+            knockback: self.knockback,
+            view_model: self.view_model,
+            muzzle_flash: self.muzzle_flash,
+            kind: self.kind,
+            ammo: Some(ammo),
         }
     }
 
@@ -47,13 +147,17 @@ impl WeaponConfiguration {
             ShootState::Startup
         } else if elapsed_in_microseconds < self.startup.duration_in_microseconds + self.active.duration_in_microseconds {
             ShootState::Active
-        } else if elapsed_in_microseconds < self.startup.duration_in_microseconds + self.active.duration_in_microseconds + self.recovery.duration_in_microseconds {
+        } else if elapsed_in_microseconds < self.duration_in_microseconds() {
             ShootState::Recovery
         } else {
             ShootState::Finished
         }
     }
 
+    pub fn duration_in_microseconds(&self) -> u128 {
+        self.startup.duration_in_microseconds + self.active.duration_in_microseconds + self.recovery.duration_in_microseconds
+    }
+
 
     pub fn default(&self) -> TextureIndex {
         self.default
@@ -70,6 +174,36 @@ impl WeaponConfiguration {
     pub fn damage(&self) -> u32 {
         self.damage
     }
+    pub fn knockback(&self) -> f32 {
+        self.knockback
+    }
+    pub fn view_model(&self) -> ViewModel {
+        self.view_model
+    }
+    pub fn muzzle_flash(&self) -> Option<TextureIndex> {
+        self.muzzle_flash
+    }
+    pub fn kind(&self) -> WeaponKind {
+        self.kind
+    }
+    pub fn ammo(&self) -> Option<u32> {
+        self.ammo
+    }
+}
+
+impl WeaponKind {
+    pub fn hitscan(range: f32) -> Self {
+        WeaponKind::Hitscan { range, radius: DEFAULT_HITSCAN_RADIUS }
+    }
+}
+
+impl Default for WeaponKind {
+    fn default() -> Self {
+        WeaponKind::Melee {
+            range: DEFAULT_MELEE_RANGE,
+            angle: Angle::new(PI / 4.0),
+        }
+    }
 }
 
 impl AnimationStep {
@@ -94,19 +228,34 @@ impl Weapon {
         Self {
             configuration,
             already_hit: false,
-            elapsed_in_microseconds: 0,
+            // A new weapon is at rest until the player fires it
+            elapsed_in_microseconds: configuration.duration_in_microseconds(),
+            ammo: configuration.ammo(),
         }
     }
 
     pub fn action(&mut self) {
         let current_state = self.state();
 
-        if current_state == ShootState::Finished {
+        if current_state == ShootState::Finished && self.ammo != Some(0) {
             self.already_hit = false;
             self.elapsed_in_microseconds = 0;
+            self.ammo = self.ammo.map(|ammo| ammo - 1);
         }
     }
 
+    pub fn ammo(&self) -> Option<u32> {
+        self.ammo
+    }
+
+    pub fn add_ammo(&mut self, amount: u32) {
+        self.ammo = self.ammo.map(|ammo| ammo.saturating_add(amount));
+    }
+
+    pub fn restore_ammo(&mut self, amount: u32) {
+        self.ammo = self.ammo.map(|_| amount);
+    }
+
     pub fn notify_hit(&mut self) {
         self.already_hit = true;
     }
@@ -175,6 +324,43 @@ mod weapon_test {
         assert_eq!(weapon.state(), ShootState::Active);
     }
 
+    #[test]
+    fn finite_ammo_weapon_should_not_start_when_empty() {
+        let conf = build_configuration(0.1, 0.1, 0.1).with_ammo(1);
+        let mut weapon = Weapon::new(conf);
+        weapon.notify_elapsed(1000000);
+
+        weapon.action();
+        weapon.notify_elapsed(1000000);
+        weapon.action();
+
+        assert_eq!(weapon.ammo(), Some(0));
+        assert_eq!(weapon.state(), ShootState::Finished);
+    }
+
+    #[test]
+    fn infinite_ammo_weapon_should_always_start() {
+        let conf = build_configuration(0.1, 0.1, 0.1);
+        let mut weapon = Weapon::new(conf);
+        weapon.notify_elapsed(1000000);
+
+        weapon.action();
+        weapon.notify_elapsed(1000000);
+        weapon.action();
+
+        assert_eq!(weapon.ammo(), None);
+        assert_eq!(weapon.state(), ShootState::Startup);
+    }
+
+    #[test]
+    fn added_ammo_should_not_overflow() {
+        let mut weapon = Weapon::new(build_configuration(0.1, 0.1, 0.1).with_ammo(u32::MAX - 1));
+
+        weapon.add_ammo(5);
+
+        assert_eq!(weapon.ammo(), Some(u32::MAX));
+    }
+
     fn build_configuration(startup: f32, active: f32, recovery: f32) -> WeaponConfiguration {
         let texture: TextureIndex = TextureIndex::new(0);
 
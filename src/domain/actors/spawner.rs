@@ -0,0 +1,111 @@
+use crate::domain::actors::actor::Enemy;
+use crate::domain::topology::coord::Position;
+use crate::domain::topology::map::EnemyType;
+
+#[derive(Copy, Clone)]
+pub struct SpawnerType {
+    enemy: EnemyType,
+    interval_in_microseconds: u128,
+    max_count: u32,
+}
+
+#[derive(Copy, Clone)]
+pub struct Spawner {
+    spawner_type: SpawnerType,
+    position: Position,
+    elapsed_in_microseconds: u128,
+    spawned: u32,
+}
+
+impl SpawnerType {
+    pub fn new(enemy: EnemyType, interval_in_seconds: f32, max_count: u32) -> Self {
+        Self {
+            enemy,
+            interval_in_microseconds: (interval_in_seconds * 1000000.0) as u128,
+            max_count,
+        }
+    }
+
+    pub fn build(&self, position: Position) -> Spawner {
+        Spawner {
+            spawner_type: *self,
+            position,
+            elapsed_in_microseconds: 0,
+            spawned: 0,
+        }
+    }
+}
+
+impl Spawner {
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    pub fn spawned(&self) -> u32 {
+        self.spawned
+    }
+
+    pub fn with_spawned(&self, spawned: u32) -> Self {
+        Self {
+            spawner_type: self.spawner_type,
+            position: self.position,
+            elapsed_in_microseconds: self.elapsed_in_microseconds,
+            spawned,
+        }
+    }
+
+    pub fn release(&self) -> Self {
+        self.with_spawned(self.spawned.saturating_sub(1))
+    }
+
+    pub fn build_enemy(&self, position: Position) -> Enemy {
+        self.spawner_type.enemy.build(position)
+    }
+
+    pub fn notify_elapsed(&self, microseconds: u128) -> (Option<Enemy>, Self) {
+        if self.spawned >= self.spawner_type.max_count {
+            return (None, *self);
+        }
+
+        let elapsed = self.elapsed_in_microseconds + microseconds;
+        let (enemy, elapsed_in_microseconds, spawned) = if elapsed >= self.spawner_type.interval_in_microseconds {
+            let enemy = self.build_enemy(self.position);
+            (Some(enemy), elapsed - self.spawner_type.interval_in_microseconds, self.spawned + 1)
+        } else {
+            (None, elapsed, self.spawned)
+        };
+
+        let spawner = Self {
+            spawner_type: self.spawner_type,
+            position: self.position,
+            elapsed_in_microseconds,
+            spawned,
+        };
+
+        (enemy, spawner)
+    }
+}
+
+#[cfg(test)]
+mod spawner_test {
+    use spectral::prelude::*;
+
+    use crate::domain::actors::spawner::SpawnerType;
+    use crate::domain::topology::coord::Position;
+    use crate::domain::topology::index::TextureIndex;
+    use crate::domain::topology::map::EnemyType;
+
+    #[test]
+    fn spawner_should_emit_an_enemy_at_its_position_after_an_interval() {
+        let spawner = SpawnerType::new(EnemyType::new(TextureIndex::new(0), TextureIndex::new(0), 50), 1.0, 3)
+            .build(Position::new(2.5, 3.5));
+
+        let (nothing, spawner) = spawner.notify_elapsed(600000);
+        let (enemy, _) = spawner.notify_elapsed(600000);
+
+        assert_that!(nothing.is_none()).is_true();
+        assert_that!(enemy.map(|enemy| (enemy.position().x(), enemy.position().y(), enemy.health())))
+            .is_some()
+            .is_equal_to((2.5, 3.5, 50));
+    }
+}
@@ -4,11 +4,15 @@ use dyn_clone::DynClone;
 
 use crate::domain::actors::actor::SpeedStats;
 use crate::domain::maths::between;
+use crate::domain::save::SavedDoor;
+use crate::domain::topology::coord::Position;
 use crate::domain::topology::door::{LateralOpening, Openable};
 use crate::domain::topology::map::{Map, Tile};
 
 pub struct Actions {
     paving: Vec<Vec<Box<dyn ActionState>>>,
+    dynamic_cells: Vec<(usize, usize)>,
+    animated_cells: usize,
     width: i16,
     height: i16,
 }
@@ -18,6 +22,10 @@ pub trait ActionState: Sync + DynClone {
     fn trigger(&self) -> Box<dyn ActionState>;
     fn openable(&self) -> Box<dyn Openable>;
     fn activated_percentage(&self) -> f32;
+    fn blocking(&self) -> bool;
+    fn activated(&self) -> bool;
+    fn auto_open_radius(&self) -> Option<f32>;
+    fn restore(&self, activated: bool, opening_percentage: f32) -> Box<dyn ActionState>;
 }
 dyn_clone::clone_trait_object!(ActionState);
 
@@ -31,21 +39,34 @@ pub struct LinearActionState {
     opening_speed: SpeedStats,
     activated: bool,
     opening_percentage: f32,
+    passable_threshold: f32,
+    auto_open_radius: Option<f32>,
+    auto_close_in_microseconds: Option<u128>,
+    open_in_microseconds: u128,
     openable: Box<dyn Openable>,
 }
 
+#[derive(Clone)]
+pub struct EasedActionState {
+    linear: Box<dyn ActionState>,
+}
+
 #[derive(Debug, Copy, Clone)]
 pub struct NothingActionState {}
 
 impl Actions {
     pub fn new(map: &Map) -> Self {
         let mut paving = vec![];
+        let mut dynamic_cells = vec![];
         for x in 0..map.width() {
             let mut line = vec![];
             for y in 0..map.height() {
                 let current_paving = map.paving_at(x, y).unwrap();
                 match current_paving {
-                    Tile::DYNAMIC(_, _, state_generator) => line.push(state_generator.build()),
+                    Tile::DYNAMIC(_, _, state_generator) => {
+                        line.push(state_generator.build());
+                        dynamic_cells.push((x as usize, y as usize));
+                    }
                     _ => line.push(Box::new(NothingActionState::new()))
                 }
             }
@@ -54,6 +75,8 @@ impl Actions {
 
         Self {
             paving,
+            dynamic_cells,
+            animated_cells: 0,
             width: map.width(),
             height: map.height(),
         }
@@ -77,12 +100,61 @@ impl Actions {
     }
 
     pub fn notify_elapsed(&mut self, microseconds: u128) {
-        for x in 0..self.width {
-            for y in 0..self.height {
-                self.paving[x as usize][y as usize] = self.paving[x as usize][y as usize].elapsed(microseconds);
+        for (x, y) in &self.dynamic_cells {
+            self.paving[*x][*y] = self.paving[*x][*y].elapsed(microseconds);
+        }
+        self.animated_cells = self.dynamic_cells.len();
+    }
+
+    pub fn notify_elapsed_around(&mut self, microseconds: u128, position: Position, distance: f32) {
+        self.animated_cells = 0;
+        for (x, y) in &self.dynamic_cells {
+            let center = Position::new(*x as f32 + 0.5, *y as f32 + 0.5);
+            if position.distance(&center) <= distance {
+                self.paving[*x][*y] = self.paving[*x][*y].elapsed(microseconds);
+                self.animated_cells += 1;
             }
         }
     }
+
+    pub fn notify_approach(&mut self, previous: Option<Position>, position: Position, can_open: impl Fn(i16, i16) -> bool) {
+        for (x, y) in &self.dynamic_cells {
+            let state = &self.paving[*x][*y];
+            if let Some(radius) = state.auto_open_radius() {
+                let center = Position::new(*x as f32 + 0.5, *y as f32 + 0.5);
+                let was_in_range = previous.map(|previous| previous.distance(&center) <= radius).unwrap_or(false);
+                let in_range = position.distance(&center) <= radius;
+                let entering = in_range && !was_in_range && !state.activated() && can_open(*x as i16, *y as i16);
+                let leaving = !in_range && was_in_range && state.activated();
+                if entering || leaving {
+                    self.paving[*x][*y] = state.trigger();
+                }
+            }
+        }
+    }
+
+    pub fn animated_cells(&self) -> usize {
+        self.animated_cells
+    }
+
+    pub fn doors(&self) -> Vec<SavedDoor> {
+        self.dynamic_cells.iter()
+            .map(|(x, y)| {
+                let state = &self.paving[*x][*y];
+                SavedDoor::new(*x as i16, *y as i16, state.activated(), state.activated_percentage())
+            })
+            .collect()
+    }
+
+    pub fn restore(&mut self, door: &SavedDoor) -> Result<(), String> {
+        let (x, y) = (door.x() as usize, door.y() as usize);
+        if door.x() < 0 || door.y() < 0 || !self.dynamic_cells.contains(&(x, y)) {
+            return Err(format!("There is no door at ({}, {})", door.x(), door.y()));
+        }
+
+        self.paving[x][y] = self.paving[x][y].restore(door.activated(), door.opening_percentage());
+        Ok(())
+    }
 }
 
 impl Debug for Box<dyn ActionState> {
@@ -108,10 +180,53 @@ impl LinearActionState {
         Self {
             activated: false,
             opening_percentage: 0.0,
+            passable_threshold: 1.0,
+            auto_open_radius: None,
+            auto_close_in_microseconds: None,
+            open_in_microseconds: 0,
             openable,
             opening_speed,
         }
     }
+
+    pub fn with_auto_open(&self, radius: f32) -> Self {
+        Self {
+            activated: self.activated,
+            opening_percentage: self.opening_percentage,
+            passable_threshold: self.passable_threshold,
+            auto_open_radius: Some(radius),
+            auto_close_in_microseconds: self.auto_close_in_microseconds,
+            open_in_microseconds: self.open_in_microseconds,
+            openable: self.openable.clone(),
+            opening_speed: self.opening_speed,
+        }
+    }
+
+    pub fn with_passable_threshold(&self, passable_threshold: f32) -> Self {
+        Self {
+            activated: self.activated,
+            opening_percentage: self.opening_percentage,
+            passable_threshold,
+            auto_open_radius: self.auto_open_radius,
+            auto_close_in_microseconds: self.auto_close_in_microseconds,
+            open_in_microseconds: self.open_in_microseconds,
+            openable: self.openable.clone(),
+            opening_speed: self.opening_speed,
+        }
+    }
+
+    pub fn with_auto_close(&self, delay_in_seconds: f32) -> Self {
+        Self {
+            activated: self.activated,
+            opening_percentage: self.opening_percentage,
+            passable_threshold: self.passable_threshold,
+            auto_open_radius: self.auto_open_radius,
+            auto_close_in_microseconds: Some((delay_in_seconds * 1000000.0) as u128),
+            open_in_microseconds: self.open_in_microseconds,
+            openable: self.openable.clone(),
+            opening_speed: self.opening_speed,
+        }
+    }
 }
 
 impl ActionState for LinearActionState {
@@ -121,12 +236,25 @@ impl ActionState for LinearActionState {
 
         let new_percentage = between(0.0, self.opening_percentage + increment, 1.0);
 
+        let open_in_microseconds = if self.activated && self.opening_percentage >= 1.0 {
+            self.open_in_microseconds + microseconds
+        } else {
+            0
+        };
+        let expired = self.auto_close_in_microseconds
+            .map(|delay| open_in_microseconds >= delay)
+            .unwrap_or(false);
+
         Box::new(
             Self {
                 opening_speed: self.opening_speed,
-                activated: self.activated,
+                activated: self.activated && !expired,
                 openable: self.openable.clone(),
                 opening_percentage: new_percentage,
+                passable_threshold: self.passable_threshold,
+                auto_open_radius: self.auto_open_radius,
+                auto_close_in_microseconds: self.auto_close_in_microseconds,
+                open_in_microseconds: if expired { 0 } else { open_in_microseconds },
             }
         )
     }
@@ -139,6 +267,10 @@ impl ActionState for LinearActionState {
                 activated: !self.activated,
                 openable: self.openable.clone(),
                 opening_percentage: self.opening_percentage,
+                passable_threshold: self.passable_threshold,
+                auto_open_radius: self.auto_open_radius,
+                auto_close_in_microseconds: self.auto_close_in_microseconds,
+                open_in_microseconds: 0,
             }
         )
     }
@@ -151,6 +283,82 @@ impl ActionState for LinearActionState {
     fn activated_percentage(&self) -> f32 {
         self.opening_percentage
     }
+
+    fn blocking(&self) -> bool {
+        self.opening_percentage < self.passable_threshold
+    }
+
+    fn activated(&self) -> bool {
+        self.activated
+    }
+
+    fn auto_open_radius(&self) -> Option<f32> {
+        self.auto_open_radius
+    }
+
+    fn restore(&self, activated: bool, opening_percentage: f32) -> Box<dyn ActionState> {
+        Box::new(
+            Self {
+                opening_speed: self.opening_speed,
+                activated,
+                openable: self.openable.clone(),
+                opening_percentage: between(0.0, opening_percentage, 1.0),
+                passable_threshold: self.passable_threshold,
+                auto_open_radius: self.auto_open_radius,
+                auto_close_in_microseconds: self.auto_close_in_microseconds,
+                open_in_microseconds: 0,
+            }
+        )
+    }
+}
+
+impl EasedActionState {
+    pub fn new(linear: Box<dyn ActionState>) -> Self {
+        Self { linear }
+    }
+}
+
+impl ActionState for EasedActionState {
+    fn elapsed(&self, microseconds: u128) -> Box<dyn ActionState> {
+        Box::new(Self::new(self.linear.elapsed(microseconds)))
+    }
+
+    fn trigger(&self) -> Box<dyn ActionState> {
+        Box::new(Self::new(self.linear.trigger()))
+    }
+
+    fn openable(&self) -> Box<dyn Openable> {
+        self.linear.openable()
+    }
+
+    fn activated_percentage(&self) -> f32 {
+        smoothstep(self.linear.activated_percentage())
+    }
+
+    fn blocking(&self) -> bool {
+        self.linear.blocking()
+    }
+
+    fn activated(&self) -> bool {
+        self.linear.activated()
+    }
+
+    fn auto_open_radius(&self) -> Option<f32> {
+        self.linear.auto_open_radius()
+    }
+
+    fn restore(&self, activated: bool, opening_percentage: f32) -> Box<dyn ActionState> {
+        let eased = between(0.0, opening_percentage, 1.0);
+        Box::new(Self::new(self.linear.restore(activated, inverse_smoothstep(eased))))
+    }
+}
+
+fn smoothstep(x: f32) -> f32 {
+    x * x * (3.0 - 2.0 * x)
+}
+
+fn inverse_smoothstep(y: f32) -> f32 {
+    0.5 - ((1.0 - 2.0 * y).asin() / 3.0).sin()
 }
 
 impl NothingActionState {
@@ -181,18 +389,37 @@ impl ActionState for NothingActionState {
     fn activated_percentage(&self) -> f32 {
         0.0
     }
+
+    fn blocking(&self) -> bool {
+        true
+    }
+
+    fn activated(&self) -> bool {
+        false
+    }
+
+    fn auto_open_radius(&self) -> Option<f32> {
+        None
+    }
+
+    fn restore(&self, _activated: bool, _opening_percentage: f32) -> Box<dyn ActionState> {
+        Box::new(Self {})
+    }
 }
 
 #[cfg(test)]
 mod actions_test {
     use spectral::prelude::*;
 
-    use crate::domain::control::actions::Actions;
-    use crate::domain::topology::map::map_test::build_map;
+    use crate::domain::actors::actor::SpeedStats;
+    use crate::domain::control::actions::{Actions, ActionState, LinearActionState};
+    use crate::domain::topology::coord::Position;
+    use crate::domain::topology::door::LateralOpening;
+    use crate::domain::topology::map::map_test::{build_map, DOOR_OPENING_SPEED_IN_UNITS_PER_SECONDS};
 
     #[test]
     fn should_read_paving_information() {
-        let map = build_map("###\n# #\n# #\n###");
+        let map = build_map("###\n#r#\n# #\n###");
 
         let actions = Actions::new(&map);
 
@@ -215,7 +442,7 @@ mod actions_test {
 
     #[test]
     fn should_not_get_paving_information_on_tiles_with_x_coordinate_bigger_than_width_map() {
-        let map = build_map("  \n  ");
+        let map = build_map("  \nr ");
         let actions = Actions::new(&map);
 
         let state = actions.state_at(0, 2);
@@ -224,7 +451,7 @@ mod actions_test {
 
     #[test]
     fn should_not_get_paving_information_on_tiles_with_x_coordinate_bigger_than_height_map() {
-        let map = build_map("  \n  ");
+        let map = build_map("  \nr ");
         let actions = Actions::new(&map);
 
         let state = actions.state_at(2, 0);
@@ -233,7 +460,7 @@ mod actions_test {
 
     #[test]
     fn should_not_get_paving_information_on_tiles_with_negative_x_coordinate() {
-        let map = build_map("  \n  ");
+        let map = build_map("  \nr ");
         let actions = Actions::new(&map);
 
         let state = actions.state_at(-1, 0);
@@ -242,15 +469,106 @@ mod actions_test {
 
     #[test]
     fn should_not_get_paving_information_on_tiles_with_negative_y_coordinate() {
-        let map = build_map("  \n  ");
+        let map = build_map("  \nr ");
         let actions = Actions::new(&map);
 
         let state = actions.state_at(0, -1);
         assert_that!(state).is_none()
     }
+
+    #[test]
+    fn simultaneous_doors_should_animate_like_a_single_door() {
+        let map = build_map("#D#\n#r#\n#D#");
+        let mut actions = Actions::new(&map);
+        let mut expected: Box<dyn ActionState> = Box::new(LinearActionState::new(
+            SpeedStats::new(DOOR_OPENING_SPEED_IN_UNITS_PER_SECONDS),
+            Box::new(LateralOpening::default()),
+        ));
+
+        actions.activate(1, 0);
+        actions.activate(1, 2);
+        expected = expected.trigger();
+
+        for _ in 0..10 {
+            actions.notify_elapsed(16000);
+            expected = expected.elapsed(16000);
+
+            assert_that!(actions.state_at(1, 0).unwrap().activated_percentage()).is_equal_to(expected.activated_percentage());
+            assert_that!(actions.state_at(1, 2).unwrap().activated_percentage()).is_equal_to(expected.activated_percentage());
+        }
+
+        assert_that!(expected.activated_percentage()).is_greater_than(0.0);
+        assert_that!(actions.state_at(1, 1).unwrap().activated_percentage()).is_equal_to(0.0);
+    }
+
+    #[test]
+    fn distant_door_should_be_frozen_until_approached() {
+        let map = build_map("Dr        ");
+        let mut actions = Actions::new(&map);
+        actions.activate(0, 0);
+
+        actions.notify_elapsed_around(100000, Position::new(9.5, 0.5), 3.0);
+        assert_that!(actions.state_at(0, 0).unwrap().activated_percentage()).is_equal_to(0.0);
+        assert_that!(actions.animated_cells()).is_equal_to(0);
+
+        actions.notify_elapsed_around(100000, Position::new(2.5, 0.5), 3.0);
+        assert_that!(actions.state_at(0, 0).unwrap().activated_percentage()).is_close_to(0.3, 0.001);
+        assert_that!(actions.animated_cells()).is_equal_to(1);
+    }
 }
 
 
+#[cfg(test)]
+mod eased_action_state_test {
+    use spectral::prelude::*;
+
+    use crate::domain::actors::actor::SpeedStats;
+    use crate::domain::control::actions::{ActionState, EasedActionState, LinearActionState};
+    use crate::domain::topology::door::LateralOpening;
+
+    #[test]
+    fn eased_door_should_differ_from_linear_door_while_opening() {
+        let linear = LinearActionState::new(SpeedStats::new(1.0), Box::new(LateralOpening::default()));
+        let eased = EasedActionState::new(Box::new(linear.clone()));
+
+        let linear = linear.trigger().elapsed(250000);
+        let eased = eased.trigger().elapsed(250000);
+
+        assert_that!(linear.activated_percentage()).is_close_to(0.25, 0.01);
+        assert_that!(eased.activated_percentage()).is_close_to(0.156, 0.01);
+    }
+
+    #[test]
+    fn eased_door_should_match_linear_door_at_both_ends() {
+        let linear = LinearActionState::new(SpeedStats::new(1.0), Box::new(LateralOpening::default()));
+        let eased = EasedActionState::new(Box::new(linear));
+
+        assert_that!(eased.activated_percentage()).is_equal_to(0.0);
+        assert_that!(eased.trigger().elapsed(2000000).activated_percentage()).is_equal_to(1.0);
+    }
+
+    #[test]
+    fn eased_door_should_keep_its_progress_when_triggered_mid_animation() {
+        let linear = LinearActionState::new(SpeedStats::new(1.0), Box::new(LateralOpening::default()));
+        let eased = EasedActionState::new(Box::new(linear))
+            .trigger()
+            .elapsed(500000)
+            .trigger()
+            .elapsed(250000);
+
+        assert_that!(eased.activated()).is_false();
+        assert_that!(eased.activated_percentage()).is_close_to(0.156, 0.01);
+    }
+
+    #[test]
+    fn restored_eased_door_should_show_the_saved_percentage() {
+        let linear = LinearActionState::new(SpeedStats::new(1.0), Box::new(LateralOpening::default()));
+        let eased = EasedActionState::new(Box::new(linear));
+
+        assert_that!(eased.restore(true, 0.3).activated_percentage()).is_close_to(0.3, 0.001);
+    }
+}
+
 #[cfg(test)]
 mod linear_action_state_test {
     use spectral::prelude::*;
@@ -305,4 +623,30 @@ mod linear_action_state_test {
 
         assert_that!(action.activated_percentage()).is_close_to(0.25, 0.01);
     }
+
+    #[test]
+    fn door_with_auto_close_should_start_closing_after_the_delay() {
+        let open = LinearActionState::new(SpeedStats::new(1.0), Box::new(LateralOpening::default()))
+            .with_auto_close(2.0)
+            .trigger()
+            .elapsed(1000000);
+
+        let before_delay = open.elapsed(1500000).elapsed(250000);
+        let after_delay = open.elapsed(2000000).elapsed(500000);
+
+        assert_that!(before_delay.activated_percentage()).is_equal_to(1.0);
+        assert_that!(after_delay.activated()).is_false();
+        assert_that!(after_delay.activated_percentage()).is_close_to(0.5, 0.01);
+    }
+
+    #[test]
+    fn door_without_auto_close_should_stay_open() {
+        let action = LinearActionState::new(SpeedStats::new(1.0), Box::new(LateralOpening::default()))
+            .trigger()
+            .elapsed(1000000)
+            .elapsed(99999999999);
+
+        assert_that!(action.activated()).is_true();
+        assert_that!(action.activated_percentage()).is_equal_to(1.0);
+    }
 }
@@ -0,0 +1,186 @@
+use crate::domain::control::input::Input;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Modifiers {
+    alt: bool,
+    ctrl: bool,
+    shift: bool,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Chord<K> {
+    key: K,
+    modifiers: Modifiers,
+}
+
+#[derive(Clone)]
+pub struct KeyBindings<K> {
+    held: Vec<(Chord<K>, Input)>,
+    pressed: Vec<(Chord<K>, Input)>,
+}
+
+impl Modifiers {
+    pub fn new(alt: bool, ctrl: bool, shift: bool) -> Self {
+        Self { alt, ctrl, shift }
+    }
+
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    pub fn alt() -> Self {
+        Self::new(true, false, false)
+    }
+
+    pub fn ctrl() -> Self {
+        Self::new(false, true, false)
+    }
+
+    pub fn shift() -> Self {
+        Self::new(false, false, true)
+    }
+
+    pub fn contains(&self, other: Modifiers) -> bool {
+        (self.alt || !other.alt) && (self.ctrl || !other.ctrl) && (self.shift || !other.shift)
+    }
+
+    fn count(&self) -> usize {
+        [self.alt, self.ctrl, self.shift].iter().filter(|modifier| **modifier).count()
+    }
+}
+
+impl<K> Chord<K> {
+    pub fn new(key: K, modifiers: Modifiers) -> Self {
+        Self { key, modifiers }
+    }
+
+    pub fn key(key: K) -> Self {
+        Self::new(key, Modifiers::none())
+    }
+}
+
+impl<K> KeyBindings<K> {
+    pub fn new() -> Self {
+        Self {
+            held: vec![],
+            pressed: vec![],
+        }
+    }
+
+    pub fn bind_held(&mut self, chord: Chord<K>, input: Input) {
+        self.held.push((chord, input));
+    }
+
+    pub fn bind_pressed(&mut self, chord: Chord<K>, input: Input) {
+        self.pressed.push((chord, input));
+    }
+
+    // Every input bound here replaces all the chords previously bound to it
+    pub fn override_with(&mut self, custom: KeyBindings<K>) {
+        let bound: Vec<Input> = custom.held.iter().chain(custom.pressed.iter())
+            .map(|(_, input)| *input)
+            .collect();
+
+        self.held.retain(|(_, input)| !bound.contains(input));
+        self.pressed.retain(|(_, input)| !bound.contains(input));
+        self.held.extend(custom.held);
+        self.pressed.extend(custom.pressed);
+    }
+
+    pub fn try_map_keys<T>(&self, key: impl Fn(&K) -> Result<T, String>) -> Result<KeyBindings<T>, String> {
+        let map = |bindings: &[(Chord<K>, Input)]| bindings.iter()
+            .map(|(chord, input)| Ok((Chord::new(key(&chord.key)?, chord.modifiers), *input)))
+            .collect::<Result<Vec<(Chord<T>, Input)>, String>>();
+
+        Ok(KeyBindings {
+            held: map(&self.held)?,
+            pressed: map(&self.pressed)?,
+        })
+    }
+}
+
+impl<K: PartialEq> KeyBindings<K> {
+    pub fn resolve_held(&self, key: K, modifiers: Modifiers) -> Option<Input> {
+        Self::resolve(&self.held, key, modifiers)
+    }
+
+    pub fn resolve_pressed(&self, key: K, modifiers: Modifiers) -> Option<Input> {
+        Self::resolve(&self.pressed, key, modifiers)
+    }
+
+    fn resolve(bindings: &[(Chord<K>, Input)], key: K, modifiers: Modifiers) -> Option<Input> {
+        bindings.iter()
+            .filter(|(chord, _)| chord.key == key && modifiers.contains(chord.modifiers))
+            .max_by_key(|(chord, _)| chord.modifiers.count())
+            .map(|(_, input)| *input)
+    }
+}
+
+impl<K> Default for KeyBindings<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod bindings_test {
+    use spectral::prelude::*;
+
+    use crate::domain::control::bindings::{Chord, KeyBindings, Modifiers};
+    use crate::domain::control::input::Input;
+
+    #[test]
+    fn chord_should_only_resolve_with_its_modifiers() {
+        let mut bindings = KeyBindings::new();
+        bindings.bind_pressed(Chord::new('s', Modifiers::ctrl()), Input::ShowFps);
+
+        assert_that!(bindings.resolve_pressed('s', Modifiers::ctrl())).is_equal_to(Some(Input::ShowFps));
+        assert_that!(bindings.resolve_pressed('s', Modifiers::none())).is_none();
+    }
+
+    #[test]
+    fn most_specific_chord_should_win() {
+        let mut bindings = KeyBindings::new();
+        bindings.bind_pressed(Chord::key('e'), Input::Action);
+        bindings.bind_pressed(Chord::new('e', Modifiers::alt()), Input::Shoot);
+
+        assert_that!(bindings.resolve_pressed('e', Modifiers::none())).is_equal_to(Some(Input::Action));
+        assert_that!(bindings.resolve_pressed('e', Modifiers::alt())).is_equal_to(Some(Input::Shoot));
+    }
+
+    #[test]
+    fn custom_binding_should_replace_every_default_chord_of_its_input() {
+        let mut bindings = KeyBindings::new();
+        bindings.bind_held(Chord::key('w'), Input::Forward);
+        bindings.bind_held(Chord::key('z'), Input::Forward);
+        bindings.bind_held(Chord::key('s'), Input::Backward);
+        let mut custom = KeyBindings::new();
+        custom.bind_held(Chord::key('i'), Input::Forward);
+
+        bindings.override_with(custom);
+
+        assert_that!(bindings.resolve_held('i', Modifiers::none())).is_equal_to(Some(Input::Forward));
+        assert_that!(bindings.resolve_held('w', Modifiers::none())).is_none();
+        assert_that!(bindings.resolve_held('z', Modifiers::none())).is_none();
+        assert_that!(bindings.resolve_held('s', Modifiers::none())).is_equal_to(Some(Input::Backward));
+    }
+
+    #[test]
+    fn unknown_key_should_fail_the_key_mapping() {
+        let mut bindings = KeyBindings::new();
+        bindings.bind_pressed(Chord::key(String::from("E")), Input::Action);
+        bindings.bind_pressed(Chord::key(String::from("Nope")), Input::Shoot);
+
+        let mapped = bindings.try_map_keys(|name| name.chars().next().filter(|_| name.len() == 1).ok_or_else(|| format!("Unknown key '{}'", name)));
+
+        assert_that!(mapped.err()).is_some();
+    }
+
+    #[test]
+    fn held_keys_should_still_resolve_while_a_modifier_is_held() {
+        let mut bindings = KeyBindings::new();
+        bindings.bind_held(Chord::key('w'), Input::Forward);
+
+        assert_that!(bindings.resolve_held('w', Modifiers::shift())).is_equal_to(Some(Input::Forward));
+    }
+}
@@ -1,5 +1,9 @@
+use std::f32::consts::PI;
+
 use crate::domain::maths::{Angle, ANGLE_0, ANGLE_DOWN, ANGLE_LEFT, ANGLE_RIGHT, ANGLE_UP};
 
+const STICK_TURN_IN_RADIANS_PER_SECOND: f32 = PI;
+
 #[derive(Copy, Clone)]
 pub struct Force {
     orientation: Angle,
@@ -11,6 +15,8 @@ pub struct Force {
 pub struct InputForce {
     movement_speed: f32,
     rotation_speed: f32,
+    sensitivity: f32,
+    inverted_rotation: bool,
 }
 
 impl Force {
@@ -42,6 +48,22 @@ impl Force {
         }
     }
 
+    pub fn with_rotation(&self, rotation: Angle) -> Self {
+        Self {
+            power: self.power,
+            rotation,
+            orientation: self.orientation,
+        }
+    }
+
+    pub fn clamp_power(&self, max: f32) -> Self {
+        Self {
+            power: self.power.min(max),
+            rotation: self.rotation,
+            orientation: self.orientation,
+        }
+    }
+
     pub fn add(&self, force: Force) -> Force {
         let x1 = self.power() * self.orientation().cos();
         let y1 = self.power() * self.orientation().sin();
@@ -73,6 +95,26 @@ impl InputForce {
         Self {
             movement_speed,
             rotation_speed,
+            sensitivity: 1.0,
+            inverted_rotation: false,
+        }
+    }
+
+    pub fn with_sensitivity(&self, sensitivity: f32) -> Self {
+        Self {
+            movement_speed: self.movement_speed,
+            rotation_speed: self.rotation_speed,
+            sensitivity,
+            inverted_rotation: self.inverted_rotation,
+        }
+    }
+
+    pub fn with_inverted_rotation(&self) -> Self {
+        Self {
+            movement_speed: self.movement_speed,
+            rotation_speed: self.rotation_speed,
+            sensitivity: self.sensitivity,
+            inverted_rotation: true,
         }
     }
 
@@ -88,20 +130,41 @@ impl InputForce {
         self.movement_to_force(ANGLE_UP)
     }
 
-    pub fn state_right(&self) -> Force {
+    pub fn strafe_right(&self) -> Force {
         self.movement_to_force(ANGLE_DOWN)
     }
 
+    pub fn movement_speed(&self) -> f32 {
+        self.movement_speed
+    }
+
+    pub fn rotation_speed(&self) -> f32 {
+        self.rotation_speed
+    }
+
+    pub fn sensitivity(&self) -> f32 {
+        self.sensitivity
+    }
+
     pub fn rotate(&self, amplitude: i32) -> Force {
         self.rotation_to_force(amplitude)
     }
 
+    // A held stick gives a turn rate between -1 and 1, so the rotation follows the elapsed time
+    pub fn turn(&self, rate: f32, microseconds: u128) -> Force {
+        let direction = if self.inverted_rotation { 1.0 } else { -1.0 };
+        let seconds = microseconds as f32 / 1000000.0;
+        let angle = Angle::new(STICK_TURN_IN_RADIANS_PER_SECOND * self.sensitivity * direction * rate * seconds);
+        Force::new(ANGLE_RIGHT, 0.0, angle)
+    }
+
     fn movement_to_force(&self, orientation: Angle) -> Force {
         Force::new(orientation, self.movement_speed, ANGLE_0)
     }
 
     fn rotation_to_force(&self, amplitude: i32) -> Force {
-        let angle = Angle::new(self.rotation_speed * -amplitude as f32);
+        let direction = if self.inverted_rotation { 1.0 } else { -1.0 };
+        let angle = Angle::new(self.rotation_speed * self.sensitivity * direction * amplitude as f32);
         Force::new(ANGLE_RIGHT, 0.0, angle)
     }
 }
@@ -146,6 +209,38 @@ mod input_force_test {
         assert_that!(force.rotation().to_radiant()).is_close_to(-4.516, 0.001);
     }
 
+    #[test]
+    fn rotation_force_should_scale_with_sensitivity() {
+        let stats = InputForce::new(2.4, 0.01);
+
+        let normal = stats.rotate(10);
+        let sensitive = stats.with_sensitivity(2.5).rotate(10);
+
+        assert_that!(sensitive.rotation().to_radiant()).is_close_to(normal.rotation().to_radiant() * 2.5, 0.0001);
+    }
+
+    #[test]
+    fn inverted_rotation_force_should_turn_the_other_way() {
+        let stats = InputForce::new(2.4, 0.01);
+
+        let normal = stats.rotate(10);
+        let inverted = stats.with_inverted_rotation().rotate(10);
+
+        assert_that!(normal.rotation().to_radiant()).is_close_to(-0.1, 0.0001);
+        assert_that!(inverted.rotation().to_radiant()).is_close_to(0.1, 0.0001);
+    }
+
+    #[test]
+    fn stick_turn_should_depend_on_elapsed_time_only() {
+        let stats = InputForce::new(2.4, 0.01);
+
+        let one_frame = stats.turn(0.5, 100000);
+        let two_frames = stats.turn(0.5, 50000).rotation().add(stats.turn(0.5, 50000).rotation());
+
+        assert_that!(one_frame.rotation().to_radiant()).is_close_to(-PI * 0.05, 0.0001);
+        assert_that!(two_frames.to_radiant()).is_close_to(one_frame.rotation().to_radiant(), 0.0001);
+    }
+
     #[test]
     fn rotation_force_should_have_no_orientation() {
         let stats = InputForce::new(2.4, 6.3);
@@ -194,6 +289,26 @@ mod input_force_test {
         assert_that!(added.rotation().to_radiant()).is_equal_to(0.0);
     }
 
+    #[test]
+    fn diagonal_force_should_be_clamped_to_straight_power() {
+        let stats = InputForce::new(2.0, 1.0);
+
+        let diagonal = stats.forward().add(stats.strafe_left()).clamp_power(stats.movement_speed());
+
+        assert_that!(diagonal.power()).is_close_to(stats.forward().power(), 0.0001);
+        assert_that!(diagonal.orientation().to_radiant()).is_close_to(PI / 4.0, 0.0001);
+    }
+
+    #[test]
+    fn clamp_should_not_increase_a_weaker_force() {
+        let force = Force::new(ANGLE_RIGHT, 1.0, Angle::new(0.5));
+
+        let clamped = force.clamp_power(2.0);
+
+        assert_that!(clamped.power()).is_equal_to(1.0);
+        assert_that!(clamped.rotation().to_radiant()).is_equal_to(0.5);
+    }
+
     #[test]
     fn should_increase_force_by_factor() {
         let force = Force::new(ANGLE_RIGHT, 10.0, ANGLE_0);
@@ -1,12 +1,23 @@
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub enum Input {
     Forward,
     Backward,
     StrafeLeft,
     StrafeRight,
+    Run,
+    Crouch,
+    Zoom,
     Rotate(i32),
+    Turn(f32),
+    Resize(i32, i32),
     Action,
     ToggleFullscreen,
     ShowFps,
+    ToggleCompass,
+    ToggleMinimap,
     Shoot,
+    SelectWeapon(usize),
+    QuickSave,
+    QuickLoad,
     Quit,
 }
@@ -1,3 +1,4 @@
+pub mod bindings;
 pub mod input;
 pub mod actions;
 pub mod force;
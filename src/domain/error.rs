@@ -0,0 +1,18 @@
+use std::fmt::{Display, Formatter};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum EngineError {
+    Map(String),
+    Configuration(String),
+    Resource(String),
+}
+
+impl Display for EngineError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            EngineError::Map(message) => write!(f, "Map is not valid: {}", message),
+            EngineError::Configuration(message) => write!(f, "Configuration is not valid: {}", message),
+            EngineError::Resource(message) => write!(f, "Resource can not be loaded: {}", message),
+        }
+    }
+}
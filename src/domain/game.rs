@@ -0,0 +1,471 @@
+use crate::domain::actors::actor::{AimAssist, Despawn, WallBump};
+use crate::domain::control::bindings::KeyBindings;
+use crate::domain::control::force::InputForce;
+use crate::domain::ui::crosshair::Crosshair;
+use crate::domain::ui::view::{AspectMode, ViewScreen};
+
+#[derive(Clone)]
+pub struct GameConfiguration {
+    view: ViewScreen,
+    input_force: InputForce,
+    font: String,
+    configuration: String,
+    map: String,
+    animation_distance: Option<f32>,
+    aspect_mode: AspectMode,
+    crosshair: Option<Crosshair>,
+    wall_bump: Option<WallBump>,
+    despawn: Option<Despawn>,
+    render_scale: f32,
+    turn_snap: bool,
+    fps_smoothing: Option<f32>,
+    max_sounds_per_frame: Option<usize>,
+    aim_assist: Option<AimAssist>,
+    key_bindings: KeyBindings<String>,
+    vsync: bool,
+    fps_cap: Option<u32>,
+}
+
+impl GameConfiguration {
+    pub fn new(view: ViewScreen, input_force: InputForce, font: String, configuration: String, map: String) -> Self {
+        Self {
+            view,
+            input_force,
+            font,
+            configuration,
+            map,
+            animation_distance: None,
+            aspect_mode: AspectMode::Maintain,
+            crosshair: None,
+            wall_bump: None,
+            despawn: None,
+            render_scale: 1.0,
+            turn_snap: false,
+            fps_smoothing: None,
+            max_sounds_per_frame: None,
+            aim_assist: None,
+            key_bindings: KeyBindings::new(),
+            vsync: true,
+            fps_cap: None,
+        }
+    }
+
+    pub fn with_animation_distance(&self, animation_distance: f32) -> Self {
+        Self {
+            view: self.view,
+            input_force: self.input_force,
+            font: self.font.clone(),
+            configuration: self.configuration.clone(),
+            map: self.map.clone(),
+            animation_distance: Some(animation_distance),
+            aspect_mode: self.aspect_mode,
+            crosshair: self.crosshair,
+            wall_bump: self.wall_bump,
+            despawn: self.despawn,
+            render_scale: self.render_scale,
+            turn_snap: self.turn_snap,
+            fps_smoothing: self.fps_smoothing,
+            max_sounds_per_frame: self.max_sounds_per_frame,
+            aim_assist: self.aim_assist,
+            key_bindings: self.key_bindings.clone(),
+            vsync: self.vsync,
+            fps_cap: self.fps_cap,
+        }
+    }
+
+    pub fn with_aspect_mode(&self, aspect_mode: AspectMode) -> Self {
+        Self {
+            view: self.view,
+            input_force: self.input_force,
+            font: self.font.clone(),
+            configuration: self.configuration.clone(),
+            map: self.map.clone(),
+            animation_distance: self.animation_distance,
+            aspect_mode,
+            crosshair: self.crosshair,
+            wall_bump: self.wall_bump,
+            despawn: self.despawn,
+            render_scale: self.render_scale,
+            turn_snap: self.turn_snap,
+            fps_smoothing: self.fps_smoothing,
+            max_sounds_per_frame: self.max_sounds_per_frame,
+            aim_assist: self.aim_assist,
+            key_bindings: self.key_bindings.clone(),
+            vsync: self.vsync,
+            fps_cap: self.fps_cap,
+        }
+    }
+
+    pub fn with_crosshair(&self, crosshair: Crosshair) -> Self {
+        Self {
+            view: self.view,
+            input_force: self.input_force,
+            font: self.font.clone(),
+            configuration: self.configuration.clone(),
+            map: self.map.clone(),
+            animation_distance: self.animation_distance,
+            aspect_mode: self.aspect_mode,
+            crosshair: Some(crosshair),
+            wall_bump: self.wall_bump,
+            despawn: self.despawn,
+            render_scale: self.render_scale,
+            turn_snap: self.turn_snap,
+            fps_smoothing: self.fps_smoothing,
+            max_sounds_per_frame: self.max_sounds_per_frame,
+            aim_assist: self.aim_assist,
+            key_bindings: self.key_bindings.clone(),
+            vsync: self.vsync,
+            fps_cap: self.fps_cap,
+        }
+    }
+
+    pub fn with_wall_bump(&self, wall_bump: WallBump) -> Self {
+        Self {
+            view: self.view,
+            input_force: self.input_force,
+            font: self.font.clone(),
+            configuration: self.configuration.clone(),
+            map: self.map.clone(),
+            animation_distance: self.animation_distance,
+            aspect_mode: self.aspect_mode,
+            crosshair: self.crosshair,
+            wall_bump: Some(wall_bump),
+            despawn: self.despawn,
+            render_scale: self.render_scale,
+            turn_snap: self.turn_snap,
+            fps_smoothing: self.fps_smoothing,
+            max_sounds_per_frame: self.max_sounds_per_frame,
+            aim_assist: self.aim_assist,
+            key_bindings: self.key_bindings.clone(),
+            vsync: self.vsync,
+            fps_cap: self.fps_cap,
+        }
+    }
+
+    pub fn with_despawn(&self, despawn: Despawn) -> Self {
+        Self {
+            view: self.view,
+            input_force: self.input_force,
+            font: self.font.clone(),
+            configuration: self.configuration.clone(),
+            map: self.map.clone(),
+            animation_distance: self.animation_distance,
+            aspect_mode: self.aspect_mode,
+            crosshair: self.crosshair,
+            wall_bump: self.wall_bump,
+            despawn: Some(despawn),
+            render_scale: self.render_scale,
+            turn_snap: self.turn_snap,
+            fps_smoothing: self.fps_smoothing,
+            max_sounds_per_frame: self.max_sounds_per_frame,
+            aim_assist: self.aim_assist,
+            key_bindings: self.key_bindings.clone(),
+            vsync: self.vsync,
+            fps_cap: self.fps_cap,
+        }
+    }
+
+    pub fn with_render_scale(&self, render_scale: f32) -> Self {
+        Self {
+            view: self.view,
+            input_force: self.input_force,
+            font: self.font.clone(),
+            configuration: self.configuration.clone(),
+            map: self.map.clone(),
+            animation_distance: self.animation_distance,
+            aspect_mode: self.aspect_mode,
+            crosshair: self.crosshair,
+            wall_bump: self.wall_bump,
+            despawn: self.despawn,
+            render_scale,
+            turn_snap: self.turn_snap,
+            fps_smoothing: self.fps_smoothing,
+            max_sounds_per_frame: self.max_sounds_per_frame,
+            aim_assist: self.aim_assist,
+            key_bindings: self.key_bindings.clone(),
+            vsync: self.vsync,
+            fps_cap: self.fps_cap,
+        }
+    }
+
+    pub fn with_turn_snap(&self) -> Self {
+        Self {
+            view: self.view,
+            input_force: self.input_force,
+            font: self.font.clone(),
+            configuration: self.configuration.clone(),
+            map: self.map.clone(),
+            animation_distance: self.animation_distance,
+            aspect_mode: self.aspect_mode,
+            crosshair: self.crosshair,
+            wall_bump: self.wall_bump,
+            despawn: self.despawn,
+            render_scale: self.render_scale,
+            turn_snap: true,
+            fps_smoothing: self.fps_smoothing,
+            max_sounds_per_frame: self.max_sounds_per_frame,
+            aim_assist: self.aim_assist,
+            key_bindings: self.key_bindings.clone(),
+            vsync: self.vsync,
+            fps_cap: self.fps_cap,
+        }
+    }
+
+    pub fn with_fps_smoothing(&self, fps_smoothing: f32) -> Self {
+        Self {
+            view: self.view,
+            input_force: self.input_force,
+            font: self.font.clone(),
+            configuration: self.configuration.clone(),
+            map: self.map.clone(),
+            animation_distance: self.animation_distance,
+            aspect_mode: self.aspect_mode,
+            crosshair: self.crosshair,
+            wall_bump: self.wall_bump,
+            despawn: self.despawn,
+            render_scale: self.render_scale,
+            turn_snap: self.turn_snap,
+            fps_smoothing: Some(fps_smoothing),
+            max_sounds_per_frame: self.max_sounds_per_frame,
+            aim_assist: self.aim_assist,
+            key_bindings: self.key_bindings.clone(),
+            vsync: self.vsync,
+            fps_cap: self.fps_cap,
+        }
+    }
+
+    pub fn with_max_sounds_per_frame(&self, max_sounds_per_frame: usize) -> Self {
+        Self {
+            view: self.view,
+            input_force: self.input_force,
+            font: self.font.clone(),
+            configuration: self.configuration.clone(),
+            map: self.map.clone(),
+            animation_distance: self.animation_distance,
+            aspect_mode: self.aspect_mode,
+            crosshair: self.crosshair,
+            wall_bump: self.wall_bump,
+            despawn: self.despawn,
+            render_scale: self.render_scale,
+            turn_snap: self.turn_snap,
+            fps_smoothing: self.fps_smoothing,
+            max_sounds_per_frame: Some(max_sounds_per_frame),
+            aim_assist: self.aim_assist,
+            key_bindings: self.key_bindings.clone(),
+            vsync: self.vsync,
+            fps_cap: self.fps_cap,
+        }
+    }
+
+    pub fn with_aim_assist(&self, aim_assist: AimAssist) -> Self {
+        Self {
+            view: self.view,
+            input_force: self.input_force,
+            font: self.font.clone(),
+            configuration: self.configuration.clone(),
+            map: self.map.clone(),
+            animation_distance: self.animation_distance,
+            aspect_mode: self.aspect_mode,
+            crosshair: self.crosshair,
+            wall_bump: self.wall_bump,
+            despawn: self.despawn,
+            render_scale: self.render_scale,
+            turn_snap: self.turn_snap,
+            fps_smoothing: self.fps_smoothing,
+            max_sounds_per_frame: self.max_sounds_per_frame,
+            aim_assist: Some(aim_assist),
+            key_bindings: self.key_bindings.clone(),
+            vsync: self.vsync,
+            fps_cap: self.fps_cap,
+        }
+    }
+
+    pub fn with_key_bindings(&self, key_bindings: KeyBindings<String>) -> Self {
+        Self {
+            view: self.view,
+            input_force: self.input_force,
+            font: self.font.clone(),
+            configuration: self.configuration.clone(),
+            map: self.map.clone(),
+            animation_distance: self.animation_distance,
+            aspect_mode: self.aspect_mode,
+            crosshair: self.crosshair,
+            wall_bump: self.wall_bump,
+            despawn: self.despawn,
+            render_scale: self.render_scale,
+            turn_snap: self.turn_snap,
+            fps_smoothing: self.fps_smoothing,
+            max_sounds_per_frame: self.max_sounds_per_frame,
+            aim_assist: self.aim_assist,
+            key_bindings,
+            vsync: self.vsync,
+            fps_cap: self.fps_cap,
+        }
+    }
+
+    pub fn with_vsync(&self, vsync: bool) -> Self {
+        Self {
+            view: self.view,
+            input_force: self.input_force,
+            font: self.font.clone(),
+            configuration: self.configuration.clone(),
+            map: self.map.clone(),
+            animation_distance: self.animation_distance,
+            aspect_mode: self.aspect_mode,
+            crosshair: self.crosshair,
+            wall_bump: self.wall_bump,
+            despawn: self.despawn,
+            render_scale: self.render_scale,
+            turn_snap: self.turn_snap,
+            fps_smoothing: self.fps_smoothing,
+            max_sounds_per_frame: self.max_sounds_per_frame,
+            aim_assist: self.aim_assist,
+            key_bindings: self.key_bindings.clone(),
+            vsync,
+            fps_cap: self.fps_cap,
+        }
+    }
+
+    pub fn with_fps_cap(&self, fps_cap: u32) -> Self {
+        Self {
+            view: self.view,
+            input_force: self.input_force,
+            font: self.font.clone(),
+            configuration: self.configuration.clone(),
+            map: self.map.clone(),
+            animation_distance: self.animation_distance,
+            aspect_mode: self.aspect_mode,
+            crosshair: self.crosshair,
+            wall_bump: self.wall_bump,
+            despawn: self.despawn,
+            render_scale: self.render_scale,
+            turn_snap: self.turn_snap,
+            fps_smoothing: self.fps_smoothing,
+            max_sounds_per_frame: self.max_sounds_per_frame,
+            aim_assist: self.aim_assist,
+            key_bindings: self.key_bindings.clone(),
+            vsync: self.vsync,
+            fps_cap: Some(fps_cap),
+        }
+    }
+
+    pub fn view(&self) -> ViewScreen {
+        self.view
+    }
+
+    pub fn input_force(&self) -> InputForce {
+        self.input_force
+    }
+
+    pub fn font(&self) -> String {
+        self.font.clone()
+    }
+
+    pub fn configuration(&self) -> String {
+        self.configuration.clone()
+    }
+
+    pub fn map(&self) -> String {
+        self.map.clone()
+    }
+
+    pub fn animation_distance(&self) -> Option<f32> {
+        self.animation_distance
+    }
+
+    pub fn aspect_mode(&self) -> AspectMode {
+        self.aspect_mode
+    }
+
+    pub fn crosshair(&self) -> Option<Crosshair> {
+        self.crosshair
+    }
+
+    pub fn wall_bump(&self) -> Option<WallBump> {
+        self.wall_bump
+    }
+
+    pub fn despawn(&self) -> Option<Despawn> {
+        self.despawn
+    }
+
+    pub fn render_scale(&self) -> f32 {
+        self.render_scale
+    }
+
+    pub fn turn_snap(&self) -> bool {
+        self.turn_snap
+    }
+
+    pub fn fps_smoothing(&self) -> Option<f32> {
+        self.fps_smoothing
+    }
+
+    pub fn max_sounds_per_frame(&self) -> Option<usize> {
+        self.max_sounds_per_frame
+    }
+
+    pub fn aim_assist(&self) -> Option<AimAssist> {
+        self.aim_assist
+    }
+
+    pub fn key_bindings(&self) -> KeyBindings<String> {
+        self.key_bindings.clone()
+    }
+
+    pub fn vsync(&self) -> bool {
+        self.vsync
+    }
+
+    pub fn fps_cap(&self) -> Option<u32> {
+        self.fps_cap
+    }
+
+    pub fn frame_sleep(&self, elapsed_in_microseconds: u128) -> u128 {
+        self.fps_cap
+            .filter(|fps| *fps > 0)
+            .map(|fps| (1000000 / fps as u128).saturating_sub(elapsed_in_microseconds))
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod game_configuration_test {
+    use spectral::prelude::*;
+
+    use crate::domain::control::force::InputForce;
+    use crate::domain::game::GameConfiguration;
+    use crate::domain::maths::ANGLE_90;
+    use crate::domain::ui::view::ViewScreen;
+
+    fn game() -> GameConfiguration {
+        let view = ViewScreen::new(480, 640, ANGLE_90);
+        GameConfiguration::new(view, InputForce::new(0.004, 0.005), String::from("font.otf"), String::from("conf.json"), String::from("start.map"))
+    }
+
+    #[test]
+    fn frame_sleep_should_fill_the_rest_of_the_target_frame() {
+        let game = game().with_fps_cap(50);
+
+        assert_that!(game.frame_sleep(5000)).is_equal_to(15000);
+    }
+
+    #[test]
+    fn frame_sleep_should_be_zero_when_the_frame_is_late() {
+        let game = game().with_fps_cap(50);
+
+        assert_that!(game.frame_sleep(25000)).is_equal_to(0);
+    }
+
+    #[test]
+    fn frame_sleep_should_be_zero_without_cap() {
+        assert_that!(game().frame_sleep(5000)).is_equal_to(0);
+    }
+
+    #[test]
+    fn zero_fps_cap_should_not_limit_the_frame_rate() {
+        let game = game().with_fps_cap(0);
+
+        assert_that!(game.frame_sleep(5000)).is_equal_to(0);
+    }
+}
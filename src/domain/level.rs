@@ -1,18 +1,44 @@
+use std::collections::HashSet;
 use std::f32::consts::PI;
 
-use crate::domain::actors::actor::{Enemy, Player};
-use crate::domain::actors::shoot::{Weapon, ShootState};
+use rayon::prelude::*;
+
+use crate::domain::actors::actor::{AimAssist, Despawn, Enemy, EnemyOrigin, Player, SpeedStats, WallBump};
+use crate::domain::actors::pickup::{Pickup, PickupKind};
+use crate::domain::actors::projectile::Projectile;
+use crate::domain::actors::spawner::Spawner;
+use crate::domain::actors::shoot::{Weapon, ShootState, WeaponKind};
 use crate::domain::control::actions::Actions;
 use crate::domain::control::force::Force;
-use crate::domain::level_drawer::{build_background_actions, build_clear_actions, build_enemies, build_walls, build_weapons, DrawActionZIndex};
-use crate::domain::maths::{Angle, signed_angle, Vector};
-use crate::domain::topology::coord::{Position};
-use crate::domain::topology::map::Map;
+use crate::domain::error::EngineError;
+use crate::domain::level_drawer::{build_backdrop, build_background_actions, build_clear_actions, build_enemies, build_hud, build_minimap, build_pickups, build_projectiles, build_walls, build_weapons, upscale, DrawActionZIndex};
+use crate::domain::maths::{Angle, ANGLE_0, between, signed_angle, Vector};
+use crate::domain::save::{LevelState, SavedEnemy, SavedPlayer};
+use crate::domain::sound::{loudest, SoundEvent, SoundKind};
+use crate::domain::topology::coord::{MapPoint, Position};
+use crate::domain::topology::index::{FontIndex, TextureIndex};
+use crate::domain::topology::map::{Checkpoint, KeyColor, Map, Tile};
 use crate::domain::topology::projection::{project, ProjectedPoint};
-use crate::domain::ui::draw_action::DrawAction;
+use crate::domain::ui::compass::Compass;
+use crate::domain::ui::crosshair::{Crosshair, InteractionKind};
+use crate::domain::ui::damage_indicator::DamageIndicator;
+use crate::domain::ui::draw_action::{DrawAction, DrawLayer, LayeredActions};
+use crate::domain::ui::letterbox::Letterbox;
 use crate::domain::ui::view::ViewScreen;
 
-const WALL_MINIMUM_DISTANCE: f32 = 0.1;
+const COLLISION_EPSILON: f32 = 0.0001;
+const LETTERBOX_SPEED_IN_FRACTION_PER_SECONDS: f32 = 1.0;
+const PROJECTILE_HIT_DISTANCE: f32 = 0.3;
+const ENEMY_SIZE: f32 = 0.5;
+const ENEMY_COLLISION_DISTANCE: f32 = 0.5;
+const ENEMY_WALL_MINIMUM_DISTANCE: f32 = 0.1;
+const ACTION_DISTANCE: f32 = 1.0;
+const EXPLORATION_RAYS: i32 = 32;
+const PICKUP_DISTANCE: f32 = 0.5;
+const CROUCH_SPEED_IN_FRACTION_PER_SECONDS: f32 = 5.0;
+const CROUCH_HORIZON_SHIFT: f32 = 0.1;
+const MINIMAP_SCALE: i32 = 4;
+const ZOOM_FACTOR: f32 = 0.5;
 
 pub struct Level {
     view: ViewScreen,
@@ -20,360 +46,2969 @@ pub struct Level {
     actions: Actions,
     player: Player,
     enemies: Vec<Enemy>,
-    current_weapon: Weapon,
+    projectiles: Vec<Projectile>,
+    spawners: Vec<Spawner>,
+    pickups: Vec<Pickup>,
+    weapons: Vec<Weapon>,
+    current_weapon_index: usize,
+    keys: HashSet<KeyColor>,
+    approach_position: Option<Position>,
+    collision_epsilon: f32,
+    enemy_collision_distance: f32,
+    letterbox: Letterbox,
+    running: bool,
+    crouching: bool,
+    zooming: bool,
+    zoomed: bool,
+    crouch: f32,
+    animation_distance: Option<f32>,
+    crosshair: Option<Crosshair>,
+    compass: Option<Compass>,
+    hud_font: Option<FontIndex>,
+    wall_bump: Option<WallBump>,
+    bumping: bool,
+    firing: bool,
+    turn_snap: bool,
+    turning: bool,
+    aim_assist: Option<AimAssist>,
+    despawn: Option<Despawn>,
+    damage_indicator: DamageIndicator,
+    last_checkpoint: Option<usize>,
+    on_exit: bool,
+    transition: Option<String>,
+    sound_events: Vec<SoundEvent>,
+    pending_sounds: Vec<SoundEvent>,
+    max_sounds_per_frame: Option<usize>,
+    render_scale: f32,
+    explored: HashSet<MapPoint>,
+    minimap: bool,
+    map_name: Option<String>,
+    parallel: bool,
 }
 
 
 impl Level {
-    pub fn new(view: ViewScreen, map: Map) -> Self {
+    pub fn new(view: ViewScreen, map: Map) -> Result<Self, EngineError> {
+        let player = map.generate_player()
+            .ok_or_else(|| EngineError::Map(String::from("No spawn point for the player")))?;
         let actions = Actions::new(&map);
 
-        Self {
+        Ok(Self {
             view,
-            current_weapon: map.generate_weapon(),
-            player: map.generate_player().unwrap(),
+            weapons: map.generate_weapons(),
+            current_weapon_index: 0,
+            keys: HashSet::new(),
+            approach_position: None,
+            pickups: map.generate_pickups(),
+            player,
             enemies: map.generate_enemies(),
+            projectiles: vec![],
+            spawners: map.generate_spawners(),
             map,
             actions,
+            collision_epsilon: COLLISION_EPSILON,
+            enemy_collision_distance: ENEMY_COLLISION_DISTANCE,
+            letterbox: Letterbox::new(SpeedStats::new(LETTERBOX_SPEED_IN_FRACTION_PER_SECONDS)),
+            running: false,
+            crouching: false,
+            zooming: false,
+            zoomed: false,
+            crouch: 0.0,
+            animation_distance: None,
+            crosshair: None,
+            compass: None,
+            hud_font: None,
+            wall_bump: None,
+            bumping: false,
+            firing: false,
+            turn_snap: false,
+            turning: false,
+            aim_assist: None,
+            despawn: None,
+            damage_indicator: DamageIndicator::new(),
+            last_checkpoint: None,
+            on_exit: false,
+            transition: None,
+            sound_events: vec![],
+            pending_sounds: vec![],
+            max_sounds_per_frame: None,
+            render_scale: 1.0,
+            explored: HashSet::new(),
+            minimap: false,
+            map_name: None,
+            parallel: true,
+        })
+    }
+
+    pub fn reset(&mut self) {
+        self.actions = Actions::new(&self.map);
+        self.player = self.map.generate_player().unwrap();
+        self.enemies = self.map.generate_enemies();
+        self.projectiles = vec![];
+        self.spawners = self.map.generate_spawners();
+        self.pickups = self.map.generate_pickups();
+        self.weapons = self.map.generate_weapons();
+        self.current_weapon_index = 0;
+        self.keys.clear();
+        self.approach_position = None;
+        self.running = false;
+        self.crouching = false;
+        self.zooming = false;
+        self.zoomed = false;
+        self.crouch = 0.0;
+        self.bumping = false;
+        self.firing = false;
+        self.damage_indicator = DamageIndicator::new();
+        self.last_checkpoint = None;
+        self.on_exit = false;
+        self.transition = None;
+        self.sound_events = vec![];
+        self.pending_sounds = vec![];
+        self.explored = HashSet::new();
+    }
+
+    pub fn with_collision_epsilon(self, collision_epsilon: f32) -> Self {
+        Self {
+            collision_epsilon,
+            ..self
+        }
+    }
+
+    pub fn with_enemy_collision_distance(self, enemy_collision_distance: f32) -> Self {
+        Self {
+            enemy_collision_distance,
+            ..self
+        }
+    }
+
+    pub fn with_aim_assist(self, aim_assist: AimAssist) -> Self {
+        Self {
+            aim_assist: Some(aim_assist),
+            ..self
+        }
+    }
+
+    pub fn with_max_sounds_per_frame(self, max_sounds_per_frame: usize) -> Self {
+        Self {
+            max_sounds_per_frame: Some(max_sounds_per_frame),
+            ..self
+        }
+    }
+
+    pub fn with_render_scale(self, render_scale: f32) -> Self {
+        Self {
+            render_scale,
+            ..self
+        }
+    }
+
+    pub fn with_animation_distance(self, animation_distance: f32) -> Self {
+        Self {
+            animation_distance: Some(animation_distance),
+            ..self
+        }
+    }
+
+    pub fn with_compass(self, compass: Compass) -> Self {
+        Self {
+            compass: Some(compass),
+            ..self
+        }
+    }
+
+    pub fn with_hud(self, font: FontIndex) -> Self {
+        Self {
+            hud_font: Some(font),
+            ..self
+        }
+    }
+
+    pub fn with_crosshair(self, crosshair: Crosshair) -> Self {
+        Self {
+            crosshair: Some(crosshair),
+            ..self
+        }
+    }
+
+    pub fn with_turn_snap(self) -> Self {
+        Self {
+            turn_snap: true,
+            ..self
+        }
+    }
+
+    pub fn with_wall_bump(self, wall_bump: WallBump) -> Self {
+        Self {
+            wall_bump: Some(wall_bump),
+            ..self
+        }
+    }
+
+    pub fn with_despawn(self, despawn: Despawn) -> Self {
+        Self {
+            despawn: Some(despawn),
+            ..self
+        }
+    }
+
+    // Health and weapons follow the player to the next map, keys stay behind
+    pub fn with_player_state_of(self, previous: &Level) -> Self {
+        Self {
+            player: self.player.with_health(previous.player.health()),
+            weapons: previous.weapons.clone(),
+            current_weapon_index: previous.current_weapon_index,
+            ..self
+        }
+    }
+
+    pub fn with_map_name(self, map_name: String) -> Self {
+        Self {
+            map_name: Some(map_name),
+            ..self
+        }
+    }
+
+    pub fn sound_events(&self) -> &[SoundEvent] {
+        &self.sound_events
+    }
+
+    // Sounds emitted by every tick since the last drain, meant to be played once per frame
+    pub fn drain_sounds(&mut self) -> Vec<SoundEvent> {
+        let sounds = std::mem::take(&mut self.pending_sounds);
+        match self.max_sounds_per_frame {
+            Some(maximum) => loudest(&sounds, maximum),
+            None => sounds,
+        }
+    }
+
+    pub fn explored(&self) -> &HashSet<MapPoint> {
+        &self.explored
+    }
+
+    pub fn toggle_minimap(&mut self) {
+        self.minimap = !self.minimap;
+    }
+
+    pub fn toggle_compass(&mut self) {
+        self.compass = self.compass.map(|compass| compass.toggle());
+    }
+
+    fn explore(&mut self) {
+        let position = *self.player.position();
+        self.explored.insert(position.to_map_point(0.0, 0.0));
+
+        for angle in self.player.orientation().discreet_cone_straight_space(self.view_angle(), EXPLORATION_RAYS) {
+            for (point, _) in self.map.ray_tiles(position, angle) {
+                self.explored.insert(point);
+                if !matches!(self.map.paving_at(point.x(), point.y()), Some(Tile::NOTHING) | Some(Tile::FIELD(_))) {
+                    break;
+                }
+            }
+        }
+    }
+
+    pub fn animated_tiles(&self) -> usize {
+        self.actions.animated_cells()
+    }
+
+    pub fn player(&self) -> &Player {
+        &self.player
+    }
+
+    pub fn enemies(&self) -> &[Enemy] {
+        &self.enemies
+    }
+
+    pub fn pickups(&self) -> &[Pickup] {
+        &self.pickups
+    }
+
+    pub fn weapons(&self) -> &[Weapon] {
+        &self.weapons
+    }
+
+    pub fn current_weapon(&self) -> &Weapon {
+        &self.weapons[self.current_weapon_index]
+    }
+
+    pub fn has_key(&self, key: KeyColor) -> bool {
+        self.keys.contains(&key)
+    }
+
+    pub fn select_weapon(&mut self, index: usize) {
+        if index < self.weapons.len() && self.current_weapon().state() == ShootState::Finished {
+            self.current_weapon_index = index;
+        }
+    }
+
+    pub fn map(&self) -> &Map {
+        &self.map
+    }
+
+    pub fn actions(&self) -> &Actions {
+        &self.actions
+    }
+
+    pub fn run(&mut self) {
+        self.running = true;
+    }
+
+    pub fn crouch(&mut self) {
+        self.crouching = true;
+    }
+
+    pub fn zoom(&mut self) {
+        self.zooming = true;
+    }
+
+    fn view_angle(&self) -> Angle {
+        if self.zoomed {
+            self.view.angle().multiplication(ZOOM_FACTOR)
+        } else {
+            self.view.angle()
+        }
+    }
+
+    pub fn horizon(&self) -> i32 {
+        self.view.with_horizon_shift(self.horizon_shift()).horizon()
+    }
+
+    pub fn apply_forces(&mut self, force: Force, microseconds_elapsed: u128) {
+        let force = if self.turn_snap { self.snap_turn(force) } else { force };
+        self.update_crouch(microseconds_elapsed);
+        let crouched = self.crouch > 0.0;
+        let running = self.running && !crouched && self.player.can_run() && force.power() > 0.0;
+        self.running = false;
+        self.zoomed = self.zooming;
+        self.zooming = false;
+
+        let stats = self.player.stats();
+        let player = self.player.consume_stamina(running, microseconds_elapsed);
+        let player = if running {
+            player.with_stats(stats.running())
+        } else if crouched {
+            player.with_stats(stats.crouching())
+        } else {
+            player
+        };
+
+        let relative_force = force.for_relative_view(player.orientation());
+        let no_limit = player.apply_force(relative_force, microseconds_elapsed);
+        let collision_radius = player.stats().collision_radius();
+        let constrained = self.constrains(*player.position(), *no_limit.position(), collision_radius);
+        let constrained = match self.collide_with_enemies(*player.position(), constrained) {
+            Some(separated) => self.constrains(*player.position(), separated, collision_radius),
+            None => constrained,
+        };
+
+        self.player = no_limit.with_position(constrained).with_stats(stats);
+        self.bump_into_walls(*no_limit.position(), constrained, microseconds_elapsed);
+        self.reach_checkpoints();
+        self.reach_exits();
+        self.collect_pickups(*player.position(), constrained);
+        self.enter_teleports(*player.position());
+    }
+
+    fn enter_teleports(&mut self, from: Position) {
+        let position = *self.player.position();
+        let entered = self.map.teleports().iter()
+            .find(|teleport| teleport.contains(position) && !teleport.contains(from))
+            .map(|teleport| teleport.destination());
+        if let Some(destination) = entered {
+            self.teleport(destination);
+        }
+    }
+
+    fn collect_pickups(&mut self, from: Position, to: Position) {
+        let path = Vector::new(from, to);
+        let length = path.length();
+        let (collected, remaining): (Vec<Pickup>, Vec<Pickup>) = std::mem::take(&mut self.pickups)
+            .into_iter()
+            .partition(|pickup| {
+                let ratio = if length > 0.0 {
+                    between(0.0, path.scalar(Vector::new(from, pickup.position())) / (length * length), 1.0)
+                } else {
+                    0.0
+                };
+                let closest = Position::new(from.x() + (to.x() - from.x()) * ratio, from.y() + (to.y() - from.y()) * ratio);
+                closest.distance(&pickup.position()) < PICKUP_DISTANCE
+            });
+        self.pickups = remaining;
+
+        for pickup in collected {
+            match pickup.kind() {
+                PickupKind::Weapon(weapon) => self.weapons.push(Weapon::new(**weapon)),
+                PickupKind::Health(amount) => {
+                    let health = self.player.health().saturating_add(*amount).min(self.player.stats().health());
+                    self.player = self.player.with_health(health);
+                }
+                PickupKind::Ammo(amount) => {
+                    // Ammo goes to the current weapon, or the first one using ammo, and stays on the floor otherwise
+                    let receiver = Some(self.current_weapon_index)
+                        .filter(|index| self.weapons[*index].ammo().is_some())
+                        .or_else(|| self.weapons.iter().position(|weapon| weapon.ammo().is_some()));
+                    match receiver {
+                        Some(index) => self.weapons[index].add_ammo(*amount),
+                        None => self.pickups.push(pickup),
+                    }
+                }
+                PickupKind::Key(key) => {
+                    self.keys.insert(*key);
+                }
+            }
+        }
+    }
+
+    fn update_crouch(&mut self, microseconds_elapsed: u128) {
+        let target = if self.crouching { 1.0 } else { 0.0 };
+        self.crouching = false;
+
+        let step = CROUCH_SPEED_IN_FRACTION_PER_SECONDS * microseconds_elapsed as f32 / 1000000.0;
+        self.crouch = if self.crouch < target {
+            (self.crouch + step).min(target)
+        } else {
+            (self.crouch - step).max(target)
+        };
+    }
+
+    fn horizon_shift(&self) -> f32 {
+        self.crouch * CROUCH_HORIZON_SHIFT
+    }
+
+    fn snap_turn(&mut self, force: Force) -> Force {
+        let rotation = force.rotation().to_radiant();
+        let turning = rotation != 0.0;
+        let snapped = if turning && !self.turning {
+            let orientation = self.player.orientation();
+            let target = orientation.align_to_axis().addition(Angle::new(rotation.signum() * PI / 2.0));
+            Angle::new(target.to_radiant() - orientation.to_radiant())
+        } else {
+            ANGLE_0
+        };
+        self.turning = turning;
+
+        Force::new(force.orientation(), force.power(), snapped)
+    }
+
+    fn reach_checkpoints(&mut self) {
+        let position = *self.player.position();
+        if let Some(index) = self.map.checkpoints().iter().position(|checkpoint| checkpoint.contains(position)) {
+            self.last_checkpoint = Some(index);
+        }
+    }
+
+    fn reach_exits(&mut self) {
+        let position = *self.player.position();
+        let exit = self.map.exits().iter().find(|exit| exit.contains(position));
+        // Only stepping onto an exit triggers it, a failed transition is not retried on every tick
+        if let (Some(exit), false) = (exit, self.on_exit) {
+            self.transition = Some(String::from(exit.target()));
+        }
+        self.on_exit = exit.is_some();
+    }
+
+    pub fn poll_transition(&mut self) -> Option<String> {
+        self.transition.take()
+    }
+
+    pub fn checkpoints(&self) -> &[Checkpoint] {
+        self.map.checkpoints()
+    }
+
+    pub fn last_checkpoint(&self) -> Option<&Checkpoint> {
+        self.last_checkpoint.map(|index| &self.map.checkpoints()[index])
+    }
+
+    pub fn teleport_to_checkpoint(&mut self, name: &str) -> Result<(), String> {
+        let checkpoint = self.map.checkpoints().iter()
+            .find(|checkpoint| checkpoint.name() == name)
+            .ok_or_else(|| format!("Unknown checkpoint '{}'", name))?;
+
+        self.teleport(checkpoint.position());
+        Ok(())
+    }
+
+    pub fn respawn_at_last_checkpoint(&mut self) {
+        let spawn = self.map.generate_player().unwrap();
+        let position = self.last_checkpoint()
+            .map(|checkpoint| checkpoint.position())
+            .unwrap_or(*spawn.position());
+
+        self.player = spawn.with_position(position);
+        self.projectiles = vec![];
+    }
+
+    fn emit_sound(&mut self, kind: SoundKind, position: Position) {
+        let event = SoundEvent::new(kind, position).heard_from(*self.player.position(), self.player.orientation());
+        self.sound_events.push(event);
+        self.pending_sounds.push(event);
+    }
+
+    fn bump_into_walls(&mut self, intended: Position, allowed: Position, microseconds_elapsed: u128) {
+        if let Some(wall_bump) = self.wall_bump {
+            let bumping = wall_bump.is_bump(Vector::new(allowed, intended).length(), microseconds_elapsed);
+            if bumping && !self.bumping {
+                self.emit_sound(SoundKind::Bump, allowed);
+                self.player = self.player.damage(wall_bump.damage());
+            }
+            self.bumping = bumping;
+        }
+    }
+
+    pub fn apply_shoots(&mut self) {
+        let firing = matches!(self.current_weapon().state(), ShootState::Active | ShootState::AlreadyHit);
+        if firing && !self.firing {
+            self.emit_sound(SoundKind::PlayerShot, *self.player.position());
+        }
+        self.firing = firing;
+
+        if matches!(self.current_weapon().state(), ShootState::Active) {
+            match self.current_weapon().configuration().kind() {
+                WeaponKind::Melee { range, angle } => Level::sword(&mut self.enemies, self.player, &mut self.weapons[self.current_weapon_index], range, angle, self.parallel),
+                WeaponKind::Hitscan { range, radius } => self.hitscan(range, radius),
+                WeaponKind::Projectile { speed, texture } => self.fire(speed, texture),
+            }
+        }
+    }
+
+    fn hitscan(&mut self, range: f32, radius: f32) {
+        let origin = *self.player.position();
+        let orientation = self.assisted_aim(range);
+        let reach = match self.distance(origin, orientation) {
+            wall if wall > 0.0 => wall.min(range),
+            _ => range,
+        };
+        let look_at = Vector::from_angle(orientation);
+
+        let target = self.enemies.iter()
+            .enumerate()
+            .filter(|(_, enemy)| !enemy.is_dead())
+            .filter_map(|(index, enemy)| {
+                let to_enemy = Vector::new(origin, enemy.position());
+                let along = look_at.scalar(to_enemy);
+                let aside = (to_enemy.length().powi(2) - along.powi(2)).max(0.0).sqrt();
+                (along > 0.0 && along < reach && aside < radius).then_some((index, along))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+
+        if let Some((index, _)) = target {
+            let configuration = self.current_weapon().configuration();
+            self.enemies[index] = self.enemies[index].damage(configuration.damage()).knock_back(orientation, configuration.knockback());
+            self.weapons[self.current_weapon_index].notify_hit();
+        }
+    }
+
+    fn fire(&mut self, speed: f32, texture: TextureIndex) {
+        let projectile = Projectile::new(
+            *self.player.position(),
+            self.assisted_aim(f32::MAX),
+            SpeedStats::new(speed),
+            self.current_weapon().configuration().damage(),
+            texture,
+        );
+        self.projectiles.push(projectile.with_friendly(true).with_knockback(self.current_weapon().configuration().knockback()));
+        self.weapons[self.current_weapon_index].notify_hit();
+    }
+
+    fn assisted_aim(&self, range: f32) -> Angle {
+        let origin = *self.player.position();
+        let orientation = self.player.orientation();
+        let aim_assist = match self.aim_assist {
+            Some(aim_assist) => aim_assist,
+            None => return orientation,
+        };
+        let window = aim_assist.window().to_radiant();
+        let look_at = Vector::from_angle(orientation);
+
+        self.enemies.iter()
+            .filter(|enemy| !enemy.is_dead())
+            .filter(|enemy| origin.distance(&enemy.position()) < range)
+            .filter(|enemy| look_at.angle(Vector::new(origin, enemy.position()))
+                .map(|angle| angle.to_radiant() <= window)
+                .unwrap_or(false))
+            .filter(|enemy| has_line_of_sight(origin, enemy.position(), &self.map, &self.actions))
+            .min_by(|a, b| origin.distance(&a.position()).total_cmp(&origin.distance(&b.position())))
+            .and_then(|enemy| signed_angle(origin, enemy.position()))
+            .map(|target| aim_assist.bend(orientation, target))
+            .unwrap_or(orientation)
+    }
+
+    fn alert_enemies(&mut self) {
+        let target = *self.player.position();
+        let alerted: Vec<bool> = decide(&self.enemies, self.parallel, |enemy| {
+            match enemy.perception() {
+                Some(perception) => {
+                    let sees = perception.can_see(enemy.position(), enemy.orientation(), target)
+                        && has_line_of_sight(enemy.position(), target, &self.map, &self.actions);
+                    let hears = self.sound_events.iter()
+                        .any(|event| perception.can_hear(enemy.position(), event.position()));
+                    sees || hears
+                }
+                None => false,
+            }
+        });
+
+        for (enemy, alerted) in self.enemies.iter_mut().zip(alerted) {
+            if alerted {
+                *enemy = enemy.alert();
+            }
+        }
+    }
+
+    fn move_enemies(&mut self, microseconds: u128) {
+        let moved: Vec<Enemy> = decide(&self.enemies, self.parallel, |enemy| {
+            let (moves, enemy) = enemy.knockback_move(microseconds);
+            let start = enemy.position();
+            enemy.with_position(self.constrains(start, start.apply_force(moves), ENEMY_WALL_MINIMUM_DISTANCE))
+        });
+
+        self.enemies = moved;
+    }
+
+    fn despawn_enemies(&mut self, microseconds: u128) {
+        if let Some(despawn) = self.despawn {
+            let player = *self.player.position();
+            let enemies: Vec<Enemy> = self.enemies.iter()
+                .map(|enemy| {
+                    let far = enemy.position().distance(&player) > despawn.distance();
+                    enemy.notify_unseen(far && !has_line_of_sight(player, enemy.position(), &self.map, &self.actions), microseconds)
+                })
+                .collect();
+
+            let (kept, despawned): (Vec<Enemy>, Vec<Enemy>) = enemies.into_iter()
+                .partition(|enemy| enemy.unseen_in_microseconds() < despawn.grace_in_microseconds());
+            // A spawner can replace the enemies it lost
+            for enemy in despawned {
+                if let EnemyOrigin::Spawner(index) = enemy.origin() {
+                    self.spawners[index] = self.spawners[index].release();
+                }
+            }
+            self.enemies = kept;
+        }
+    }
+
+    pub fn spawn_enemy(&mut self, enemy: Enemy) {
+        self.enemies.push(enemy);
+    }
+
+    fn notify_spawners(&mut self, microseconds: u128) {
+        for index in 0..self.spawners.len() {
+            let (enemy, spawner) = self.spawners[index].notify_elapsed(microseconds);
+            self.spawners[index] = spawner;
+            if let Some(enemy) = enemy {
+                self.spawn_enemy(enemy.with_origin(EnemyOrigin::Spawner(index)));
+            }
+        }
+    }
+
+    fn enemies_attack(&mut self, microseconds: u128) {
+        let target = *self.player.position();
+        let decisions: Vec<(Option<Projectile>, Enemy)> = decide(&self.enemies, self.parallel, |enemy| {
+            enemy.update_state(target).fire(target, microseconds)
+        });
+
+        for (index, (projectile, enemy)) in decisions.into_iter().enumerate() {
+            self.enemies[index] = enemy;
+            if let Some(projectile) = projectile {
+                self.emit_sound(SoundKind::EnemyShot, enemy.position());
+                self.projectiles.push(projectile);
+            }
+        }
+    }
+
+    pub fn update_projectiles(&mut self, microseconds: u128) {
+        let projectiles = std::mem::take(&mut self.projectiles);
+
+        for projectile in projectiles {
+            let start = projectile.position();
+            let end = start.apply_force(projectile.next_move(microseconds));
+
+            if self.distance(start, projectile.orientation()) < start.distance(&end) {
+                continue;
+            }
+
+            if projectile.friendly() {
+                if let Some(index) = self.enemies.iter().position(|enemy| !enemy.is_dead() && end.distance(&enemy.position()) < ENEMY_SIZE) {
+                    self.enemies[index] = self.enemies[index].damage(projectile.damage()).knock_back(projectile.orientation(), projectile.knockback());
+                    continue;
+                }
+            } else if end.distance(self.player.position()) < PROJECTILE_HIT_DISTANCE {
+                self.hurt_player(projectile.damage(), projectile.orientation().add(Angle::new(PI)));
+                continue;
+            }
+
+            self.projectiles.push(projectile.with_position(end));
+        }
+    }
+
+    fn enemies_strike(&mut self, microseconds: u128) {
+        let player = *self.player.position();
+        for index in 0..self.enemies.len() {
+            let (damage, enemy) = self.enemies[index].strike(player, microseconds);
+            self.enemies[index] = enemy;
+            if let Some(damage) = damage {
+                self.hurt_player(damage, signed_angle(player, enemy.position()).unwrap_or(self.player.orientation()));
+            }
+        }
+    }
+
+    pub fn damage_player(&mut self, amount: u32) {
+        self.player = self.player.damage(amount);
+    }
+
+    pub fn is_player_dead(&self) -> bool {
+        self.player.health() == 0
+    }
+
+    fn hurt_player(&mut self, damage: u32, attacker_direction: Angle) {
+        self.damage_player(damage);
+        let relative_direction = Angle::new(attacker_direction.to_radiant() - self.player.orientation().to_radiant());
+        self.damage_indicator = self.damage_indicator.with_hit(relative_direction);
+    }
+
+    fn sword(enemies: &mut [Enemy], player: Player, weapon: &mut Weapon, range_distance: f32, range_angle: Angle, parallel: bool) {
+        let configuration = weapon.configuration();
+
+        let hits: Vec<Option<Enemy>> = decide(enemies, parallel, |enemy| {
+            let distance = player.position().distance(&enemy.position());
+            let look_at = Vector::from_angle(player.orientation());
+            let enemy_look = Vector::new(*player.position(), enemy.position());
+
+            let hit = !enemy.is_dead() && look_at.angle(enemy_look)
+                .map(|angle| distance < range_distance + ENEMY_SIZE && angle.to_radiant() < range_angle.to_radiant())
+                .unwrap_or(false);
+
+            if hit {
+                let direction = signed_angle(*player.position(), enemy.position()).unwrap_or(player.orientation());
+                Some(enemy.damage(configuration.damage()).knock_back(direction, configuration.knockback()))
+            } else {
+                None
+            }
+        });
+
+        for (enemy, hit) in enemies.iter_mut().zip(hits) {
+            if let Some(hit) = hit {
+                *enemy = hit;
+                weapon.notify_hit();
+            }
+        }
+    }
+
+    pub fn handle_action(&mut self) {
+        if let Some(map_point) = self.facing() {
+            if self.is_unlocked(map_point) {
+                self.actions.activate(map_point.x(), map_point.y());
+            }
+        }
+    }
+
+    pub fn interaction_hint(&self) -> Option<InteractionKind> {
+        let position = *self.player.position();
+        let closest = project(position, self.player.orientation(), &self.map, &self.actions)
+            .first()
+            .map(|closest| (closest.distance(), closest.map_point()));
+
+        let wall_distance = closest.map_or(f32::MAX, |(distance, _)| distance);
+        let direction = Vector::from_angle(self.player.orientation());
+        let pickup_ahead = self.pickups.iter().any(|pickup| {
+            let to_pickup = Vector::new(position, pickup.position());
+            let along = direction.scalar(to_pickup);
+            along > 0.0 && along < wall_distance && along - PICKUP_DISTANCE < ACTION_DISTANCE
+                && direction.cross(to_pickup).abs() < PICKUP_DISTANCE
+        });
+        if pickup_ahead {
+            return Some(InteractionKind::Pickup);
+        }
+
+        let map_point = closest
+            .filter(|(distance, _)| *distance < ACTION_DISTANCE)
+            .map(|(_, map_point)| map_point)?;
+        match self.map.paving_at(map_point.x(), map_point.y()) {
+            Some(Tile::DYNAMIC(_, _, _)) if self.is_unlocked(map_point) => Some(InteractionKind::Door),
+            Some(Tile::DYNAMIC(_, _, _)) => Some(InteractionKind::LockedDoor),
+            _ => None,
+        }
+    }
+
+    fn update_proximity_doors(&mut self) {
+        let position = *self.player.position();
+        let map = &self.map;
+        let keys = &self.keys;
+        self.actions.notify_approach(self.approach_position, position, |x, y| {
+            map.lock_at(x, y).map(|key| keys.contains(&key)).unwrap_or(true)
+        });
+        self.approach_position = Some(position);
+    }
+
+    fn is_unlocked(&self, map_point: MapPoint) -> bool {
+        self.map.lock_at(map_point.x(), map_point.y())
+            .map(|key| self.has_key(key))
+            .unwrap_or(true)
+    }
+
+    fn facing(&self) -> Option<MapPoint> {
+        self.pick_facing(ACTION_DISTANCE)
+    }
+
+    pub fn pick_facing(&self, max_distance: f32) -> Option<MapPoint> {
+        project(*self.player.position(), self.player.orientation(), &self.map, &self.actions)
+            .first()
+            .filter(|closest| closest.distance() < max_distance)
+            .map(|closest| closest.map_point())
+    }
+
+    pub fn handle_shoot(&mut self) {
+        self.weapons[self.current_weapon_index].action();
+    }
+
+    pub fn set_view(&mut self, view: ViewScreen) {
+        self.view = view;
+    }
+
+    pub fn set_letterbox(&mut self, fraction: f32) {
+        self.letterbox = self.letterbox.with_target(fraction);
+    }
+
+    // One fixed step of simulation: the same sequence of ticks always gives the same level
+    pub fn tick(&mut self, force: Force, microseconds: u128) {
+        self.notify_elapsed(microseconds);
+        self.apply_forces(force, microseconds);
+        self.apply_shoots();
+    }
+
+    pub fn notify_elapsed(&mut self, microseconds: u128) {
+        self.alert_enemies();
+        self.sound_events.clear();
+        self.explore();
+        self.update_proximity_doors();
+        match self.animation_distance {
+            Some(distance) => self.actions.notify_elapsed_around(microseconds, *self.player.position(), distance),
+            None => self.actions.notify_elapsed(microseconds),
+        }
+        for weapon in self.weapons.iter_mut() {
+            weapon.notify_elapsed(microseconds);
+        }
+        self.letterbox = self.letterbox.notify_elapsed(microseconds);
+        self.damage_indicator = self.damage_indicator.notify_elapsed(microseconds);
+        self.notify_spawners(microseconds);
+        self.move_enemies(microseconds);
+        self.despawn_enemies(microseconds);
+        self.enemies_attack(microseconds);
+        self.enemies_strike(microseconds);
+        self.update_projectiles(microseconds);
+    }
+
+    pub fn generate_actions(&self) -> Vec<DrawAction> {
+        let mut layers = LayeredActions::new();
+        let mut actions_ordered: Vec<DrawActionZIndex> = vec![];
+
+        // The world is rendered for the band left between the letterbox bars, then moved into it
+        let bar_height = self.letterbox.bar_height(self.view);
+        let band = self.view.cropped((self.view.height() - 2 * bar_height) as u16);
+        let scene = band.with_angle(self.view_angle()).scaled(self.render_scale).with_horizon_shift(self.horizon_shift());
+
+        layers.add(DrawLayer::Background, build_clear_actions());
+        layers.add(DrawLayer::Background, build_background_actions(scene, self.map.ceiling_color(), self.map.floor_color(), self.map.ambient()));
+        if let Some(backdrop) = self.map.backdrop() {
+            layers.add(DrawLayer::Background, build_backdrop(scene, self.player.orientation(), backdrop, self.map.ambient()));
+        }
+
+        actions_ordered.extend(build_walls(
+            scene,
+            self.player.position(),
+            self.player.orientation(),
+            &self.map,
+            &self.actions,
+        ));
+
+        actions_ordered.extend(build_enemies(
+            scene,
+            *self.player.position(),
+            &self.player.orientation(),
+            &self.enemies,
+            self.map.ambient(),
+        ));
+
+        actions_ordered.extend(build_projectiles(
+            scene,
+            *self.player.position(),
+            &self.player.orientation(),
+            &self.projectiles,
+            self.map.ambient(),
+        ));
+
+        actions_ordered.extend(build_pickups(
+            scene,
+            *self.player.position(),
+            &self.player.orientation(),
+            &self.pickups,
+            self.map.ambient(),
+        ));
+
+        actions_ordered.sort_by(|a, b| a.depth_order(b));
+        layers.add(DrawLayer::World, actions_ordered.iter().map(|ordered| ordered.action().clone()));
+
+        if self.render_scale < 1.0 || bar_height > 0 {
+            layers.map(DrawLayer::Background, |action| upscale(action, scene, band, bar_height));
+            layers.map(DrawLayer::World, |action| upscale(action, scene, band, bar_height));
+        }
+        // Walls close to the player overflow the band, the bars are drawn over them but under the HUD
+        layers.add(DrawLayer::Frame, self.letterbox.generate_actions(self.view));
+
+        layers.add(DrawLayer::Overlay, build_weapons(self.view, *self.current_weapon()));
+
+        if let Some(crosshair) = self.crosshair {
+            layers.add(DrawLayer::Overlay, crosshair.generate_actions(self.view, self.interaction_hint()));
+        }
+
+        if self.minimap {
+            layers.add(DrawLayer::Hud, build_minimap(self.view, &self.map, &self.player, &self.explored, MINIMAP_SCALE));
+        }
+
+        if let Some(font) = self.hud_font {
+            let health = self.player.health();
+            let max_health = self.player.stats().health();
+            layers.add(DrawLayer::Hud, build_hud(self.view, font, health, max_health, self.current_weapon().ammo()));
+        }
+
+        if let Some(compass) = self.compass {
+            layers.add(DrawLayer::Hud, compass.generate_actions(self.view, self.player.orientation()));
+        }
+
+        layers.add(DrawLayer::Hud, self.damage_indicator.generate_actions(self.view));
+
+        layers.into_actions()
+    }
+
+    pub fn save_state(&self) -> LevelState {
+        let player = SavedPlayer::new(*self.player.position(), self.player.orientation(), self.player.health());
+        let enemies = self.enemies.iter()
+            .map(|enemy| SavedEnemy::new(enemy.origin(), enemy.position(), enemy.health()))
+            .collect();
+
+        let ammo = self.weapons.iter().map(|weapon| weapon.ammo()).collect();
+        let spawned = self.spawners.iter().map(|spawner| spawner.spawned()).collect();
+
+        LevelState::new(player, enemies, self.actions.doors())
+            .with_map(self.map_name.clone())
+            .with_weapons(self.current_weapon_index, ammo)
+            .with_spawned(spawned)
+    }
+
+    pub fn load_state(&mut self, state: &LevelState) -> Result<(), String> {
+        if state.map() != self.map_name.as_deref() {
+            return Err(format!("Saved state belongs to map {}", state.map().unwrap_or("unknown")));
+        }
+        if state.spawned().len() > self.spawners.len() {
+            return Err(String::from("Saved state does not match the spawners of the level"));
+        }
+
+        let map_enemies = self.map.generate_enemies();
+        let enemies = state.enemies().iter()
+            .map(|saved| {
+                let enemy = match saved.origin() {
+                    EnemyOrigin::Map(index) => map_enemies.get(index).copied(),
+                    EnemyOrigin::Spawner(index) => self.spawners.get(index)
+                        .map(|spawner| spawner.build_enemy(saved.position()).with_origin(saved.origin())),
+                };
+                enemy
+                    .map(|enemy| enemy.with_position(saved.position()).with_health(saved.health()))
+                    .ok_or_else(|| String::from("Saved state does not match the enemies of the level"))
+            })
+            .collect::<Result<Vec<Enemy>, String>>()?;
+
+        let mut actions = Actions::new(&self.map);
+        for door in state.doors() {
+            actions.restore(door)?;
+        }
+
+        let saved_player = state.player();
+        self.player = Player::new(saved_player.position(), saved_player.orientation(), self.player.stats())
+            .with_health(saved_player.health());
+        self.enemies = enemies;
+        for (index, spawner) in self.spawners.iter_mut().enumerate() {
+            *spawner = spawner.with_spawned(state.spawned().get(index).copied().unwrap_or(0));
+        }
+        self.actions = actions;
+        self.projectiles = vec![];
+        // Weapons picked up after the save are kept, as their pickups are not restored either
+        for (weapon, ammo) in self.weapons.iter_mut().zip(state.ammo()) {
+            if let Some(ammo) = ammo {
+                weapon.restore_ammo(*ammo);
+            }
+        }
+        if state.current_weapon() < self.weapons.len() {
+            self.current_weapon_index = state.current_weapon();
+        }
+        Ok(())
+    }
+
+    pub fn teleport(&mut self, to: Position) {
+        self.player = self.player.with_position(to);
+    }
+
+    fn constrains(&self, start: Position, end: Position, collision_radius: f32) -> Position {
+        let angle_opt = signed_angle(start, end);
+        if angle_opt.is_none() {
+            return end;
+        }
+
+        let angle = angle_opt.unwrap();
+
+        let angle_x = angle.align_to_x();
+        let angle_y = angle.align_to_y();
+
+        let distance_x = self.distance(start, angle_x) - collision_radius;
+        let distance_y = self.distance(start, angle_y) - collision_radius;
+
+        let mov_x = (end.x() - start.x()).abs();
+        let mov_y = (end.y() - start.y()).abs();
+
+        let should_go_x = self.deadband(self.min(mov_x, distance_x));
+        let should_go_y = self.deadband(self.min(mov_y, distance_y));
+
+        start
+            .with_x(start.x() + (should_go_x * angle.cos().signum()))
+            .with_y(start.y() + (should_go_y * angle.sin().signum()))
+    }
+
+    // Pushes the end of a move out of living enemies, along the enemy to player direction so
+    // walking into an enemy slides around it, and stops moves crossing an enemy at its edge
+    fn collide_with_enemies(&self, start: Position, end: Position) -> Option<Position> {
+        let radius = self.enemy_collision_distance;
+
+        self.enemies.iter()
+            .filter(|enemy| !enemy.is_dead())
+            .fold(None, |collided, enemy| {
+                let position = collided.unwrap_or(end);
+                let center = enemy.position();
+                let distance = center.distance(&position);
+                if distance < radius {
+                    if distance == 0.0 {
+                        return Some(start);
+                    }
+                    let ratio = radius / distance;
+                    return Some(Position::new(center.x() + (position.x() - center.x()) * ratio, center.y() + (position.y() - center.y()) * ratio));
+                }
+
+                let path = Vector::new(start, position);
+                let length = path.length();
+                let start_distance = start.distance(&center);
+                if length == 0.0 || start_distance < radius {
+                    return collided;
+                }
+
+                let along = path.scalar(Vector::new(start, center)) / length;
+                let aside_squared = start_distance * start_distance - along * along;
+                if along <= 0.0 || aside_squared >= radius * radius {
+                    return collided;
+                }
+
+                let entry = (along - (radius * radius - aside_squared).sqrt()) / length;
+                if entry >= 1.0 {
+                    return collided;
+                }
+                Some(Position::new(start.x() + (position.x() - start.x()) * entry, start.y() + (position.y() - start.y()) * entry))
+            })
+    }
+
+    fn min(&self, a: f32, b: f32) -> f32 {
+        if a < b {
+            a
+        } else {
+            b
+        }
+    }
+
+    fn deadband(&self, movement: f32) -> f32 {
+        if movement.abs() < self.collision_epsilon {
+            0.0
+        } else {
+            movement
+        }
+    }
+
+    fn distance(&self, start: Position, angle: Angle) -> f32 {
+        let projected: Vec<ProjectedPoint> = project(start, angle, &self.map, &self.actions)
+            .iter()
+            .filter(|projection| projection.blocking())
+            .cloned()
+            .collect();
+
+        if !projected.is_empty() {
+            projected[0].distance()
+        } else {
+            0.0
+        }
+    }
+}
+
+// Decisions are taken from a read-only snapshot, in parallel or not, and always come back in enemy order
+fn decide<T, F>(enemies: &[Enemy], parallel: bool, decision: F) -> Vec<T>
+    where T: Send, F: Fn(&Enemy) -> T + Sync + Send {
+    if parallel {
+        enemies.par_iter().map(decision).collect()
+    } else {
+        enemies.iter().map(decision).collect()
+    }
+}
+
+fn has_line_of_sight(from: Position, to: Position, map: &Map, actions: &Actions) -> bool {
+    let target_distance = from.distance(&to);
+
+    match signed_angle(from, to) {
+        Some(angle) => project(from, angle, map, actions)
+            .iter()
+            .filter(|projection| projection.blocking())
+            .all(|projection| projection.distance() >= target_distance),
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod level_test {
+    use std::f32::consts::PI;
+
+    use spectral::prelude::*;
+
+    use crate::domain::actors::actor::{AccelerationStats, AimAssist, DEFAULT_COLLISION_RADIUS, Despawn, EnemyState, MeleeAttack, Perception, Player, PlayerStats, RangedAttack, SpeedStats, StaminaStats, WallBump};
+    use crate::domain::actors::pickup::{PickupKind, PickupType};
+    use crate::domain::actors::projectile::Projectile;
+    use crate::domain::actors::shoot::WeaponKind;
+    use crate::domain::actors::spawner::SpawnerType;
+    use crate::domain::control::actions::{ActionStateBuilder, Actions, LinearActionState};
+    use crate::domain::control::force::{Force, InputForce};
+    use crate::domain::maths::{Angle, ANGLE_0, ANGLE_90, ANGLE_LEFT, ANGLE_RIGHT, ANGLE_UP, signed_angle};
+    use crate::domain::sound::{SoundEvent, SoundKind};
+    use crate::domain::topology::coord::{MapPoint, Position};
+    use crate::domain::topology::door::LateralOpening;
+    use crate::domain::topology::map::map_test::{build_map, build_map_with_configuration, configuration_with_weapon, default_configuration, default_weapon};
+    use crate::domain::topology::index::TextureIndex;
+    use crate::domain::topology::map::{Backdrop, EnemyType, Fog, KeyColor, Map, MapConfiguration, Tile};
+    use crate::domain::ui::color::{Color, WHITE};
+    use crate::domain::ui::crosshair::{Crosshair, InteractionKind};
+    use crate::domain::ui::draw_action::DrawAction;
+    use crate::domain::ui::view::{AspectMode, ViewScreen};
+    use crate::domain::ui::view_model::ViewModel;
+
+    use super::{has_line_of_sight, Level};
+
+    const TOLERANCE: f32 = DEFAULT_COLLISION_RADIUS + 0.01;
+
+    #[test]
+    fn actions_should_start_with_a_clear() {
+        let view = ViewScreen::new(0, 0, ANGLE_90);
+        let level = Level::new(view, build_map("r#")).unwrap();
+
+        let actions = level.generate_actions();
+
+        assert_that!(actions.len()).is_greater_than(0);
+
+        assert!(matches!(actions[0], DrawAction::Clear { .. }));
+    }
+
+    #[test]
+    fn actions_should_draw_ceiling() {
+        let view = ViewScreen::new(200, 100, ANGLE_90);
+        let level = Level::new(view, build_map("r#")).unwrap();
+        let mut found = false;
+
+        let actions = level.generate_actions();
+
+        for action in actions {
+            if let DrawAction::Rectangle(start, end, _) = action {
+                if start.x() == 0 && start.y() == 0 && end.x() == 100 && end.y() == 100 {
+                    found = true
+                }
+            }
+        }
+
+        assert_that!(found).is_true();
+    }
+
+    #[test]
+    fn actions_should_draw_floor() {
+        let view = ViewScreen::new(200, 100, ANGLE_90);
+
+        let level = Level::new(view, build_map("r#")).unwrap();
+        let mut found = false;
+
+        let actions = level.generate_actions();
+
+        for action in actions {
+            if let DrawAction::Rectangle(start, end, _) = action {
+                if start.x() == 0 && start.y() == 100 && end.x() == 100 && end.y() == 200 {
+                    found = true
+                }
+            }
+        }
+
+        assert_that!(found).is_true();
+    }
+
+    #[test]
+    fn apply_force_should_constraint_moves() {
+        let map = build_map("#r#");
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+
+        let mut level = Level::new(view, map).unwrap();
+
+        level.apply_forces(Force::new(ANGLE_RIGHT, 10.0, ANGLE_0), 1000000);
+        assert_that!(level.player.position().x()).is_less_than_or_equal_to(2.0);
+    }
+
+    #[test]
+    fn moving_into_an_enemy_should_stop_at_its_edge() {
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let mut level = Level::new(view, build_map("#r  E   #")).unwrap();
+
+        level.apply_forces(Force::new(ANGLE_RIGHT, 10.0, ANGLE_0), 1000000);
+
+        assert_that!(level.player.position().x()).is_close_to(4.0, 0.01);
+    }
+
+    #[test]
+    fn ending_a_move_inside_an_enemy_should_push_the_player_back_to_its_edge() {
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let mut level = Level::new(view, build_map("#r  E   #")).unwrap();
+        level.teleport(Position::new(3.8, 0.5));
+
+        level.apply_forces(Force::new(ANGLE_RIGHT, 0.5, ANGLE_0), 1000000);
+        let first = *level.player.position();
+        level.apply_forces(Force::new(ANGLE_RIGHT, 0.5, ANGLE_0), 1000000);
+
+        assert_that!(first.x()).is_close_to(4.0, 0.01);
+        assert_that!(level.player.position().x()).is_close_to(4.0, 0.01);
+    }
+
+    #[test]
+    fn corpses_should_not_block_the_player() {
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let mut level = Level::new(view, build_map("#r  E   #")).unwrap();
+        level.enemies[0] = level.enemies[0].with_health(0);
+
+        level.apply_forces(Force::new(ANGLE_RIGHT, 10.0, ANGLE_0), 1000000);
+
+        assert_that!(level.player.position().x()).is_greater_than(7.0);
+    }
+
+    #[test]
+    fn larger_collision_radius_should_stop_the_player_farther_from_the_wall() {
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let stop = |radius: f32| {
+            let mut level = Level::new(view, build_map("#r   #")).unwrap();
+            let player = level.player;
+            level.player = player.with_stats(player.stats().with_collision_radius(radius));
+            level.apply_forces(Force::new(ANGLE_RIGHT, 10.0, ANGLE_0), 1000000);
+            level.player.position().x()
+        };
+
+        assert_that!(stop(0.1)).is_close_to(4.9, 0.01);
+        assert_that!(stop(0.4)).is_close_to(4.6, 0.01);
+    }
+
+    #[test]
+    fn same_ticks_should_give_the_same_level() {
+        let play = || {
+            let view = ViewScreen::new(100, 100, ANGLE_90);
+            let mut level = Level::new(view, build_map("#########\n#r     E#\n#  D    #\n#########")).unwrap();
+            level.handle_shoot();
+            for frame in 0..120 {
+                let rotation = if frame % 10 == 0 { Angle::new(0.05) } else { ANGLE_0 };
+                level.tick(Force::new(ANGLE_RIGHT, 1.0, rotation), 16666);
+            }
+            level
+        };
+
+        let first = play();
+        let second = play();
+
+        assert_that!(first.player.position().x()).is_equal_to(second.player.position().x());
+        assert_that!(first.player.position().y()).is_equal_to(second.player.position().y());
+        assert_that!(first.player.orientation().to_radiant()).is_equal_to(second.player.orientation().to_radiant());
+        assert_that!(first.enemies[0].position().x()).is_equal_to(second.enemies[0].position().x());
+        assert_that!(first.enemies[0].health()).is_equal_to(second.enemies[0].health());
+    }
+
+    #[test]
+    fn apply_force_should_apply_not_constrained_moves() {
+        let map = build_map("#r#");
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+
+        let mut level = Level::new(view, map).unwrap();
+
+        level.apply_forces(Force::new(ANGLE_RIGHT, 0.2, ANGLE_0), 1000000);
+
+        assert_that!(level.player.position().x()).is_greater_than(1.5);
+    }
+
+    #[test]
+    fn strafing_right_should_move_the_player_to_its_right() {
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let mut level = Level::new(view, build_map("#####\n#   #\n# u #\n#   #\n#####")).unwrap();
+        let start = *level.player.position();
+
+        level.apply_forces(InputForce::new(1.0, 1.0).strafe_right(), 200000);
+
+        assert_that!(level.player.position().x()).is_greater_than(start.x());
+        assert_that!(level.player.position().y()).is_close_to(start.y(), 0.01);
+    }
+
+    #[test]
+    fn snapped_turn_should_end_on_a_multiple_of_90_degrees() {
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let mut level = Level::new(view, build_map("#####\n#   #\n# r #\n#   #\n#####")).unwrap().with_turn_snap();
+        level.apply_forces(Force::new(ANGLE_RIGHT, 0.0, Angle::new(0.3)), 16000);
+        level.apply_forces(Force::new(ANGLE_RIGHT, 0.0, ANGLE_0), 16000);
+
+        level.apply_forces(Force::new(ANGLE_RIGHT, 0.0, Angle::new(-0.02)), 16000);
+        level.apply_forces(Force::new(ANGLE_RIGHT, 0.0, Angle::new(-0.02)), 16000);
+
+        let quarters = level.player.orientation().to_radiant() / (PI / 2.0);
+        assert_that!(quarters).is_close_to(quarters.round(), 0.001);
+        assert_that!(level.player.orientation().cos()).is_close_to(1.0, 0.001);
+        assert_that!(level.player.orientation().sin()).is_close_to(0.0, 0.001);
+    }
+
+    #[test]
+    fn running_into_a_wall_at_speed_should_emit_a_bump() {
+        let mut level = level_with_wall_bump(10.0);
+
+        level.apply_forces(Force::new(ANGLE_RIGHT, 1.0, ANGLE_0), 100000);
+
+        assert_that!(level.sound_events().iter().map(|event| event.kind()).collect::<Vec<SoundKind>>())
+            .is_equal_to(vec![SoundKind::Bump]);
+        assert_that!(level.player.health()).is_equal_to(95);
+    }
+
+    #[test]
+    fn gently_approaching_a_wall_should_not_emit_a_bump() {
+        let mut level = level_with_wall_bump(0.5);
+
+        for _ in 0..20 {
+            level.apply_forces(Force::new(ANGLE_RIGHT, 1.0, ANGLE_0), 100000);
+        }
+
+        assert_that!(level.sound_events().is_empty()).is_true();
+        assert_that!(level.player.health()).is_equal_to(100);
+    }
+
+    #[test]
+    fn drained_sounds_should_keep_the_nearest_ones_of_every_tick() {
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let mut level = Level::new(view, build_map("#r      #")).unwrap().with_max_sounds_per_frame(2);
+
+        level.emit_sound(SoundKind::EnemyShot, Position::new(6.5, 0.5));
+        level.notify_elapsed(16000);
+        level.emit_sound(SoundKind::EnemyShot, Position::new(2.5, 0.5));
+        level.emit_sound(SoundKind::EnemyShot, Position::new(4.5, 0.5));
+
+        let drained = level.drain_sounds();
+
+        assert_that!(drained.iter().map(|event| event.position().x()).collect::<Vec<f32>>()).is_equal_to(vec![2.5, 4.5]);
+        assert_that!(level.drain_sounds()).is_empty();
+    }
+
+    #[test]
+    fn distant_hidden_enemy_should_be_despawned_after_the_grace_period() {
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let mut level = Level::new(view, build_map("#r #  E#")).unwrap().with_despawn(Despawn::new(2.0, 1.0));
+
+        level.notify_elapsed(600000);
+        assert_that!(level.enemies).has_length(1);
+
+        level.notify_elapsed(600000);
+        assert_that!(level.enemies).has_length(0);
+    }
+
+    #[test]
+    fn despawned_enemy_should_be_spawned_again_by_its_spawner() {
+        let mut configuration = default_configuration();
+        configuration.add_spawner('S', SpawnerType::new(EnemyType::new(TextureIndex::new(5), TextureIndex::new(5), 100), 1.0, 1));
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let mut level = Level::new(view, build_map_with_configuration("#r #  S#", configuration)).unwrap().with_despawn(Despawn::new(2.0, 1.5));
+
+        level.notify_elapsed(1000000);
+        assert_that!(level.enemies).has_length(1);
+
+        level.notify_elapsed(1000000);
+        assert_that!(level.enemies).has_length(0);
+
+        level.notify_elapsed(1000000);
+        assert_that!(level.enemies).has_length(1);
+    }
+
+    #[test]
+    fn distant_visible_enemy_should_not_be_despawned() {
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let mut level = Level::new(view, build_map("#r    E#")).unwrap().with_despawn(Despawn::new(2.0, 1.0));
+
+        level.notify_elapsed(600000);
+        level.notify_elapsed(600000);
+
+        assert_that!(level.enemies).has_length(1);
+    }
+
+    #[test]
+    fn enemy_should_be_in_the_list_after_the_wall_behind_him() {
+        let map = build_map("#rE #");
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+
+        let mut level = Level::new(view, map).unwrap();
+        level.teleport(Position::new(2.3, 0.5));
+
+        let actions = level.generate_actions();
+
+        let position_sprite = actions
+            .iter()
+            .position(|action| matches!(action, DrawAction::Sprite(_, _, _, _)));
+        let position_wall = actions
+            .iter()
+            .enumerate()
+            .filter(|(_, action)| matches!(action, DrawAction::TexturedLine(_, _, _, _, _, _)))
+            .map(|(index, _)| index)
+            .max();
+
+        assert_that!(position_sprite).is_greater_than(position_wall);
+    }
+
+    #[test]
+    fn enemy_behind_the_player_should_not_be_drawn() {
+        let map = build_map("#E  r  #");
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let level = Level::new(view, map).unwrap();
+
+        let actions = level.generate_actions();
+
+        let enemy_sprites = actions
+            .iter()
+            .filter(|action| matches!(action, DrawAction::Sprite(_, _, texture, _) if *texture == TextureIndex::new(5)))
+            .count();
+
+        assert_that!(enemy_sprites).is_equal_to(0);
+    }
+
+    #[test]
+    fn enemy_should_be_in_the_list_before_the_wall_before_him() {
+        let map = build_map("#r# E ");
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let level = Level::new(view, map).unwrap();
+
+        let actions = level.generate_actions();
+
+        let position_sprite = actions
+            .iter()
+            .position(|action| matches!(action, DrawAction::Sprite(_, _, _, _)));
+        let position_wall = actions
+            .iter()
+            .enumerate()
+            .filter(|(_, action)| matches!(action, DrawAction::TexturedLine(_, _, _, _, _, _)))
+            .map(|(index, _)| index)
+            .min();
+
+        assert_that!(position_sprite).is_less_than(position_wall);
+    }
+
+    #[test]
+    fn wall_behind_a_field_should_be_drawn_with_field_tint() {
+        let tint = middle_column_tint(build_map("#r F#"));
+
+        // Facing east, the wall is a vertical face and is drawn darker
+        assert_that!(tint).is_some().is_equal_to(Color::new(0, 0, 255).scale(0.75));
+    }
+
+    #[test]
+    fn wall_without_field_should_not_be_tinted() {
+        let tint = middle_column_tint(build_map("#r  #"));
+
+        assert_that!(tint).is_some().is_equal_to(WHITE.scale(0.75));
+    }
+
+    #[test]
+    fn wall_with_a_palette_tint_should_be_drawn_modulated() {
+        let mut configuration = default_configuration();
+        configuration.add_tint('#', Color::new(40, 80, 160));
+
+        let tinted = middle_column_tint(build_map_with_configuration("#r  #", configuration));
+        let untinted = middle_column_tint(build_map("#r  #"));
+
+        assert_that!(untinted).is_some().is_equal_to(WHITE.scale(0.75));
+        assert_that!(tinted).is_some().is_equal_to(Color::new(40, 80, 160).scale(0.75));
+    }
+
+    #[test]
+    fn maintained_aspect_should_keep_wall_proportions_after_resize() {
+        let view = ViewScreen::new(101, 100, ANGLE_90);
+        let mut level = Level::new(view, build_map("#r  #")).unwrap();
+        let before = middle_column_height(&level);
+
+        level.set_view(view.resize(300, 101, AspectMode::Maintain));
+
+        assert_that!(middle_column_height(&level)).is_some().is_equal_to(before.unwrap());
+    }
+
+    #[test]
+    fn only_tiles_reached_by_the_view_should_be_explored_on_the_minimap() {
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let mut level = Level::new(view, build_map("#######\n#  #  #\n#r #  #\n#######")).unwrap();
+
+        level.notify_elapsed(16000);
+        let hidden_minimap = rectangles(&level);
+        level.toggle_minimap();
+
+        assert_that!(level.explored().contains(&MapPoint::new(2, 1))).is_true();
+        assert_that!(level.explored().contains(&MapPoint::new(3, 1))).is_true();
+        assert_that!(level.explored().contains(&MapPoint::new(4, 1))).is_false();
+        assert_that!(level.explored().contains(&MapPoint::new(5, 2))).is_false();
+        assert_that!(rectangles(&level) - hidden_minimap).is_equal_to(level.explored().len() + 1);
+    }
+
+    fn rectangles(level: &Level) -> usize {
+        level.generate_actions()
+            .iter()
+            .filter(|action| matches!(action, DrawAction::Rectangle(_, _, _)))
+            .count()
+    }
+
+    #[test]
+    fn walking_over_a_weapon_pickup_should_add_it_to_the_inventory() {
+        let mut configuration = default_configuration();
+        let weapon = default_weapon().with_kind(WeaponKind::hitscan(10.0));
+        configuration.add_pickup('w', PickupType::new(TextureIndex::new(7), PickupKind::Weapon(Box::new(weapon))));
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let mut level = Level::new(view, build_map_with_configuration("#####\n#rw #\n#####", configuration)).unwrap();
+
+        assert_that!(level.pickups().len()).is_equal_to(1);
+        level.apply_forces(Force::new(ANGLE_RIGHT, 1.0, ANGLE_0), 100000);
+
+        assert_that!(level.player().position().x()).is_greater_than(2.5);
+        assert_that!(level.weapons().len()).is_equal_to(2);
+        assert_that!(matches!(level.weapons()[1].configuration().kind(), WeaponKind::Hitscan { .. })).is_true();
+        assert_that!(level.pickups().len()).is_equal_to(0);
+    }
+
+    #[test]
+    fn weapon_should_be_selected_by_index() {
+        let mut level = level_with_two_weapons();
+
+        level.select_weapon(1);
+
+        assert_that!(level.current_weapon_index).is_equal_to(1);
+        assert_that!(matches!(level.current_weapon().configuration().kind(), WeaponKind::Hitscan { .. })).is_true();
+    }
+
+    #[test]
+    fn out_of_range_weapon_selection_should_be_ignored() {
+        let mut level = level_with_two_weapons();
+
+        level.select_weapon(2);
+
+        assert_that!(level.current_weapon_index).is_equal_to(0);
+    }
+
+    #[test]
+    fn weapon_should_not_be_switched_mid_swing() {
+        let mut level = level_with_two_weapons();
+
+        level.handle_shoot();
+        level.notify_elapsed(50000);
+        level.select_weapon(1);
+        let during_swing = level.current_weapon_index;
+        level.notify_elapsed(1000000);
+        level.select_weapon(1);
+
+        assert_that!(during_swing).is_equal_to(0);
+        assert_that!(level.current_weapon_index).is_equal_to(1);
+    }
+
+    #[test]
+    fn walking_over_a_health_pickup_should_heal_the_player() {
+        let mut configuration = default_configuration();
+        configuration.add_pickup('h', PickupType::new(TextureIndex::new(7), PickupKind::Health(25)));
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let mut level = Level::new(view, build_map_with_configuration("#####\n#rh #\n#####", configuration)).unwrap();
+        level.damage_player(40);
+
+        level.apply_forces(Force::new(ANGLE_RIGHT, 1.0, ANGLE_0), 100000);
+
+        assert_that!(level.player().health()).is_equal_to(85);
+        assert_that!(level.pickups().len()).is_equal_to(0);
+    }
+
+    #[test]
+    fn health_pickup_should_not_heal_above_maximum_health() {
+        let mut configuration = default_configuration();
+        configuration.add_pickup('h', PickupType::new(TextureIndex::new(7), PickupKind::Health(25)));
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let mut level = Level::new(view, build_map_with_configuration("#####\n#rh #\n#####", configuration)).unwrap();
+        level.damage_player(10);
+
+        level.apply_forces(Force::new(ANGLE_RIGHT, 1.0, ANGLE_0), 100000);
+
+        assert_that!(level.player().health()).is_equal_to(100);
+    }
+
+    #[test]
+    fn walking_over_an_ammo_pickup_should_refill_the_current_weapon() {
+        let mut configuration = configuration_with_weapon(default_weapon().with_ammo(2));
+        configuration.add_pickup('a', PickupType::new(TextureIndex::new(7), PickupKind::Ammo(5)));
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let mut level = Level::new(view, build_map_with_configuration("#####\n#ra #\n#####", configuration)).unwrap();
+
+        level.apply_forces(Force::new(ANGLE_RIGHT, 1.0, ANGLE_0), 100000);
+
+        assert_that!(level.current_weapon().ammo()).is_equal_to(Some(7));
+    }
+
+    #[test]
+    fn ammo_pickup_should_refill_a_weapon_using_ammo_when_the_current_one_does_not() {
+        let mut configuration = default_configuration();
+        configuration.add_weapon(default_weapon().with_ammo(2));
+        configuration.add_pickup('a', PickupType::new(TextureIndex::new(7), PickupKind::Ammo(5)));
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let mut level = Level::new(view, build_map_with_configuration("#####\n#ra #\n#####", configuration)).unwrap();
+
+        level.apply_forces(Force::new(ANGLE_RIGHT, 1.0, ANGLE_0), 100000);
+
+        assert_that!(level.weapons().iter().map(|weapon| weapon.ammo()).collect::<Vec<Option<u32>>>()).is_equal_to(vec![None, Some(7)]);
+        assert_that!(level.pickups).is_empty();
+    }
+
+    #[test]
+    fn ammo_pickup_should_stay_when_no_weapon_uses_ammo() {
+        let mut configuration = default_configuration();
+        configuration.add_pickup('a', PickupType::new(TextureIndex::new(7), PickupKind::Ammo(5)));
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let mut level = Level::new(view, build_map_with_configuration("#####\n#ra #\n#####", configuration)).unwrap();
+
+        level.apply_forces(Force::new(ANGLE_RIGHT, 1.0, ANGLE_0), 100000);
+
+        assert_that!(level.current_weapon().ammo()).is_none();
+        assert_that!(level.pickups).has_length(1);
+    }
+
+    #[test]
+    fn enemies_should_match_the_enemies_of_the_map() {
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let level = Level::new(view, build_map("#######\n#E  E #\n# r  E#\n#######")).unwrap();
+
+        assert_that!(level.enemies().len()).is_equal_to(3);
+        assert_that!(level.map().width()).is_equal_to(7);
+    }
+
+    #[test]
+    fn backdrop_should_fill_the_configured_rows_above_the_horizon() {
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let paving = "#####\n#   #\n# r #\n#   #\n#####";
+        let mut configuration = default_configuration();
+        configuration.set_backdrop(Backdrop::new(TextureIndex::new(9), 0.2));
+        let without = Level::new(view, build_map(paving)).unwrap();
+        let with = Level::new(view, build_map_with_configuration(paving, configuration)).unwrap();
+
+        let bands = |level: &Level| level.generate_actions()
+            .into_iter()
+            .filter_map(|action| match action {
+                DrawAction::TexturedLine(start, end, texture, _, _, _) if texture == TextureIndex::new(9) => Some((start.y(), end.y())),
+                _ => None,
+            })
+            .collect::<Vec<(i32, i32)>>();
+
+        assert_that!(bands(&without)).is_empty();
+        assert_that!(bands(&with)).has_length(100);
+        assert_that!(bands(&with).iter().all(|band| *band == (30, 50))).is_true();
+    }
+
+    #[test]
+    fn background_should_use_the_configured_ceiling_and_floor_colors() {
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let mut configuration = default_configuration();
+        configuration.set_ceiling_color(Color::new(1, 2, 3));
+        configuration.set_floor_color(Color::new(4, 5, 6));
+        let level = Level::new(view, build_map_with_configuration("#####\n#   #\n# r #\n#   #\n#####", configuration)).unwrap();
+
+        let rectangles = level.generate_actions()
+            .into_iter()
+            .filter_map(|action| match action {
+                DrawAction::Rectangle(start, _, color) if start.x() == 0 => Some((start.y(), color)),
+                _ => None,
+            })
+            .collect::<Vec<(i32, Color)>>();
+
+        assert_that!(rectangles).contains((0, Color::new(1, 2, 3)));
+        assert_that!(rectangles).contains((50, Color::new(4, 5, 6)));
+    }
+
+    #[test]
+    fn side_walls_should_be_shaded_differently_from_front_walls() {
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let center_tint = |paving: &str| Level::new(view, build_map(paving)).unwrap()
+            .generate_actions()
+            .into_iter()
+            .find_map(|action| match action {
+                DrawAction::TexturedLine(start, _, _, _, tint, shade) if start.x() == 50 => Some((tint, shade.amount())),
+                _ => None,
+            })
+            .unwrap();
+
+        let facing_east = center_tint("#####\n#   #\n# r #\n#   #\n#####");
+        let facing_north = center_tint("#####\n#   #\n# u #\n#   #\n#####");
+
+        assert_that!(facing_east.0).is_not_equal_to(facing_north.0);
+        assert_that!(facing_east.1).is_equal_to(1.0);
+        assert_that!(facing_north.1).is_equal_to(1.0);
+    }
+
+    #[test]
+    fn side_walls_should_stay_darker_in_a_light_fog() {
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let center_color = |paving: &str| {
+            let mut configuration = default_configuration();
+            configuration.set_fog(Fog::new(Color::new(240, 240, 240), 4.0));
+            Level::new(view, build_map_with_configuration(paving, configuration)).unwrap()
+                .generate_actions()
+                .into_iter()
+                .find_map(|action| match action {
+                    DrawAction::TexturedLine(start, _, _, _, tint, shade) if start.x() == 50 => Some((shade.apply(tint), shade.amount())),
+                    _ => None,
+                })
+                .unwrap()
+        };
+
+        let facing_east = center_color("#####\n#   #\n# r #\n#   #\n#####");
+        let facing_north = center_color("#####\n#   #\n# u #\n#   #\n#####");
+
+        assert_that!(facing_east.1).is_close_to(facing_north.1, 0.001);
+        assert_that!(facing_east.0.red()).is_less_than(facing_north.0.red());
+    }
+
+    #[test]
+    fn farther_walls_should_be_more_shaded_by_the_fog() {
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let shade = |paving: &str, fog: Option<Fog>| {
+            let mut configuration = default_configuration();
+            if let Some(fog) = fog {
+                configuration.set_fog(fog);
+            }
+            Level::new(view, build_map_with_configuration(paving, configuration)).unwrap()
+                .generate_actions()
+                .into_iter()
+                .find_map(|action| match action {
+                    DrawAction::TexturedLine(start, _, _, _, _, shade) if start.x() == 50 => Some(shade),
+                    _ => None,
+                })
+                .unwrap()
+        };
+        let fog = Fog::new(Color::new(10, 20, 30), 8.0);
+
+        let near = shade("#r  #", Some(fog));
+        let far = shade("#r      #", Some(fog));
+        let clear = shade("#r      #", None);
+
+        assert_that!(near.amount()).is_greater_than(far.amount());
+        assert_that!(far.amount()).is_less_than(clear.amount());
+        assert_that!(far.color()).is_equal_to(Color::new(10, 20, 30));
+    }
+
+    #[test]
+    fn lower_ambient_should_darken_every_wall_column_uniformly() {
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let paving = "#####\n#   #\n# r #\n#   #\n#####";
+        let mut configuration = default_configuration();
+        configuration.set_ambient(0.5);
+        let bright = Level::new(view, build_map(paving)).unwrap();
+        let dark = Level::new(view, build_map_with_configuration(paving, configuration)).unwrap();
+
+        let tints = |level: &Level| level.generate_actions()
+            .into_iter()
+            .filter_map(|action| match action {
+                DrawAction::TexturedLine(_, _, _, _, tint, _) => Some(tint),
+                _ => None,
+            })
+            .collect::<Vec<Color>>();
+
+        let (bright, dark) = (tints(&bright), tints(&dark));
+        assert_that!(dark).has_length(bright.len());
+        assert_that!(bright.iter().zip(&dark).all(|(bright, dark)| (bright.red() as i32 / 2 - dark.red() as i32).abs() <= 1)).is_true();
+    }
+
+    #[test]
+    fn half_render_scale_should_draw_half_as_many_wall_columns() {
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let level = Level::new(view, build_map("#####\n#   #\n# r #\n#   #\n#####")).unwrap()
+            .with_render_scale(0.5);
+
+        let columns: Vec<i32> = level.generate_actions()
+            .iter()
+            .filter_map(|action| match action {
+                DrawAction::TexturedLine(start, _, _, _, _, _) => Some(start.x()),
+                _ => None,
+            })
+            .collect();
+
+        assert_that!(columns.len()).is_equal_to(50);
+        assert_that!(columns.iter().max()).is_some().is_less_than(&100);
+    }
+
+    fn middle_column_height(level: &Level) -> Option<i32> {
+        level.generate_actions()
+            .iter()
+            .find_map(|action| match action {
+                DrawAction::TexturedLine(start, end, _, _, _, _) if start.x() == 50 => Some(end.y() - start.y()),
+                _ => None,
+            })
+    }
+
+    fn middle_column_tint(map: Map) -> Option<Color> {
+        let view = ViewScreen::new(100, 101, ANGLE_90);
+        let level = Level::new(view, map).unwrap();
+
+        level.generate_actions()
+            .iter()
+            .find_map(|action| match action {
+                DrawAction::TexturedLine(start, _, _, _, tint, _) if start.x() == 50 => Some(*tint),
+                _ => None,
+            })
+    }
+
+    #[test]
+    fn apply_force_should_constrains_move_by_sliding_through_the_wall_by_top() {
+        let map = build_map("#r#");
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+
+        let mut level = Level::new(view, map).unwrap();
+
+        level.apply_forces(
+            Force::new(Angle::new(PI / 16.0), 1.0, ANGLE_0),
+            10000000,
+        );
+
+        assert_that!(level.player.position().x()).is_close_to(2.0, TOLERANCE);
+        assert_that!(level.player.position().y()).is_greater_than(0.5);
+    }
+
+    #[test]
+    fn apply_force_should_constrains_move_by_sliding_through_the_wall_by_bottom() {
+        let map = build_map("#l#");
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+
+        let mut level = Level::new(view, map).unwrap();
+
+        level.apply_forces(Force::new(Angle::new(PI / 16.0), 1.0, ANGLE_0), 1000000);
+
+        assert_that!(level.player.position().x()).is_close_to(1.0, TOLERANCE);
+        assert_that!(level.player.position().y()).is_less_than(0.5);
+    }
+
+    #[test]
+    fn apply_force_should_constrains_move_by_sliding_through_the_wall_by_right() {
+        let map = build_map("#\nu\n#");
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+
+        let mut level = Level::new(view, map).unwrap();
+
+        level.apply_forces(
+            Force::new(Angle::new(-PI / 16.0), 1.0, ANGLE_0),
+            1000000,
+        );
+
+        assert_that!(level.player.position().x()).is_greater_than(0.5);
+        assert_that!(level.player.position().y()).is_close_to(2.0, TOLERANCE);
+    }
+
+    #[test]
+    fn apply_force_should_constrains_move_by_sliding_through_the_wall_by_left() {
+        let map = build_map("#\nd\n#");
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+
+        let mut level = Level::new(view, map).unwrap();
+
+        level.apply_forces(
+            Force::new(Angle::new(-PI / 16.0), 1.0, ANGLE_0),
+            1000000,
+        );
+
+        assert_that!(level.player.position().x()).is_less_than(0.5);
+        assert_that!(level.player.position().y()).is_close_to(1.0, TOLERANCE);
+    }
+
+    #[test]
+    fn pushing_into_a_wall_should_converge_to_a_stable_position() {
+        let map = build_map("#\nu\n#");
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+
+        let mut level = Level::new(view, map).unwrap();
+
+        let mut positions = vec![];
+        for _ in 0..300 {
+            level.apply_forces(Force::new(ANGLE_0, 1.0, ANGLE_0), 16000);
+            positions.push(*level.player.position());
+        }
+
+        let last = positions[positions.len() - 1];
+        let before_last = positions[positions.len() - 2];
+        assert_that!(last.x()).is_equal_to(before_last.x());
+        assert_that!(last.y()).is_equal_to(before_last.y());
+    }
+
+    #[test]
+    fn letterbox_should_confine_world_to_the_central_band() {
+        let map = build_map("#########\n#       #\n#       #\n#r      #\n#       #\n#       #\n#########");
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let mut level = Level::new(view, map).unwrap();
+        level.toggle_minimap();
+
+        level.set_letterbox(0.5);
+        level.notify_elapsed(10000000);
+
+        let actions = level.generate_actions();
+        let top_bar = actions.iter()
+            .position(|action| matches!(action, DrawAction::Rectangle(start, end, _)
+                if start.y() == 0 && end.y() == 25 && end.x() == 100))
+            .unwrap();
+
+        let world = &actions[..top_bar];
+        assert_that!(world.iter().any(|action| matches!(action, DrawAction::TexturedLine(_, _, _, _, _, _)))).is_true();
+        for action in world {
+            match action {
+                DrawAction::Rectangle(start, end, _) | DrawAction::Line(start, end, _) | DrawAction::TexturedLine(start, end, _, _, _, _) => {
+                    assert_that!(start.y()).is_greater_than_or_equal_to(25);
+                    assert_that!(end.y()).is_less_than_or_equal_to(75);
+                }
+                _ => {}
+            }
+        }
+        assert!(matches!(&actions[top_bar + 1], DrawAction::Rectangle(start, end, _)
+            if start.y() == 75 && end.y() == 100 && end.x() == 100));
+        assert_that!(actions.len()).is_greater_than(top_bar + 2);
+    }
+
+    #[test]
+    fn hit_enemy_should_be_knocked_back_away_from_player() {
+        let mut level = level_with_knockback("#rE      #", 1.0);
+
+        hit_enemy_in_front(&mut level);
+
+        assert_that!(level.enemies[0].position().x()).is_close_to(3.5, 0.01);
+        assert_that!(level.enemies[0].position().y()).is_close_to(0.5, 0.01);
+    }
+
+    #[test]
+    fn projectile_should_knock_back_with_the_weapon_that_fired_it() {
+        let mut level = level_with_knockback("#rE      #", 1.0);
+        level.projectiles.push(Projectile::new(Position::new(2.3, 0.5), ANGLE_0, SpeedStats::new(1.0), 10, TextureIndex::new(0))
+            .with_friendly(true)
+            .with_knockback(0.0));
+        let health = level.enemies[0].health();
+
+        level.update_projectiles(100000);
+        for _ in 0..20 {
+            level.notify_elapsed(16000);
+        }
+
+        assert_that!(level.enemies[0].health()).is_less_than(health);
+        assert_that!(level.enemies[0].position().x()).is_close_to(2.5, 0.01);
+    }
+
+    #[test]
+    fn knockback_should_be_stopped_by_walls() {
+        let mut level = level_with_knockback("#rE #", 2.0);
+
+        hit_enemy_in_front(&mut level);
+
+        assert_that!(level.enemies[0].position().x()).is_close_to(4.0, TOLERANCE);
+    }
+
+    #[test]
+    fn player_collision_radius_should_not_change_how_close_enemies_are_knocked_to_walls() {
+        let knocked = |radius: f32| {
+            let mut level = level_with_knockback("#rE #", 2.0);
+            let player = level.player;
+            level.player = player.with_stats(player.stats().with_collision_radius(radius));
+            hit_enemy_in_front(&mut level);
+            level.enemies[0].position().x()
+        };
+
+        assert_that!(knocked(0.4)).is_equal_to(knocked(0.1));
+    }
+
+    #[test]
+    fn wall_should_block_the_line_of_sight() {
+        let map = build_map("#######\n#r #  #\n#######");
+        let actions = Actions::new(&map);
+
+        assert_that!(has_line_of_sight(Position::new(1.5, 1.5), Position::new(5.5, 1.5), &map, &actions)).is_false();
+    }
+
+    #[test]
+    fn open_room_should_not_block_the_line_of_sight() {
+        let map = build_map("#######\n#r    #\n#     #\n#######");
+        let actions = Actions::new(&map);
+
+        assert_that!(has_line_of_sight(Position::new(1.5, 1.5), Position::new(5.5, 2.5), &map, &actions)).is_true();
+    }
+
+    #[test]
+    fn melee_weapon_should_hit_only_once_per_swing() {
+        let mut level = level_with_knockback("#rE #", 0.0);
+
+        level.handle_shoot();
+        level.notify_elapsed(150000);
+        level.apply_shoots();
+        let health = level.enemies[0].health();
+        level.apply_shoots();
+
+        assert_that!(health).is_less_than(150);
+        assert_that!(level.enemies[0].health()).is_equal_to(health);
+    }
+
+    #[test]
+    fn killed_enemy_should_remain_as_a_corpse() {
+        let mut level = level_with_knockback("#rE #", 0.0);
+        level.enemies[0] = level.enemies[0].with_health(1);
+
+        hit_enemy_in_front(&mut level);
+
+        let textures: Vec<TextureIndex> = level.generate_actions()
+            .into_iter()
+            .filter_map(|action| match action {
+                DrawAction::Sprite(_, _, texture, _) => Some(texture),
+                _ => None,
+            })
+            .collect();
+        assert_that!(level.enemies[0].is_dead()).is_true();
+        assert_that!(textures).contains(TextureIndex::new(7));
+        assert_that!(textures).does_not_contain(TextureIndex::new(5));
+    }
+
+    #[test]
+    fn corpse_should_not_be_hit_again() {
+        let mut level = level_with_knockback("#rE #", 1.0);
+        level.enemies[0] = level.enemies[0].with_health(0);
+
+        hit_enemy_in_front(&mut level);
+
+        assert_that!(level.enemies[0].position().x()).is_close_to(2.5, 0.01);
+    }
+
+    #[test]
+    fn melee_weapon_should_hit_an_enemy_just_inside_its_arc() {
+        let mut level = level_with_melee_enemy_aside(0.4);
+
+        hit_enemy_in_front(&mut level);
+
+        assert_that!(level.enemies[0].health()).is_equal_to(120);
+    }
+
+    #[test]
+    fn melee_weapon_should_miss_an_enemy_just_outside_its_arc() {
+        let mut level = level_with_melee_enemy_aside(0.7);
+
+        hit_enemy_in_front(&mut level);
+
+        assert_that!(level.enemies[0].health()).is_equal_to(150);
+    }
+
+    #[test]
+    fn melee_weapon_should_not_reach_a_distant_enemy() {
+        let mut level = level_with_weapon_kind("#r    E #", WeaponKind::default());
+
+        hit_enemy_in_front(&mut level);
+
+        assert_that!(level.enemies[0].health()).is_equal_to(150);
+    }
+
+    #[test]
+    fn hitscan_weapon_should_hit_a_distant_enemy_instantly() {
+        let mut level = level_with_weapon_kind("#r    E #", WeaponKind::hitscan(10.0));
+
+        level.handle_shoot();
+        level.notify_elapsed(150000);
+        level.apply_shoots();
+
+        assert_that!(level.enemies[0].health()).is_equal_to(120);
+        assert_that!(level.projectiles).is_empty();
+    }
+
+    #[test]
+    fn hitscan_weapon_should_not_hit_through_a_wall() {
+        let mut level = level_with_weapon_kind("#r  # E #", WeaponKind::hitscan(10.0));
+
+        level.handle_shoot();
+        level.notify_elapsed(150000);
+        level.apply_shoots();
+
+        assert_that!(level.enemies[0].health()).is_equal_to(150);
+    }
+
+    #[test]
+    fn hitscan_radius_should_widen_the_enemy_hit_circle() {
+        let paving = "#########\n#       #\n#r   E  #\n#########";
+        let shoot = |radius: f32| {
+            let mut level = level_with_weapon_kind(paving, WeaponKind::Hitscan { range: 10.0, radius });
+            let enemy = level.enemies[0].position();
+            level.enemies[0] = level.enemies[0].with_position(Position::new(enemy.x(), enemy.y() + 0.7));
+            level.handle_shoot();
+            level.notify_elapsed(150000);
+            level.apply_shoots();
+            level.enemies[0].health()
+        };
+
+        assert_that!(shoot(0.5)).is_equal_to(150);
+        assert_that!(shoot(0.8)).is_equal_to(120);
+    }
+
+    #[test]
+    fn aim_assist_should_bring_a_slightly_missed_shot_onto_the_enemy() {
+        let paving = "#######\n#    E#\n#r    #\n#######";
+        let mut assisted = level_with_weapon_kind(paving, WeaponKind::hitscan(10.0))
+            .with_aim_assist(AimAssist::new(Angle::new(PI / 9.0), 1.0));
+        let mut unassisted = level_with_weapon_kind(paving, WeaponKind::hitscan(10.0));
+
+        for level in [&mut assisted, &mut unassisted] {
+            level.handle_shoot();
+            level.notify_elapsed(150000);
+            level.apply_shoots();
+        }
+
+        assert_that!(assisted.enemies[0].health()).is_equal_to(120);
+        assert_that!(unassisted.enemies[0].health()).is_equal_to(150);
+    }
+
+    #[test]
+    fn partial_aim_assist_should_only_turn_the_aim_part_of_the_way() {
+        let paving = "#######\n#    E#\n#r    #\n#######";
+        let level = level_with_weapon_kind(paving, WeaponKind::hitscan(10.0))
+            .with_aim_assist(AimAssist::new(Angle::new(PI / 9.0), 0.5));
+
+        let target = signed_angle(*level.player.position(), level.enemies[0].position()).unwrap();
+
+        assert_that!(level.assisted_aim(f32::MAX).to_radiant()).is_close_to(target.to_radiant() / 2.0, 0.001);
+    }
+
+    #[test]
+    fn weapons_should_not_fire_without_input_on_a_fresh_or_reset_level() {
+        let projectile = WeaponKind::Projectile { speed: 10.0, texture: TextureIndex::new(6) };
+        for kind in [projectile, WeaponKind::hitscan(10.0)] {
+            let mut level = level_with_weapon_kind("#r    E #", kind);
+
+            idle(&mut level);
+            level.reset();
+            idle(&mut level);
+
+            assert_that!(level.projectiles).is_empty();
+            assert_that!(level.enemies[0].health()).is_equal_to(150);
+            assert_that!(level.drain_sounds().iter().any(|sound| sound.kind() == SoundKind::PlayerShot)).is_false();
+        }
+    }
+
+    #[test]
+    fn empty_weapon_should_never_fire_on_spawn_or_reset() {
+        let kind = WeaponKind::Projectile { speed: 10.0, texture: TextureIndex::new(6) };
+        let configuration = configuration_with_weapon(default_weapon().with_kind(kind).with_ammo(0));
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let mut level = Level::new(view, build_map_with_configuration("#r    E #", configuration)).unwrap();
+
+        idle(&mut level);
+        level.reset();
+        level.handle_shoot();
+        idle(&mut level);
+
+        assert_that!(level.projectiles).is_empty();
+        assert_that!(level.enemies[0].health()).is_equal_to(150);
+        assert_that!(level.weapons[0].ammo()).is_equal_to(Some(0));
+    }
+
+    fn idle(level: &mut Level) {
+        for _ in 0..20 {
+            level.tick(Force::new(ANGLE_0, 0.0, ANGLE_0), 16000);
+        }
+    }
+
+    #[test]
+    fn projectile_weapon_should_spawn_a_projectile_hurting_enemies() {
+        let kind = WeaponKind::Projectile { speed: 10.0, texture: TextureIndex::new(6) };
+        let mut level = level_with_weapon_kind("#r    E #", kind);
+
+        level.handle_shoot();
+        level.notify_elapsed(150000);
+        level.apply_shoots();
+        level.apply_shoots();
+
+        assert_that!(level.projectiles).has_length(1);
+        assert_that!(level.player.health()).is_equal_to(100);
+
+        for _ in 0..40 {
+            level.notify_elapsed(16000);
+        }
+
+        assert_that!(level.enemies[0].health()).is_equal_to(120);
+        assert_that!(level.projectiles).is_empty();
+    }
+
+    #[test]
+    fn running_should_deplete_stamina_until_running_is_disabled() {
+        let mut level = level_with_stamina();
+
+        level.run();
+        level.apply_forces(Force::new(ANGLE_RIGHT, 1.0, ANGLE_0), 100000);
+        assert_that!(level.player().inertia().units_per_seconds()).is_close_to(2.0, 0.001);
+
+        for _ in 0..5 {
+            level.run();
+            level.apply_forces(Force::new(ANGLE_RIGHT, 1.0, ANGLE_0), 100000);
+        }
+        assert_that!(level.player().stamina()).is_equal_to(0.0);
+        assert_that!(level.player().can_run()).is_false();
+
+        level.run();
+        level.apply_forces(Force::new(ANGLE_RIGHT, 1.0, ANGLE_0), 100000);
+        assert_that!(level.player().inertia().units_per_seconds()).is_close_to(1.0, 0.001);
+    }
+
+    #[test]
+    fn running_should_move_farther_than_walking_in_the_same_time() {
+        let mut walking = level_with_stamina();
+        let mut running = level_with_stamina();
+
+        walking.apply_forces(Force::new(ANGLE_RIGHT, 1.0, ANGLE_0), 200000);
+        running.run();
+        running.apply_forces(Force::new(ANGLE_RIGHT, 1.0, ANGLE_0), 200000);
+
+        assert_that!(walking.player().position().x()).is_close_to(1.7, 0.001);
+        assert_that!(running.player().position().x()).is_close_to(1.9, 0.001);
+    }
+
+    #[test]
+    fn running_should_still_be_stopped_by_walls() {
+        let mut level = level_with_stamina_in("#r #");
+
+        for _ in 0..4 {
+            level.run();
+            level.apply_forces(Force::new(ANGLE_RIGHT, 1.0, ANGLE_0), 100000);
+        }
+
+        assert_that!(level.player().position().x()).is_less_than_or_equal_to(3.0 - DEFAULT_COLLISION_RADIUS + 0.001);
+    }
+
+    #[test]
+    fn zoom_should_narrow_the_view_only_while_held() {
+        let mut level = level_with_stamina();
+        let wide = level.view_angle().to_radiant();
+
+        level.zoom();
+        level.apply_forces(Force::new(ANGLE_RIGHT, 0.0, ANGLE_0), 16000);
+        let zoomed = level.view_angle().to_radiant();
+        level.apply_forces(Force::new(ANGLE_RIGHT, 0.0, ANGLE_0), 16000);
+
+        assert_that!(zoomed).is_close_to(wide * 0.5, 0.001);
+        assert_that!(level.view_angle().to_radiant()).is_close_to(wide, 0.001);
+    }
+
+    #[test]
+    fn crouching_should_lower_the_horizon_until_released() {
+        let mut level = level_with_stamina();
+        let standing = level.horizon();
+
+        level.crouch();
+        level.apply_forces(Force::new(ANGLE_RIGHT, 0.0, ANGLE_0), 100000);
+        let lowering = level.horizon();
+        level.crouch();
+        level.apply_forces(Force::new(ANGLE_RIGHT, 0.0, ANGLE_0), 1000000);
+        let crouched = level.horizon();
+        level.apply_forces(Force::new(ANGLE_RIGHT, 0.0, ANGLE_0), 1000000);
+
+        assert_that!(lowering).is_greater_than(standing);
+        assert_that!(crouched).is_greater_than(lowering);
+        assert_that!(level.horizon()).is_equal_to(standing);
+    }
+
+    #[test]
+    fn stamina_should_regenerate_after_running_stops() {
+        let mut level = level_with_stamina();
+        for _ in 0..6 {
+            level.run();
+            level.apply_forces(Force::new(ANGLE_RIGHT, 1.0, ANGLE_0), 100000);
+        }
+
+        level.apply_forces(Force::new(ANGLE_RIGHT, 1.0, ANGLE_0), 500000);
+
+        assert_that!(level.player().stamina()).is_close_to(0.5, 0.001);
+        assert_that!(level.player().can_run()).is_true();
+    }
+
+    #[test]
+    fn distant_doors_should_not_be_animated_when_an_animation_distance_is_set() {
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let mut level = Level::new(view, build_map("rD   D")).unwrap().with_animation_distance(2.0);
+        level.actions.activate(1, 0);
+        level.actions.activate(5, 0);
+
+        level.notify_elapsed(100000);
+
+        assert_that!(level.animated_tiles()).is_equal_to(1);
+        assert_that!(level.actions.state_at(1, 0).unwrap().activated_percentage()).is_greater_than(0.0);
+        assert_that!(level.actions.state_at(5, 0).unwrap().activated_percentage()).is_equal_to(0.0);
+    }
+
+    #[test]
+    fn auto_door_should_open_on_approach_and_close_when_leaving() {
+        let door = LinearActionState::new(SpeedStats::new(3.0), Box::new(LateralOpening::default())).with_auto_open(1.5);
+        let mut configuration = default_configuration();
+        configuration.add('A', Tile::DYNAMIC(TextureIndex::new(2), TextureIndex::new(4), ActionStateBuilder::new(Box::new(door))));
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let mut level = Level::new(view, build_map_with_configuration("#r    A#", configuration)).unwrap();
+
+        level.notify_elapsed(100000);
+        let far = level.actions.state_at(6, 0).unwrap().activated_percentage();
+
+        level.teleport(Position::new(5.5, 0.5));
+        level.notify_elapsed(1000000);
+        let near = level.actions.state_at(6, 0).unwrap().activated_percentage();
+
+        level.teleport(Position::new(1.5, 0.5));
+        level.notify_elapsed(1000000);
+        let left = level.actions.state_at(6, 0).unwrap().activated_percentage();
+
+        assert_that!(far).is_equal_to(0.0);
+        assert_that!(near).is_equal_to(1.0);
+        assert_that!(left).is_equal_to(0.0);
+    }
+
+    #[test]
+    fn auto_closed_door_should_not_reopen_until_the_player_comes_back() {
+        let door = LinearActionState::new(SpeedStats::new(3.0), Box::new(LateralOpening::default()))
+            .with_auto_open(1.5)
+            .with_auto_close(1.0);
+        let mut configuration = default_configuration();
+        configuration.add('A', Tile::DYNAMIC(TextureIndex::new(2), TextureIndex::new(4), ActionStateBuilder::new(Box::new(door))));
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let mut level = Level::new(view, build_map_with_configuration("#r    A#", configuration)).unwrap();
+        level.teleport(Position::new(5.5, 0.5));
+
+        level.notify_elapsed(500000);
+        let adjacent = level.actions.state_at(6, 0).unwrap().activated_percentage();
+        for _ in 0..10 {
+            level.notify_elapsed(200000);
+        }
+        let staying = level.actions.state_at(6, 0).unwrap().activated_percentage();
+        level.teleport(Position::new(1.5, 0.5));
+        level.notify_elapsed(100000);
+        level.teleport(Position::new(5.5, 0.5));
+        level.notify_elapsed(500000);
+        let back = level.actions.state_at(6, 0).unwrap().activated_percentage();
+
+        assert_that!(adjacent).is_equal_to(1.0);
+        assert_that!(staying).is_equal_to(0.0);
+        assert_that!(back).is_equal_to(1.0);
+    }
+
+    #[test]
+    fn locked_auto_door_should_not_open_without_its_key() {
+        let door = LinearActionState::new(SpeedStats::new(3.0), Box::new(LateralOpening::default())).with_auto_open(1.5);
+        let mut configuration = default_configuration();
+        configuration.add('A', Tile::DYNAMIC(TextureIndex::new(2), TextureIndex::new(4), ActionStateBuilder::new(Box::new(door))));
+        configuration.add_lock('A', KeyColor::Blue);
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let mut level = Level::new(view, build_map_with_configuration("#r   A#", configuration)).unwrap();
+        level.teleport(Position::new(4.5, 0.5));
+
+        level.notify_elapsed(1000000);
+
+        assert_that!(level.actions.state_at(5, 0).unwrap().activated_percentage()).is_equal_to(0.0);
+    }
+
+    #[test]
+    fn weapon_should_be_drawn_in_its_view_model_rect() {
+        let weapon = default_weapon().with_view_model(ViewModel::new(0.5, 0.5, 0.5, 0.5));
+        let view = ViewScreen::new(100, 200, ANGLE_90);
+        let level = Level::new(view, build_map_with_configuration("#r  #", configuration_with_weapon(weapon))).unwrap();
+
+        let weapon_rect = level.generate_actions()
+            .iter()
+            .find_map(|action| match action {
+                DrawAction::Sprite(start, end, _, _) => Some((start.x(), start.y(), end.x(), end.y())),
+                _ => None,
+            });
+
+        assert_that!(weapon_rect).is_some().is_equal_to((100, 50, 200, 100));
+    }
+
+    #[test]
+    fn muzzle_flash_should_only_be_drawn_during_the_active_state() {
+        let weapon = default_weapon().with_muzzle_flash(TextureIndex::new(9));
+        let view = ViewScreen::new(100, 200, ANGLE_90);
+        let mut level = Level::new(view, build_map_with_configuration("#r  #", configuration_with_weapon(weapon))).unwrap();
+        let has_muzzle_flash = |level: &Level| level.generate_actions().iter()
+            .any(|action| matches!(action, DrawAction::AdditiveSprite(_, _, texture) if *texture == TextureIndex::new(9)));
+
+        let idle = has_muzzle_flash(&level);
+        level.handle_shoot();
+        level.notify_elapsed(150000);
+        let active = has_muzzle_flash(&level);
+        level.notify_elapsed(100000);
+        let recovery = has_muzzle_flash(&level);
+
+        assert_that!(idle).is_false();
+        assert_that!(active).is_true();
+        assert_that!(recovery).is_false();
+    }
+
+    #[test]
+    fn stepping_on_an_exit_should_report_the_next_map() {
+        let mut configuration = default_configuration();
+        configuration.add_exit('X', String::from("2.map"));
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let mut level = Level::new(view, build_map_with_configuration("#r X #", configuration)).unwrap();
+
+        assert_that!(level.poll_transition()).is_none();
+        level.teleport(Position::new(3.5, 0.5));
+        level.apply_forces(Force::new(ANGLE_RIGHT, 0.0, ANGLE_0), 16000);
+
+        assert_that!(level.poll_transition()).is_some().is_equal_to(String::from("2.map"));
+        assert_that!(level.poll_transition()).is_none();
+
+        level.apply_forces(Force::new(ANGLE_RIGHT, 0.0, ANGLE_0), 16000);
+        assert_that!(level.poll_transition()).is_none();
+    }
+
+    #[test]
+    fn next_map_should_keep_the_player_health_and_weapons() {
+        let mut configuration = configuration_with_weapon(default_weapon().with_ammo(5));
+        configuration.add_weapon(default_weapon().with_ammo(8));
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let mut previous = Level::new(view, build_map_with_configuration("#r  #", configuration)).unwrap();
+        previous.notify_elapsed(1000000);
+        previous.damage_player(30);
+        previous.select_weapon(1);
+
+        let next = Level::new(view, build_map("#####\n# u #\n#####")).unwrap().with_player_state_of(&previous);
+
+        assert_that!(next.player().health()).is_equal_to(70);
+        assert_that!(next.weapons().iter().map(|weapon| weapon.ammo()).collect::<Vec<Option<u32>>>()).is_equal_to(vec![Some(5), Some(8)]);
+        assert_that!(next.current_weapon().ammo()).is_equal_to(Some(8));
+    }
+
+    #[test]
+    fn entering_a_teleport_should_move_the_player_to_its_destination() {
+        let mut level = level_with_teleports();
+
+        level.apply_forces(Force::new(ANGLE_RIGHT, 1.0, ANGLE_0), 16000);
+
+        assert_that!(level.player().position().x()).is_close_to(4.5, 0.001);
+        assert_that!(level.player().position().y()).is_close_to(0.5, 0.001);
+    }
+
+    #[test]
+    fn teleport_destination_should_not_bounce_the_player_back() {
+        let mut level = level_with_teleports();
+
+        level.apply_forces(Force::new(ANGLE_RIGHT, 1.0, ANGLE_0), 16000);
+        for _ in 0..10 {
+            level.apply_forces(Force::new(ANGLE_LEFT, 1.0, ANGLE_0), 16000);
         }
+
+        assert_that!(level.player().position().x()).is_greater_than(4.0);
+        assert_that!(level.player().position().x()).is_less_than(5.0);
     }
 
-    pub fn apply_forces(&mut self, force: Force, microseconds_elapsed: u128) {
-        let relative_force = force.for_relative_view(self.player.orientation());
-        let no_limit = self
-            .player
-            .apply_force(relative_force, microseconds_elapsed);
-        let constrained = self.constrains(*self.player.position(), *no_limit.position());
+    #[test]
+    fn respawn_should_bring_player_back_to_the_last_crossed_checkpoint() {
+        let mut level = level_with_checkpoint();
+        level.teleport(Position::new(3.3, 0.5));
+        level.apply_forces(Force::new(ANGLE_RIGHT, 0.0, ANGLE_0), 16000);
+        level.teleport(Position::new(5.5, 0.5));
+        level.player = level.player.damage(100);
+
+        level.respawn_at_last_checkpoint();
 
-        self.player = Player::new(constrained, no_limit.orientation(), self.player.stats())
-            .with_inertia(no_limit.inertia())
+        assert_that!(level.player.position().x()).is_close_to(3.5, 0.001);
+        assert_that!(level.player.health()).is_equal_to(100);
     }
 
-    pub fn apply_shoots(&mut self) {
-        if matches!(self.current_weapon.state(), ShootState::Active) {
-            Level::sword(&mut self.enemies, self.player, &mut self.current_weapon);
-        }
+    #[test]
+    fn respawn_without_checkpoint_should_bring_player_back_to_spawn() {
+        let mut level = level_with_checkpoint();
+        level.teleport(Position::new(5.5, 0.5));
+
+        level.respawn_at_last_checkpoint();
+
+        assert_that!(level.player.position().x()).is_close_to(1.5, 0.001);
     }
 
-    fn sword(enemies: &mut Vec<Enemy>, player: Player, weapon: &mut Weapon) {
-        let range_distance = 0.5;
-        let range_angle = Angle::new(PI / 4.0);
+    #[test]
+    fn teleport_to_checkpoint_should_find_it_by_name() {
+        let mut level = level_with_checkpoint();
 
-        enemies.iter_mut().for_each(|enemy| {
-            let enemy_size = 0.5;
-            let distance = player.position().distance(&enemy.position());
-            let look_at = Vector::from_angle(player.orientation());
-            let enemy_look = Vector::new(*player.position(), enemy.position());
+        assert_that!(level.teleport_to_checkpoint("hall")).is_ok();
+        assert_that!(level.player.position().x()).is_close_to(3.5, 0.001);
+        assert_that!(level.teleport_to_checkpoint("cellar")).is_err();
+    }
 
-            let hit = look_at.angle(enemy_look)
-                .map(|angle| distance < range_distance + enemy_size && angle.to_radiant() < range_angle.to_radiant())
-                .unwrap_or(false);
+    #[test]
+    fn parallel_updates_should_match_serial_updates() {
+        let parallel = simulate_fight(true);
+        let serial = simulate_fight(false);
 
-            if hit {
-                *enemy = enemy.damage(weapon.configuration().damage());
-                weapon.notify_hit();
-                println!("Enemy hit! Now at {} hp", enemy.health());
-            }
-        });
+        assert_that!(parallel.len()).is_greater_than(4);
+        assert_that!(parallel).is_equal_to(serial);
     }
 
-    pub fn handle_action(&mut self) {
-        let action_projected = project(*self.player.position(), self.player.orientation(), &self.map, &self.actions);
-        if !action_projected.is_empty() {
-            let closest = action_projected[0];
-            if closest.distance() < 1.0 {
-                let map_point = closest.map_point();
-                self.actions.activate(map_point.x(), map_point.y());
-            }
+    fn simulate_fight(parallel: bool) -> Vec<(u32, u32, u32)> {
+        let ranged_attack = RangedAttack::new(TextureIndex::new(6), SpeedStats::new(5.0), 10, 0.2, 5.0);
+        let perception = Perception::new(6.0, ANGLE_90, 4.0);
+        let mut configuration = configuration_with_weapon(default_weapon().with_knockback(1.0));
+        configuration.add_enemy('R', EnemyType::new(TextureIndex::new(5), TextureIndex::new(5), 100).with_ranged_attack(ranged_attack));
+        configuration.add_enemy('Q', EnemyType::new(TextureIndex::new(5), TextureIndex::new(5), 100).with_perception(perception));
+        configuration.add_spawner('S', SpawnerType::new(EnemyType::new(TextureIndex::new(5), TextureIndex::new(5), 100), 0.2, 3));
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let paving = "##########\n#rE R  Q #\n#  R  D  #\n#Q  E R S#\n##########";
+        let mut level = Level::new(view, build_map_with_configuration(paving, configuration)).unwrap();
+        level.parallel = parallel;
+        level.teleport(Position::new(1.8, 1.5));
+
+        for tick in 0..60 {
+            level.handle_shoot();
+            level.notify_elapsed(16000);
+            let turn = if tick % 20 < 10 { Angle::new(0.05) } else { Angle::new(-0.05) };
+            level.apply_forces(Force::new(ANGLE_RIGHT, 0.2, turn), 16000);
+            level.apply_shoots();
         }
-    }
 
-    pub fn handle_shoot(&mut self) {
-        self.current_weapon.action();
+        let mut state: Vec<(u32, u32, u32)> = level.enemies.iter()
+            .map(|enemy| (enemy.position().x().to_bits(), enemy.position().y().to_bits(), enemy.health()))
+            .collect();
+        state.push((level.player.position().x().to_bits(), level.player.position().y().to_bits(), level.player.health()));
+        state
     }
 
-    pub fn notify_elapsed(&mut self, microseconds: u128) {
-        self.actions.notify_elapsed(microseconds);
-        self.current_weapon.notify_elapsed(microseconds);
-    }
+    #[test]
+    fn reset_should_bring_player_back_to_spawn_and_close_doors() {
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let mut level = Level::new(view, build_map("#rD  #")).unwrap();
+        level.handle_action();
+        level.notify_elapsed(1000000);
+        level.teleport(Position::new(4.5, 0.5));
+        assert_that!(level.actions.state_at(2, 0).unwrap().activated_percentage()).is_equal_to(1.0);
 
-    pub fn generate_actions(&self) -> Vec<DrawAction> {
-        let mut actions: Vec<DrawAction> = vec![];
-        let mut actions_ordered: Vec<DrawActionZIndex> = vec![];
+        level.reset();
 
-        actions.extend(build_clear_actions());
+        assert_that!(level.player.position().x()).is_equal_to(1.5);
+        assert_that!(level.player.position().y()).is_equal_to(0.5);
+        assert_that!(level.actions.state_at(2, 0).unwrap().activated_percentage()).is_equal_to(0.0);
+    }
 
-        actions.extend(build_background_actions(self.view));
+    #[test]
+    fn reset_should_restore_enemies() {
+        let mut level = level_with_knockback("#rE      #", 1.0);
+        hit_enemy_in_front(&mut level);
 
-        actions_ordered.extend(build_walls(
-            self.view,
-            self.player.position(),
-            self.player.orientation(),
-            &self.map,
-            &self.actions,
-        ));
+        level.reset();
 
-        actions_ordered.extend(build_enemies(
-            self.view,
-            *self.player.position(),
-            &self.player.orientation(),
-            &self.enemies,
-        ));
+        assert_that!(level.enemies[0].health()).is_equal_to(150);
+        assert_that!(level.enemies[0].position().x()).is_equal_to(2.5);
+    }
 
-        actions_ordered.sort_by(|a, b| a.z_index().total_cmp(&b.z_index()).reverse());
-        actions.extend(actions_ordered.iter().map(|ordered| ordered.action().clone()));
+    #[test]
+    fn enemy_shots_should_be_spatialized_around_the_player() {
+        let ranged_attack = RangedAttack::new(TextureIndex::new(6), SpeedStats::new(5.0), 10, 1.0, 5.0);
+        let mut configuration = default_configuration();
+        configuration.add_enemy('R', EnemyType::new(TextureIndex::new(5), TextureIndex::new(5), 100).with_ranged_attack(ranged_attack));
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let mut level = Level::new(view, build_map_with_configuration("######\n#R   #\n#r  R#\n######", configuration)).unwrap();
 
-        actions.push(build_weapons(self.view, self.current_weapon));
+        level.notify_elapsed(16000);
 
-        actions
-    }
+        let shots: Vec<SoundEvent> = level.sound_events().iter()
+            .filter(|event| event.kind() == SoundKind::EnemyShot)
+            .copied()
+            .collect();
+        let near = shots.iter().find(|event| event.position().x() < 2.0).unwrap();
+        let far = shots.iter().find(|event| event.position().x() > 2.0).unwrap();
 
-    pub fn teleport(&mut self, to: Position) {
-        self.player = self.player.with_position(to);
+        assert_that!(near.pan()).is_less_than(-0.9);
+        assert_that!(far.volume()).is_less_than(near.volume());
     }
 
-    fn constrains(&self, start: Position, end: Position) -> Position {
-        let angle_opt = signed_angle(start, end);
-        if angle_opt.is_none() {
-            return end;
-        }
+    #[test]
+    fn enemy_behind_the_player_should_be_alerted_by_a_shot_it_hears() {
+        let mut level = level_with_perceptive_enemy("#Q r     #");
+        level.enemies[0] = level.enemies[0].with_orientation(ANGLE_LEFT);
 
-        let angle = angle_opt.unwrap();
+        level.notify_elapsed(16000);
+        assert_that!(level.enemies[0].is_aware()).is_false();
 
-        let angle_x = angle.align_to_x();
-        let angle_y = angle.align_to_y();
+        fire_once(&mut level);
 
-        let distance_x = self.distance(start, angle_x) - WALL_MINIMUM_DISTANCE;
-        let distance_y = self.distance(start, angle_y) - WALL_MINIMUM_DISTANCE;
+        assert_that!(level.enemies[0].is_aware()).is_true();
+    }
 
-        let mov_x = (end.x() - start.x()).abs();
-        let mov_y = (end.y() - start.y()).abs();
+    #[test]
+    fn enemy_out_of_sight_and_hearing_ranges_should_stay_idle() {
+        let mut level = level_with_perceptive_enemy("#Q      r#");
 
-        let should_go_x = self.min(mov_x, distance_x);
-        let should_go_y = self.min(mov_y, distance_y);
+        level.notify_elapsed(16000);
+        fire_once(&mut level);
 
-        start
-            .with_x(start.x() + (should_go_x * angle.cos().signum()))
-            .with_y(start.y() + (should_go_y * angle.sin().signum()))
+        assert_that!(level.enemies[0].is_aware()).is_false();
+        assert_that!(level.enemies[0].state()).is_equal_to(EnemyState::Idle);
     }
 
-    fn min(&self, a: f32, b: f32) -> f32 {
-        if a < b {
-            a
-        } else {
-            b
-        }
+    #[test]
+    fn ranged_enemy_should_fire_toward_the_player() {
+        let mut level = level_with_ranged_enemy();
+
+        level.notify_elapsed(16000);
+
+        assert_that!(level.projectiles).has_length(1);
+        assert_that!(level.projectiles[0].position().x()).is_less_than(5.5);
+        assert_that!(level.projectiles[0].position().y()).is_close_to(0.5, 0.001);
     }
 
-    fn distance(&self, start: Position, angle: Angle) -> f32 {
-        let projected: Vec<ProjectedPoint> = project(start, angle, &self.map, &self.actions)
-            .iter()
-            .filter(|projection| projection.blocking())
-            .cloned()
-            .collect();
+    #[test]
+    fn projectile_hitting_the_player_should_reduce_health() {
+        let mut level = level_with_ranged_enemy();
 
-        if !projected.is_empty() {
-            projected[0].distance()
-        } else {
-            0.0
+        for _ in 0..50 {
+            level.notify_elapsed(16000);
         }
+
+        assert_that!(level.player().health()).is_equal_to(90);
+        assert_that!(level.projectiles).is_empty();
     }
-}
 
-#[cfg(test)]
-mod level_test {
-    use std::f32::consts::PI;
+    #[test]
+    fn damage_should_reduce_player_health() {
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let mut level = Level::new(view, build_map("#r #")).unwrap();
 
-    use spectral::prelude::*;
+        level.damage_player(30);
 
-    use crate::domain::control::force::Force;
-    use crate::domain::level::WALL_MINIMUM_DISTANCE;
-    use crate::domain::maths::{Angle, ANGLE_0, ANGLE_90, ANGLE_RIGHT};
-    use crate::domain::topology::coord::Position;
-    use crate::domain::topology::map::map_test::build_map;
-    use crate::domain::ui::draw_action::DrawAction;
-    use crate::domain::ui::view::ViewScreen;
+        assert_that!(level.player().health()).is_equal_to(70);
+        assert_that!(level.is_player_dead()).is_false();
+    }
 
-    use super::Level;
+    #[test]
+    fn player_health_should_not_go_below_zero() {
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let mut level = Level::new(view, build_map("#r #")).unwrap();
 
-    const TOLERANCE: f32 = WALL_MINIMUM_DISTANCE + 0.01;
+        level.damage_player(30000);
 
-    #[test]
-    fn actions_should_start_with_a_clear() {
-        let view = ViewScreen::new(0, 0, ANGLE_90);
-        let level = Level::new(view, build_map("r#"));
+        assert_that!(level.player().health()).is_equal_to(0);
+        assert_that!(level.is_player_dead()).is_true();
+    }
 
-        let actions = level.generate_actions();
+    #[test]
+    fn enemy_in_melee_range_should_hurt_the_player_on_a_cooldown() {
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let mut level = Level::new(view, build_map("#rE #")).unwrap();
+        level.teleport(Position::new(1.9, 0.5));
 
-        assert_that!(actions.len()).is_greater_than(0);
+        level.notify_elapsed(16000);
+        level.notify_elapsed(16000);
+        let after_first_strike = level.player().health();
+        level.notify_elapsed(1000000);
 
-        assert!(matches!(actions[0], DrawAction::Clear { .. }));
+        assert_that!(after_first_strike).is_equal_to(90);
+        assert_that!(level.player().health()).is_equal_to(80);
     }
 
     #[test]
-    fn actions_should_draw_ceiling() {
-        let view = ViewScreen::new(200, 100, ANGLE_90);
-        let level = Level::new(view, build_map("r#"));
-        let mut found = false;
+    fn each_enemy_in_melee_range_should_strike_on_its_own_cooldown() {
+        let mut configuration = default_configuration();
+        configuration.add_enemy('M', EnemyType::new(TextureIndex::new(5), TextureIndex::new(5), 100).with_melee_attack(MeleeAttack::new(25, 0.5, 1.2)));
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let mut level = Level::new(view, build_map_with_configuration("#rEM #", configuration)).unwrap();
+        level.teleport(Position::new(2.9, 0.5));
 
-        let actions = level.generate_actions();
+        level.notify_elapsed(16000);
+        let after_both_strikes = level.player().health();
+        level.notify_elapsed(600000);
 
-        for action in actions {
-            if let DrawAction::Rectangle(start, end, _) = action {
-                if start.x() == 0 && start.y() == 0 && end.x() == 100 && end.y() == 100 {
-                    found = true
-                }
-            }
+        assert_that!(after_both_strikes).is_equal_to(100 - 10 - 25);
+        assert_that!(level.player().health()).is_equal_to(100 - 10 - 25 - 25);
+    }
+
+    #[test]
+    fn damage_from_the_right_should_show_an_indicator_on_the_right() {
+        let mut level = level_with_ranged_enemy();
+        level.player = Player::new(*level.player.position(), ANGLE_UP, level.player.stats());
+
+        for _ in 0..50 {
+            level.notify_elapsed(16000);
         }
 
-        assert_that!(found).is_true();
+        let indicators: Vec<i32> = level.generate_actions().iter()
+            .filter_map(|action| match action {
+                DrawAction::Rectangle(start, end, color) if *color == Color::new(200, 0, 0) => Some((start.x() + end.x()) / 2),
+                _ => None,
+            })
+            .collect();
+        assert_that!(indicators).has_length(1);
+        assert_that!(indicators[0]).is_greater_than(50);
     }
 
     #[test]
-    fn actions_should_draw_floor() {
-        let view = ViewScreen::new(200, 100, ANGLE_90);
+    fn projectile_should_travel_toward_its_orientation() {
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let mut level = Level::new(view, build_map("#    \n#    \n#r   ")).unwrap();
+        level.projectiles.push(Projectile::new(Position::new(1.5, 0.5), ANGLE_90, SpeedStats::new(1.0), 10, TextureIndex::new(0)).with_friendly(true));
 
-        let level = Level::new(view, build_map("r#"));
-        let mut found = false;
+        level.update_projectiles(1000000);
 
-        let actions = level.generate_actions();
+        assert_that!(level.projectiles).has_length(1);
+        assert_that!(level.projectiles[0].position().x()).is_close_to(1.5, 0.001);
+        assert_that!(level.projectiles[0].position().y()).is_close_to(1.5, 0.001);
+    }
 
-        for action in actions {
-            if let DrawAction::Rectangle(start, end, _) = action {
-                if start.x() == 0 && start.y() == 100 && end.x() == 100 && end.y() == 200 {
-                    found = true
-                }
-            }
+    #[test]
+    fn projectile_should_be_removed_when_reaching_a_wall() {
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let mut level = Level::new(view, build_map("#r   #")).unwrap();
+        level.projectiles.push(Projectile::new(Position::new(3.5, 0.5), ANGLE_0, SpeedStats::new(5.0), 10, TextureIndex::new(0)));
+
+        for _ in 0..50 {
+            level.notify_elapsed(16000);
         }
 
-        assert_that!(found).is_true();
+        assert_that!(level.projectiles).is_empty();
+        assert_that!(level.player().health()).is_equal_to(100);
     }
 
     #[test]
-    fn apply_force_should_constraint_moves() {
-        let map = build_map("#r#");
+    fn spawner_should_produce_an_enemy_each_interval_up_to_its_cap() {
+        let mut configuration = default_configuration();
+        configuration.add_spawner('S', SpawnerType::new(EnemyType::new(TextureIndex::new(5), TextureIndex::new(5), 100), 1.0, 2));
         let view = ViewScreen::new(100, 100, ANGLE_90);
+        let mut level = Level::new(view, build_map_with_configuration("#r  S#", configuration)).unwrap();
 
-        let mut level = Level::new(view, map);
+        let mut counts = vec![];
+        for _ in 0..4 {
+            level.notify_elapsed(1000000);
+            counts.push(level.enemies.len());
+        }
 
-        level.apply_forces(Force::new(ANGLE_RIGHT, 10.0, ANGLE_0), 1000000);
-        assert_that!(level.player.position().x()).is_less_than_or_equal_to(2.0);
+        assert_that!(counts).is_equal_to(vec![1, 2, 2, 2]);
+        assert_that!(level.enemies[0].position().x()).is_equal_to(4.5);
     }
 
     #[test]
-    fn apply_force_should_apply_not_constrained_moves() {
-        let map = build_map("#r#");
-        let view = ViewScreen::new(100, 100, ANGLE_90);
+    fn facing_an_unlocked_door_should_color_crosshair_as_openable() {
+        let level = level_with_crosshair(default_configuration());
 
-        let mut level = Level::new(view, map);
+        assert_that!(level.interaction_hint()).is_equal_to(Some(InteractionKind::Door));
+        assert_that!(crosshair_color(&level)).is_some().is_equal_to(Color::new(0, 255, 0));
+    }
 
-        level.apply_forces(Force::new(ANGLE_RIGHT, 0.2, ANGLE_0), 1000000);
+    #[test]
+    fn facing_a_locked_door_should_color_crosshair_as_locked() {
+        let mut configuration = default_configuration();
+        configuration.add_lock('D', KeyColor::Red);
+        let level = level_with_crosshair(configuration);
 
-        assert_that!(level.player.position().x()).is_greater_than(1.5);
+        assert_that!(level.interaction_hint()).is_equal_to(Some(InteractionKind::LockedDoor));
+        assert_that!(crosshair_color(&level)).is_some().is_equal_to(Color::new(255, 0, 0));
     }
 
     #[test]
-    fn enemy_should_be_in_the_list_after_the_wall_behind_him() {
-        let map = build_map("#rE #");
+    fn locked_door_should_stay_closed_without_its_key() {
+        let mut configuration = default_configuration();
+        configuration.add_lock('D', KeyColor::Red);
         let view = ViewScreen::new(100, 100, ANGLE_90);
+        let mut level = Level::new(view, build_map_with_configuration("#rD #", configuration)).unwrap();
 
-        let mut level = Level::new(view, map);
-        level.teleport(Position::new(2.3, 0.5));
+        level.handle_action();
+        level.notify_elapsed(1000000);
 
-        let actions = level.generate_actions();
+        assert_that!(level.actions.state_at(2, 0).unwrap().activated_percentage()).is_equal_to(0.0);
+    }
 
-        let position_sprite = actions
-            .iter()
-            .position(|action| matches!(action, DrawAction::Sprite(_, _, _)));
-        let position_wall = actions
-            .iter()
-            .enumerate()
-            .filter(|(_, action)| matches!(action, DrawAction::TexturedLine(_, _, _, _)))
-            .map(|(index, _)| index)
-            .max();
+    #[test]
+    fn locked_door_should_open_with_its_key() {
+        let mut configuration = default_configuration();
+        configuration.add_lock('D', KeyColor::Red);
+        configuration.add_pickup('k', PickupType::new(TextureIndex::new(7), PickupKind::Key(KeyColor::Red)));
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let mut level = Level::new(view, build_map_with_configuration("#rkD #", configuration)).unwrap();
 
-        assert_that!(position_sprite).is_greater_than(position_wall);
+        level.apply_forces(Force::new(ANGLE_RIGHT, 1.0, ANGLE_0), 50000);
+        level.handle_action();
+        level.notify_elapsed(1000000);
+
+        assert_that!(level.has_key(KeyColor::Red)).is_true();
+        assert_that!(level.interaction_hint()).is_equal_to(Some(InteractionKind::Door));
+        assert_that!(level.actions.state_at(3, 0).unwrap().activated_percentage()).is_equal_to(1.0);
     }
 
     #[test]
-    fn enemy_should_be_in_the_list_before_the_wall_before_him() {
-        let map = build_map("#r# E ");
+    fn pick_facing_should_return_the_nearby_door() {
         let view = ViewScreen::new(100, 100, ANGLE_90);
-        let level = Level::new(view, map);
+        let level = Level::new(view, build_map("#rD #")).unwrap();
 
-        let actions = level.generate_actions();
+        let picked = level.pick_facing(1.0).map(|map_point| (map_point.x(), map_point.y()));
 
-        let position_sprite = actions
-            .iter()
-            .position(|action| matches!(action, DrawAction::Sprite(_, _, _)));
-        let position_wall = actions
-            .iter()
-            .enumerate()
-            .filter(|(_, action)| matches!(action, DrawAction::TexturedLine(_, _, _, _)))
-            .map(|(index, _)| index)
-            .min();
+        assert_that!(picked).is_some().is_equal_to((2, 0));
+    }
 
-        assert_that!(position_sprite).is_less_than(position_wall);
+    #[test]
+    fn pick_facing_should_ignore_tiles_out_of_range() {
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let level = Level::new(view, build_map("#r   D#")).unwrap();
+
+        assert_that!(level.pick_facing(1.0).is_none()).is_true();
+        assert_that!(level.pick_facing(5.0).map(|map_point| map_point.x())).is_some().is_equal_to(5);
     }
 
     #[test]
-    fn apply_force_should_constrains_move_by_sliding_through_the_wall_by_top() {
-        let map = build_map("#r#");
+    fn facing_a_nearby_pickup_should_hint_to_interact_with_it() {
+        let mut configuration = default_configuration();
+        configuration.add_pickup('k', PickupType::new(TextureIndex::new(7), PickupKind::Key(KeyColor::Red)));
         let view = ViewScreen::new(100, 100, ANGLE_90);
+        let level = Level::new(view, build_map_with_configuration("#rk  #", configuration)).unwrap();
 
-        let mut level = Level::new(view, map);
+        assert_that!(level.interaction_hint()).is_equal_to(Some(InteractionKind::Pickup));
+    }
 
-        level.apply_forces(
-            Force::new(Angle::new(PI / 16.0), 1.0, ANGLE_0),
-            10000000,
-        );
+    #[test]
+    fn pickup_behind_a_wall_should_not_hint() {
+        let mut configuration = default_configuration();
+        configuration.add_pickup('k', PickupType::new(TextureIndex::new(7), PickupKind::Key(KeyColor::Red)));
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        let level = Level::new(view, build_map_with_configuration("#r#k #", configuration)).unwrap();
 
-        assert_that!(level.player.position().x()).is_close_to(2.0, TOLERANCE);
-        assert_that!(level.player.position().y()).is_greater_than(0.5);
+        assert_that!(level.interaction_hint()).is_none();
     }
 
     #[test]
-    fn apply_force_should_constrains_move_by_sliding_through_the_wall_by_bottom() {
-        let map = build_map("#l#");
+    fn facing_a_wall_should_keep_default_crosshair_color() {
         let view = ViewScreen::new(100, 100, ANGLE_90);
+        let level = Level::new(view, build_map("#r#")).unwrap().with_crosshair(Crosshair::default());
 
-        let mut level = Level::new(view, map);
+        assert_that!(level.interaction_hint()).is_none();
+        assert_that!(crosshair_color(&level)).is_some().is_equal_to(WHITE);
+    }
 
-        level.apply_forces(Force::new(Angle::new(PI / 16.0), 1.0, ANGLE_0), 1000000);
+    fn level_with_crosshair(configuration: MapConfiguration) -> Level {
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+        Level::new(view, build_map_with_configuration("#rD #", configuration)).unwrap().with_crosshair(Crosshair::default())
+    }
 
-        assert_that!(level.player.position().x()).is_close_to(1.0, TOLERANCE);
-        assert_that!(level.player.position().y()).is_less_than(0.5);
+    fn crosshair_color(level: &Level) -> Option<Color> {
+        level.generate_actions()
+            .last()
+            .and_then(|action| match action {
+                DrawAction::Rectangle(_, _, color) => Some(*color),
+                _ => None,
+            })
     }
 
-    #[test]
-    fn apply_force_should_constrains_move_by_sliding_through_the_wall_by_right() {
-        let map = build_map("#\nu\n#");
+    fn level_with_ranged_enemy() -> Level {
+        let ranged_attack = RangedAttack::new(TextureIndex::new(6), SpeedStats::new(5.0), 10, 1.0, 5.0);
+        let mut configuration = default_configuration();
+        configuration.add_enemy('R', EnemyType::new(TextureIndex::new(5), TextureIndex::new(5), 100).with_ranged_attack(ranged_attack));
         let view = ViewScreen::new(100, 100, ANGLE_90);
 
-        let mut level = Level::new(view, map);
+        Level::new(view, build_map_with_configuration("#r   R#", configuration)).unwrap()
+    }
+
+    fn level_with_perceptive_enemy(paving: &str) -> Level {
+        let perception = Perception::new(4.0, ANGLE_90, 3.0);
+        let mut configuration = default_configuration();
+        configuration.add_enemy('Q', EnemyType::new(TextureIndex::new(5), TextureIndex::new(5), 100).with_perception(perception));
+        let view = ViewScreen::new(100, 100, ANGLE_90);
 
-        level.apply_forces(
-            Force::new(Angle::new(-PI / 16.0), 1.0, ANGLE_0),
-            1000000,
-        );
+        Level::new(view, build_map_with_configuration(paving, configuration)).unwrap()
+    }
 
-        assert_that!(level.player.position().x()).is_greater_than(0.5);
-        assert_that!(level.player.position().y()).is_close_to(2.0, TOLERANCE);
+    fn fire_once(level: &mut Level) {
+        level.handle_shoot();
+        level.notify_elapsed(150000);
+        level.apply_shoots();
+        level.notify_elapsed(16000);
     }
 
-    #[test]
-    fn apply_force_should_constrains_move_by_sliding_through_the_wall_by_left() {
-        let map = build_map("#\nd\n#");
+    fn level_with_wall_bump(max_speed: f32) -> Level {
+        let stats = PlayerStats::new(AccelerationStats::new(1000000000.0), AccelerationStats::new(1.0), SpeedStats::new(max_speed));
         let view = ViewScreen::new(100, 100, ANGLE_90);
 
-        let mut level = Level::new(view, map);
+        let mut level = Level::new(view, build_map("#r#")).unwrap().with_wall_bump(WallBump::new(SpeedStats::new(2.0), 5));
+        level.player = Player::new(*level.player.position(), level.player.orientation(), stats);
+        level
+    }
 
-        level.apply_forces(
-            Force::new(Angle::new(-PI / 16.0), 1.0, ANGLE_0),
-            1000000,
-        );
+    fn level_with_teleports() -> Level {
+        let mut configuration = default_configuration();
+        configuration.add_teleport('A', 'B');
+        configuration.add_teleport('B', 'A');
+        let view = ViewScreen::new(100, 100, ANGLE_90);
 
-        assert_that!(level.player.position().x()).is_less_than(0.5);
-        assert_that!(level.player.position().y()).is_close_to(1.0, TOLERANCE);
+        Level::new(view, build_map_with_configuration("#rA#B #", configuration)).unwrap()
+    }
+
+    fn level_with_checkpoint() -> Level {
+        let mut configuration = default_configuration();
+        configuration.add_checkpoint('C', String::from("hall"));
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+
+        Level::new(view, build_map_with_configuration("#r C  #", configuration)).unwrap()
+    }
+
+    fn level_with_stamina() -> Level {
+        level_with_stamina_in("#r                              #")
+    }
+
+    fn level_with_stamina_in(paving: &str) -> Level {
+        let stamina = StaminaStats::new(1.0, SpeedStats::new(2.0), SpeedStats::new(1.0));
+        let stats = PlayerStats::new(AccelerationStats::new(1000000000.0), AccelerationStats::new(1.0), SpeedStats::new(1.0))
+            .with_running(2.0, stamina);
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+
+        let mut level = Level::new(view, build_map(paving)).unwrap();
+        level.player = Player::new(*level.player.position(), level.player.orientation(), stats);
+        level
+    }
+
+    fn level_with_knockback(paving: &str, knockback: f32) -> Level {
+        let configuration = configuration_with_weapon(default_weapon().with_knockback(knockback));
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+
+        let mut level = Level::new(view, build_map_with_configuration(paving, configuration)).unwrap();
+        level.teleport(Position::new(1.8, 0.5));
+        level
+    }
+
+    fn level_with_two_weapons() -> Level {
+        let mut configuration = default_configuration();
+        configuration.add_weapon(default_weapon().with_kind(WeaponKind::hitscan(10.0)));
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+
+        let mut level = Level::new(view, build_map_with_configuration("#r #", configuration)).unwrap();
+        level.notify_elapsed(1000000);
+        level
+    }
+
+    fn level_with_weapon_kind(paving: &str, kind: WeaponKind) -> Level {
+        let configuration = configuration_with_weapon(default_weapon().with_kind(kind));
+        let view = ViewScreen::new(100, 100, ANGLE_90);
+
+        Level::new(view, build_map_with_configuration(paving, configuration)).unwrap()
+    }
+
+    fn level_with_melee_enemy_aside(aside: f32) -> Level {
+        let kind = WeaponKind::Melee { range: 1.0, angle: Angle::from_degree(30.0) };
+        let mut level = level_with_weapon_kind("#####\n#rE #\n#   #\n#####", kind);
+        let player = *level.player.position();
+        level.enemies[0] = level.enemies[0].with_position(Position::new(player.x() + 1.0, player.y() + aside));
+        level
+    }
+
+    fn hit_enemy_in_front(level: &mut Level) {
+        level.handle_shoot();
+        level.notify_elapsed(150000);
+        level.apply_shoots();
+
+        for _ in 0..20 {
+            level.notify_elapsed(16000);
+        }
     }
 }
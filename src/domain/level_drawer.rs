@@ -1,16 +1,37 @@
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::f32::consts::PI;
+
 use rayon::prelude::*;
 
-use crate::domain::actors::actor::Enemy;
+use crate::domain::actors::actor::{Enemy, Player};
+use crate::domain::actors::pickup::Pickup;
+use crate::domain::actors::projectile::Projectile;
 use crate::domain::actors::shoot::{ShootState, Weapon};
 use crate::domain::control::actions::Actions;
-use crate::domain::maths::{Angle, Vector};
-use crate::domain::topology::coord::{Position, ScreenPoint};
-use crate::domain::topology::map::Map;
-use crate::domain::topology::projection::{project, ProjectedPoint};
-use crate::domain::ui::color::Color;
-use crate::domain::ui::draw_action::DrawAction;
+use crate::domain::maths::{Angle, Move, Vector};
+use crate::domain::topology::coord::{MapPoint, Position, ScreenPoint};
+use crate::domain::topology::index::{FontIndex, TextureIndex};
+use crate::domain::topology::map::{Backdrop, Map, Tile};
+use crate::domain::topology::projection::{project, ProjectedPoint, WallFace};
+use crate::domain::ui::color::{Color, WHITE};
+use crate::domain::ui::draw_action::{DrawAction, Shade};
 use crate::domain::ui::view::ViewScreen;
 
+const PROJECTILE_SCALE: f32 = 0.3;
+const PICKUP_SCALE: f32 = 0.5;
+const VERTICAL_FACE_BRIGHTNESS: f32 = 0.75;
+const SPRITE_MAX_DISTANCE: f32 = 64.0;
+const MINIMAP_MARGIN: i32 = 10;
+const MINIMAP_PLAYER_COLOR: Color = Color::new(255, 0, 0);
+const HUD_MARGIN: i32 = 10;
+const HUD_BAR_WIDTH: i32 = 100;
+const HUD_BAR_HEIGHT: i32 = 10;
+const HUD_LABEL_WIDTH: i32 = 60;
+const HUD_LABEL_HEIGHT: i32 = 20;
+const HUD_BAR_BACKGROUND: Color = Color::new(60, 0, 0);
+const HUD_HEALTH_COLOR: Color = Color::new(200, 0, 0);
+
 pub struct DrawActionZIndex {
     action: DrawAction,
     z_index: f32,
@@ -20,25 +41,65 @@ pub fn build_clear_actions() -> Vec<DrawAction> {
     vec![DrawAction::Clear(Color::new(0, 0, 0))]
 }
 
-pub fn build_background_actions(view: ViewScreen) -> Vec<DrawAction> {
+pub fn build_background_actions(view: ViewScreen, ceiling: Color, floor: Color, ambient: f32) -> Vec<DrawAction> {
     let height: i32 = view.height();
     let width: i32 = view.width();
-    let mid_screen = height / 2;
+    let mid_screen = view.horizon();
 
     vec![
         DrawAction::Rectangle(
             ScreenPoint::new(0, 0),
             ScreenPoint::new(width, mid_screen),
-            Color::new(50, 50, 50),
+            ceiling.scale(ambient),
         ),
         DrawAction::Rectangle(
             ScreenPoint::new(0, mid_screen),
             ScreenPoint::new(width, height),
-            Color::new(100, 100, 100),
+            floor.scale(ambient),
         ),
     ]
 }
 
+pub fn upscale(action: &DrawAction, scene: ViewScreen, view: ViewScreen, top: i32) -> DrawAction {
+    let ratio_x = view.width() as f32 / scene.width() as f32;
+    let ratio_y = view.height() as f32 / scene.height() as f32;
+    let scale = |point: &ScreenPoint| ScreenPoint::new(
+        (point.x() as f32 * ratio_x) as i32,
+        (point.y() as f32 * ratio_y) as i32 + top,
+    );
+
+    match action {
+        DrawAction::Rectangle(start, end, color) => DrawAction::Rectangle(scale(start), scale(end), *color),
+        DrawAction::Line(start, end, color) => DrawAction::Line(scale(start), scale(end), *color),
+        DrawAction::TexturedLine(start, end, texture, offset, tint, shade) => {
+            let end = ScreenPoint::new(end.x() + 1, end.y());
+            DrawAction::TexturedLine(scale(start), scale(&end), *texture, *offset, *tint, *shade)
+        }
+        DrawAction::Text(text, start, end, font, color) => DrawAction::Text(text.clone(), scale(start), scale(end), *font, *color),
+        DrawAction::Clear(color) => DrawAction::Clear(*color),
+        DrawAction::Sprite(start, end, texture, tint) => DrawAction::Sprite(scale(start), scale(end), *texture, *tint),
+        DrawAction::AdditiveSprite(start, end, texture) => DrawAction::AdditiveSprite(scale(start), scale(end), *texture),
+    }
+}
+
+pub fn build_backdrop(view: ViewScreen, view_angle: Angle, backdrop: Backdrop, ambient: f32) -> Vec<DrawAction> {
+    let top = view.horizon() - (view.height() as f32 * backdrop.height()) as i32;
+    let tint = WHITE.scale(ambient);
+
+    view_angle.discreet_cone_straight_space(view.angle(), view.width())
+        .iter()
+        .enumerate()
+        .map(|(i, angle)| DrawAction::TexturedLine(
+            ScreenPoint::new(i as i32, top),
+            ScreenPoint::new(i as i32, view.horizon()),
+            backdrop.texture(),
+            angle.to_radiant().rem_euclid(2.0 * PI) / (2.0 * PI),
+            tint,
+            Shade::default(),
+        ))
+        .collect()
+}
+
 pub fn build_walls(
     view: ViewScreen,
     position: &Position,
@@ -52,32 +113,41 @@ pub fn build_walls(
         .map(|(i, angle)| (i, project(*position, *angle, map, actions)))
         .flat_map(|(i, projected)| projected.into_iter().map(|p| (i, p)).collect::<Vec<(usize, ProjectedPoint)>>())
         .map(|(i, projected_point)| {
-            let screen_length: i32 = view.height();
+            let horizon = view.horizon() as f32;
 
             let cartesian_distance = projected_point.distance();
             let distance_for_height = projected_point.distance_no_fish_eye(view_angle);
             let wall_height = object_height(view, distance_for_height);
             let start = ScreenPoint::new(
                 i as i32,
-                (screen_length as f32 / 2.0 - wall_height / 2.0) as i32,
+                (horizon - wall_height / 2.0) as i32,
             );
             let end = ScreenPoint::new(
                 i as i32,
-                (screen_length as f32 / 2.0 + wall_height / 2.0) as i32,
+                (horizon + wall_height / 2.0 - wall_height * projected_point.clip()) as i32,
             );
 
+            let shade = map.fog().map_or_else(Shade::default, |fog| fog.shade(cartesian_distance));
+            let tint = projected_point.tint().scale(map.ambient());
+            let tint = match projected_point.face() {
+                WallFace::Vertical => tint.scale(VERTICAL_FACE_BRIGHTNESS),
+                WallFace::Horizontal => tint,
+            };
+
             let action = DrawAction::TexturedLine(
                 start,
                 end,
                 projected_point.texture(),
                 projected_point.offset_in_bloc(),
+                tint,
+                shade,
             );
             DrawActionZIndex::new(action, cartesian_distance)
         })
         .collect()
 }
 
-pub fn build_weapons(view: ViewScreen, weapon: Weapon) -> DrawAction {
+pub fn build_weapons(view: ViewScreen, weapon: Weapon) -> Vec<DrawAction> {
     let state = weapon.state();
 
     let texture = match state {
@@ -87,61 +157,189 @@ pub fn build_weapons(view: ViewScreen, weapon: Weapon) -> DrawAction {
         ShootState::Finished => weapon.configuration().default(),
     };
 
-    DrawAction::Sprite(
-        ScreenPoint::new(0, 0),
-        ScreenPoint::new(view.width(), view.height()),
-        texture,
-    )
+    let (start, end) = weapon.configuration().view_model().rect(view);
+
+    let muzzle_flash = match (state, weapon.configuration().muzzle_flash()) {
+        (ShootState::Active | ShootState::AlreadyHit, Some(muzzle_flash)) => Some(DrawAction::AdditiveSprite(start, end, muzzle_flash)),
+        _ => None,
+    };
+
+    [vec![DrawAction::Sprite(start, end, texture, WHITE)], muzzle_flash.into_iter().collect()].concat()
+}
+
+pub fn build_minimap(view: ViewScreen, map: &Map, player: &Player, explored: &HashSet<MapPoint>, scale: i32) -> Vec<DrawAction> {
+    let left = view.width() - MINIMAP_MARGIN - map.width() as i32 * scale;
+    let to_screen = |x: f32, y: f32| ScreenPoint::new(
+        left + (x * scale as f32) as i32,
+        MINIMAP_MARGIN + ((map.height() as f32 - y) * scale as f32) as i32,
+    );
+
+    let mut points: Vec<&MapPoint> = explored.iter().collect();
+    points.sort_by_key(|point| (point.y(), point.x()));
+
+    let mut actions: Vec<DrawAction> = points.into_iter()
+        .filter_map(|point| {
+            let color = match map.paving_at(point.x(), point.y())? {
+                Tile::SOLID(_) | Tile::DYNAMIC(_, _, _) => Color::new(200, 200, 200),
+                Tile::FIELD(color) => *color,
+                Tile::NOTHING => Color::new(60, 60, 60),
+            };
+            let start = to_screen(point.x() as f32, point.y() as f32 + 1.0);
+            let end = to_screen(point.x() as f32 + 1.0, point.y() as f32);
+            Some(DrawAction::Rectangle(start, end, color))
+        })
+        .collect();
+
+    let position = player.position();
+    let marker = to_screen(position.x(), position.y());
+    let marker_size = (scale / 2).max(1);
+    actions.push(DrawAction::Rectangle(
+        ScreenPoint::new(marker.x() - marker_size / 2, marker.y() - marker_size / 2),
+        ScreenPoint::new(marker.x() + marker_size - marker_size / 2, marker.y() + marker_size - marker_size / 2),
+        MINIMAP_PLAYER_COLOR,
+    ));
+
+    let facing = position.apply_force(Move::new(player.orientation(), 1.0));
+    actions.push(DrawAction::Line(
+        to_screen(position.x(), position.y()),
+        to_screen(facing.x(), facing.y()),
+        MINIMAP_PLAYER_COLOR,
+    ));
+
+    actions
+}
+
+pub fn build_hud(view: ViewScreen, font: FontIndex, health: u32, max_health: u32, ammo: Option<u32>) -> Vec<DrawAction> {
+    let bottom = view.height() - HUD_MARGIN;
+    let bar_top = bottom - HUD_BAR_HEIGHT;
+    let filled = if max_health == 0 {
+        0
+    } else {
+        (HUD_BAR_WIDTH as f32 * health.min(max_health) as f32 / max_health as f32) as i32
+    };
+
+    let mut actions = vec![
+        DrawAction::Rectangle(
+            ScreenPoint::new(HUD_MARGIN, bar_top),
+            ScreenPoint::new(HUD_MARGIN + HUD_BAR_WIDTH, bottom),
+            HUD_BAR_BACKGROUND,
+        ),
+        DrawAction::Rectangle(
+            ScreenPoint::new(HUD_MARGIN, bar_top),
+            ScreenPoint::new(HUD_MARGIN + filled, bottom),
+            HUD_HEALTH_COLOR,
+        ),
+        DrawAction::Text(
+            format!("HP {}", health),
+            ScreenPoint::new(HUD_MARGIN, bar_top - HUD_LABEL_HEIGHT),
+            ScreenPoint::new(HUD_MARGIN + HUD_LABEL_WIDTH, bar_top),
+            font,
+            WHITE,
+        ),
+    ];
+
+    // Weapons with infinite ammo have nothing worth counting
+    if let Some(ammo) = ammo {
+        let right = view.width() - HUD_MARGIN;
+        actions.push(DrawAction::Text(
+            format!("AMMO {}", ammo),
+            ScreenPoint::new(right - HUD_LABEL_WIDTH, bottom - HUD_LABEL_HEIGHT),
+            ScreenPoint::new(right, bottom),
+            font,
+            WHITE,
+        ));
+    }
+
+    actions
 }
 
 pub fn build_enemies(
     view: ViewScreen,
     view_position: Position,
     orientation: &Angle,
-    enemies: &Vec<Enemy>,
+    enemies: &[Enemy],
+    ambient: f32,
 ) -> Vec<DrawActionZIndex> {
-    let mut actions = vec![];
-    for enemy in enemies {
-        let view_vector = Vector::new(
-            view_position,
-            Position::new(
-                view_position.x() + orientation.cos(),
-                view_position.y() + orientation.sin(),
-            ),
-        );
-        let enemy_vector = Vector::new(view_position, enemy.position());
-
-        let angle = view_vector.angle(enemy_vector).unwrap();
-
-        let sprite = enemy.position().with_reference_point(&view_position);
-        let sprite_projection = angle.position_in_discreet_cone_straight(&view, orientation, sprite);
-
-        if let Some(projected) = sprite_projection {
-            let sprite_height = object_height(view, projected.distance());
-            let start = ScreenPoint::new(
-                (projected.column() - sprite_height / 2.0) as i32,
-                (view.height() as f32 / 2.0 - sprite_height / 2.0) as i32,
-            );
-            let end = ScreenPoint::new(
-                (projected.column() + sprite_height / 2.0) as i32,
-                (view.height() as f32 / 2.0 + sprite_height / 2.0) as i32,
-            );
-
+    enemies.iter()
+        .filter_map(|enemy| {
             let texture = if enemy.is_dead() {
                 enemy.texture_dead()
             } else {
                 enemy.texture()
             };
 
-            let action = DrawAction::Sprite(start, end, texture);
-            actions.push(DrawActionZIndex::new(action, projected.distance()))
-        }
+            build_sprite(view, view_position, orientation, enemy.position(), texture, 1.0, ambient)
+        })
+        .collect()
+}
+
+pub fn build_projectiles(
+    view: ViewScreen,
+    view_position: Position,
+    orientation: &Angle,
+    projectiles: &[Projectile],
+    ambient: f32,
+) -> Vec<DrawActionZIndex> {
+    projectiles.iter()
+        .filter_map(|projectile| build_sprite(view, view_position, orientation, projectile.position(), projectile.texture(), PROJECTILE_SCALE, ambient))
+        .collect()
+}
+
+pub fn build_pickups(
+    view: ViewScreen,
+    view_position: Position,
+    orientation: &Angle,
+    pickups: &[Pickup],
+    ambient: f32,
+) -> Vec<DrawActionZIndex> {
+    pickups.iter()
+        .filter_map(|pickup| build_sprite(view, view_position, orientation, pickup.position(), pickup.texture(), PICKUP_SCALE, ambient))
+        .collect()
+}
+
+fn build_sprite(
+    view: ViewScreen,
+    view_position: Position,
+    orientation: &Angle,
+    position: Position,
+    texture: TextureIndex,
+    scale: f32,
+    ambient: f32,
+) -> Option<DrawActionZIndex> {
+    let view_vector = Vector::new(
+        view_position,
+        Position::new(
+            view_position.x() + orientation.cos(),
+            view_position.y() + orientation.sin(),
+        ),
+    );
+    let sprite_vector = Vector::new(view_position, position);
+
+    if view_vector.scalar(sprite_vector) <= 0.0 || sprite_vector.length() > SPRITE_MAX_DISTANCE {
+        return None;
     }
-    actions
+
+    let angle = view_vector.angle(sprite_vector)?;
+
+    let sprite = position.with_reference_point(&view_position);
+    let projected = angle.position_in_discreet_cone_straight(&view, orientation, sprite)?;
+
+    let sprite_height = object_height(view, projected.distance()) * scale;
+    let start = ScreenPoint::new(
+        (projected.column() - sprite_height / 2.0) as i32,
+        (view.horizon() as f32 - sprite_height / 2.0) as i32,
+    );
+    let end = ScreenPoint::new(
+        (projected.column() + sprite_height / 2.0) as i32,
+        (view.horizon() as f32 + sprite_height / 2.0) as i32,
+    );
+
+    let action = DrawAction::Sprite(start, end, texture, WHITE.scale(ambient));
+    Some(DrawActionZIndex::new(action, projected.distance()))
 }
 
 fn object_height(view: ViewScreen, distance: f32) -> f32 {
-    (view.height() as f32 * view.ratio()) / distance
+    view.height() as f32 / (view.vertical_angle().multiplication(0.5).tan() * distance)
 }
 
 impl DrawActionZIndex {
@@ -153,7 +351,178 @@ impl DrawActionZIndex {
         &self.action
     }
 
-    pub fn z_index(&self) -> f32 {
-        self.z_index
+    // Farthest first; at equal depth walls come before sprites, then left to right
+    pub fn depth_order(&self, other: &DrawActionZIndex) -> Ordering {
+        other.z_index.total_cmp(&self.z_index)
+            .then_with(|| Self::kind_rank(&self.action).cmp(&Self::kind_rank(&other.action)))
+            .then_with(|| Self::column(&self.action).cmp(&Self::column(&other.action)))
+    }
+
+    fn kind_rank(action: &DrawAction) -> u8 {
+        match action {
+            DrawAction::TexturedLine(..) => 0,
+            DrawAction::Sprite(..) | DrawAction::AdditiveSprite(..) => 1,
+            _ => 2,
+        }
+    }
+
+    fn column(action: &DrawAction) -> i32 {
+        match action {
+            DrawAction::Rectangle(start, _, _)
+            | DrawAction::Line(start, _, _)
+            | DrawAction::TexturedLine(start, _, _, _, _, _)
+            | DrawAction::Text(_, start, _, _, _)
+            | DrawAction::Sprite(start, _, _, _)
+            | DrawAction::AdditiveSprite(start, _, _) => start.x(),
+            DrawAction::Clear(_) => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod level_drawer_test {
+    use std::collections::HashSet;
+
+    use spectral::prelude::*;
+
+    use crate::domain::actors::actor::Player;
+    use crate::domain::level_drawer::{build_hud, build_minimap, object_height, DrawActionZIndex};
+    use crate::domain::maths::{ANGLE_90, ANGLE_RIGHT};
+    use crate::domain::topology::coord::{MapPoint, Position, ScreenPoint};
+    use crate::domain::topology::index::{FontIndex, TextureIndex};
+    use crate::domain::topology::map::map_test::{build_map, default_stats};
+    use crate::domain::ui::color::{Color, WHITE};
+    use crate::domain::ui::draw_action::{DrawAction, Shade};
+    use crate::domain::ui::view::{AspectMode, ViewScreen};
+
+    #[test]
+    fn wall_height_should_follow_the_width_for_a_same_horizontal_fov() {
+        let square = ViewScreen::new(400, 400, ANGLE_90);
+        let widescreen = ViewScreen::new(400, 800, ANGLE_90);
+
+        assert_that!(object_height(square, 2.0)).is_close_to(200.0, 0.001);
+        assert_that!(object_height(widescreen, 2.0)).is_close_to(400.0, 0.001);
+    }
+
+    #[test]
+    fn wall_height_should_not_change_when_widening_the_fov_with_the_window() {
+        let square = ViewScreen::new(400, 400, ANGLE_90);
+        let widescreen = square.resize(800, 400, AspectMode::AdjustFov);
+
+        assert_that!(object_height(widescreen, 2.0)).is_close_to(object_height(square, 2.0), 0.01);
+    }
+
+    fn explore_all(width: i16, height: i16) -> HashSet<MapPoint> {
+        (0..width).flat_map(|x| (0..height).map(move |y| MapPoint::new(x, y))).collect()
+    }
+
+    fn health_bar_width(actions: &[DrawAction]) -> i32 {
+        actions.iter()
+            .find_map(|action| match action {
+                DrawAction::Rectangle(start, end, color) if *color == Color::new(200, 0, 0) => Some(end.x() - start.x()),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    fn texts(actions: &[DrawAction]) -> Vec<String> {
+        actions.iter()
+            .filter_map(|action| match action {
+                DrawAction::Text(text, _, _, _, _) => Some(text.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn health_bar_width_should_be_proportional_to_health() {
+        let view = ViewScreen::new(100, 200, ANGLE_RIGHT);
+
+        let full = build_hud(view, FontIndex::new(0), 100, 100, None);
+        let half = build_hud(view, FontIndex::new(0), 50, 100, None);
+
+        assert_that!(health_bar_width(&full)).is_equal_to(100);
+        assert_that!(health_bar_width(&half)).is_equal_to(50);
+    }
+
+    #[test]
+    fn hud_should_show_the_ammo_only_when_it_is_limited() {
+        let view = ViewScreen::new(100, 200, ANGLE_RIGHT);
+
+        let infinite = texts(&build_hud(view, FontIndex::new(0), 100, 100, None));
+        let limited = texts(&build_hud(view, FontIndex::new(0), 100, 100, Some(12)));
+
+        assert_that!(infinite).is_equal_to(vec![String::from("HP 100")]);
+        assert_that!(limited).is_equal_to(vec![String::from("HP 100"), String::from("AMMO 12")]);
+    }
+
+    #[test]
+    fn minimap_should_draw_one_rectangle_per_explored_tile_and_a_player_marker() {
+        let view = ViewScreen::new(100, 200, ANGLE_RIGHT);
+        let player = Player::new(Position::new(1.5, 1.5), ANGLE_RIGHT, default_stats());
+        let small = build_map("###\n#r#\n###");
+        let large = build_map("#####\n#r  #\n#   #\n#####");
+
+        let rectangles = |actions: &Vec<DrawAction>| actions.iter().filter(|action| matches!(action, DrawAction::Rectangle(_, _, _))).count();
+        let small_actions = build_minimap(view, &small, &player, &explore_all(3, 3), 4);
+        let large_actions = build_minimap(view, &large, &player, &explore_all(5, 4), 4);
+
+        assert_that!(rectangles(&small_actions)).is_equal_to(9 + 1);
+        assert_that!(rectangles(&large_actions)).is_equal_to(20 + 1);
+    }
+
+    #[test]
+    fn minimap_tiles_should_be_drawn_row_by_row() {
+        let view = ViewScreen::new(100, 200, ANGLE_RIGHT);
+        let player = Player::new(Position::new(1.5, 1.5), ANGLE_RIGHT, default_stats());
+        let map = build_map("###\n#r#\n###");
+
+        let corners: Vec<(i32, i32)> = build_minimap(view, &map, &player, &explore_all(3, 3), 4)
+            .into_iter()
+            .take(9)
+            .filter_map(|action| match action {
+                DrawAction::Rectangle(start, _, _) => Some((start.x(), start.y())),
+                _ => None,
+            })
+            .collect();
+
+        let mut sorted = corners.clone();
+        sorted.sort_by_key(|(x, y)| (-y, *x));
+        assert_that!(corners).has_length(9);
+        assert_that!(corners).is_equal_to(sorted);
+    }
+
+    #[test]
+    fn minimap_player_marker_should_be_scaled_in_the_top_right_corner() {
+        let view = ViewScreen::new(100, 200, ANGLE_RIGHT);
+        let player = Player::new(Position::new(1.5, 1.5), ANGLE_RIGHT, default_stats());
+        let map = build_map("###\n#r#\n###");
+
+        let marker = build_minimap(view, &map, &player, &HashSet::new(), 8)
+            .into_iter()
+            .find_map(|action| match action {
+                DrawAction::Rectangle(start, end, color) if color == Color::new(255, 0, 0) => Some((start.x(), start.y(), end.x(), end.y())),
+                _ => None,
+            });
+
+        assert_that!(marker).is_some().is_equal_to((176, 20, 180, 24));
+    }
+
+    #[test]
+    fn equal_depths_should_always_sort_walls_before_sprites_then_by_column() {
+        let sprite = || DrawActionZIndex::new(DrawAction::Sprite(ScreenPoint::new(0, 0), ScreenPoint::new(5, 5), TextureIndex::new(0), WHITE), 2.0);
+        let wall = |x| DrawActionZIndex::new(DrawAction::TexturedLine(ScreenPoint::new(x, 0), ScreenPoint::new(x, 5), TextureIndex::new(1), 0.0, WHITE, Shade::default()), 2.0);
+
+        for mut actions in [vec![sprite(), wall(3), wall(1)], vec![wall(1), sprite(), wall(3)], vec![wall(3), wall(1), sprite()]] {
+            actions.sort_by(|a, b| a.depth_order(b));
+
+            let columns: Vec<(bool, i32)> = actions.iter()
+                .map(|ordered| match ordered.action() {
+                    DrawAction::TexturedLine(start, _, _, _, _, _) => (true, start.x()),
+                    _ => (false, 0),
+                })
+                .collect();
+            assert_that!(columns).is_equal_to(vec![(true, 1), (true, 3), (false, 0)]);
+        }
     }
 }
@@ -79,12 +79,36 @@ impl Vector {
     }
 
     pub fn angle_sign_is_negative(&self, vector: Vector) -> bool {
+        self.cross(vector) >= 0.0
+    }
+
+    pub fn cross(&self, vector: Vector) -> f32 {
         let self_origin = self.to_origin().end;
         let other_origin = vector.to_origin().end;
 
-        let z_in_cross_product =
-            self_origin.x() * other_origin.y() - self_origin.y() * other_origin.x();
-        z_in_cross_product >= 0.0
+        self_origin.x() * other_origin.y() - self_origin.y() * other_origin.x()
+    }
+
+    pub fn normalized(&self) -> Self {
+        let length = self.length();
+        if length == 0.0 {
+            return *self;
+        }
+
+        let origin = self.to_origin().end;
+        Vector {
+            start: self.start,
+            end: Position::new(self.start.x() + origin.x() / length, self.start.y() + origin.y() / length),
+        }
+    }
+
+    pub fn direction(&self) -> Option<Angle> {
+        if self.length() == 0.0 {
+            return None;
+        }
+
+        let origin = self.to_origin().end;
+        Some(Angle::new(origin.y().atan2(origin.x())))
     }
 
     pub fn length(&self) -> f32 {
@@ -143,8 +167,22 @@ impl Angle {
     }
 
     pub fn add(&self, other: Angle) -> Self {
-        Self {
-            radiant: self.radiant + other.radiant,
+        Angle::new(self.radiant + other.radiant)
+    }
+
+    pub fn sub(&self, other: Angle) -> Self {
+        Angle::new(self.radiant - other.radiant)
+    }
+
+    /// Signed rotation from this angle to the other one, between -π and π.
+    pub fn shortest_difference(&self, other: Angle) -> Self {
+        let difference = other.sub(*self).radiant;
+        if difference > PI {
+            Angle::new(difference - 2.0 * PI)
+        } else if difference < -PI {
+            Angle::new(difference + 2.0 * PI)
+        } else {
+            Angle::new(difference)
         }
     }
 
@@ -242,6 +280,14 @@ impl Angle {
         }
     }
 
+    pub fn align_to_axis(&self) -> Self {
+        if self.cos().abs() >= self.sin().abs() {
+            self.align_to_x()
+        } else {
+            self.align_to_y()
+        }
+    }
+
     pub fn sign(&self, negative_angle: bool) -> Self {
         if negative_angle {
             Angle::new(-self.radiant)
@@ -315,7 +361,7 @@ mod angle_test {
 
     use spectral::prelude::*;
 
-    use crate::domain::maths::Angle;
+    use crate::domain::maths::{Angle, ANGLE_UP};
 
     #[test]
     fn angle_should_be_created_from_radiant() {
@@ -372,6 +418,54 @@ mod angle_test {
 
         assert_that!(result.to_radiant()).is_close_to(-1.3, 0.001);
     }
+
+    #[test]
+    fn angle_should_stay_normalized_when_added_many_times() {
+        let mut angle = Angle::new(0.0);
+        for _ in 0..1001 {
+            angle = angle.add(ANGLE_UP);
+        }
+
+        assert_that!(angle.to_radiant()).is_greater_than_or_equal_to(-2.0 * PI);
+        assert_that!(angle.to_radiant()).is_less_than_or_equal_to(2.0 * PI);
+        assert_that!(angle.sin()).is_close_to(1.0, 0.001);
+    }
+
+    #[test]
+    fn angle_should_be_subtracted() {
+        let angle = Angle::new(1.2);
+        let result = angle.sub(Angle::new(2.5));
+
+        assert_that!(result.to_radiant()).is_close_to(-1.3, 0.001);
+    }
+
+    #[test]
+    fn shortest_difference_should_wrap_around_zero() {
+        let difference = Angle::from_degree(350.0).shortest_difference(Angle::from_degree(10.0));
+
+        assert_that!(difference.to_radiant()).is_close_to(Angle::from_degree(20.0).to_radiant(), 0.001);
+    }
+
+    #[test]
+    fn shortest_difference_should_be_negative_when_turning_clockwise() {
+        let difference = Angle::from_degree(10.0).shortest_difference(Angle::from_degree(350.0));
+
+        assert_that!(difference.to_radiant()).is_close_to(Angle::from_degree(-20.0).to_radiant(), 0.001);
+    }
+
+    #[test]
+    fn shortest_difference_should_handle_negative_angles() {
+        let difference = Angle::from_degree(-170.0).shortest_difference(Angle::from_degree(170.0));
+
+        assert_that!(difference.to_radiant()).is_close_to(Angle::from_degree(-20.0).to_radiant(), 0.001);
+    }
+
+    #[test]
+    fn shortest_difference_should_stay_between_minus_pi_and_pi() {
+        let difference = Angle::from_degree(90.0).shortest_difference(Angle::from_degree(-540.0));
+
+        assert_that!(difference.to_radiant().abs()).is_close_to(PI / 2.0, 0.001);
+    }
 }
 
 
@@ -459,6 +553,51 @@ mod vector_test {
         assert_that!(length).is_equal_to(0.0);
     }
 
+    #[test]
+    fn normalized_vector_should_have_a_length_of_one() {
+        let vector = Vector::new(Position::new(1.0, 2.0), Position::new(4.0, 6.0));
+
+        let normalized = vector.normalized();
+
+        assert_that!(normalized.length()).is_close_to(1.0, 0.001);
+        assert_that!(normalized.start.x()).is_equal_to(1.0);
+        assert_that!(normalized.end.x()).is_close_to(1.6, 0.001);
+        assert_that!(normalized.end.y()).is_close_to(2.8, 0.001);
+    }
+
+    #[test]
+    fn normalized_zero_length_vector_should_be_unchanged() {
+        let vector = Vector::new(Position::new(1.0, 2.0), Position::new(1.0, 2.0));
+
+        assert_that!(vector.normalized().length()).is_equal_to(0.0);
+    }
+
+    #[test]
+    fn cross_sign_should_match_angle_sign() {
+        let vector = Vector::new(Position::new(0.0, 0.0), Position::new(1.0, 0.0));
+        let above = Vector::new(Position::new(0.0, 0.0), Position::new(1.0, 1.0));
+        let below = Vector::new(Position::new(0.0, 0.0), Position::new(1.0, -1.0));
+
+        assert_that!(vector.cross(above)).is_close_to(1.0, 0.001);
+        assert_that!(vector.cross(below)).is_close_to(-1.0, 0.001);
+        assert_that!(vector.angle_sign_is_negative(above)).is_true();
+        assert_that!(vector.angle_sign_is_negative(below)).is_false();
+    }
+
+    #[test]
+    fn direction_should_be_the_angle_from_abscissa() {
+        let vector = Vector::new(Position::new(1.0, 1.0), Position::new(1.0, 3.0));
+
+        assert_that!(vector.direction().map(|angle| angle.to_radiant())).is_some().is_close_to(ANGLE_90.to_radiant(), 0.001);
+    }
+
+    #[test]
+    fn zero_length_vector_should_have_no_direction() {
+        let vector = Vector::new(Position::new(1.0, 1.0), Position::new(1.0, 1.0));
+
+        assert_that!(vector.direction().is_none()).is_true();
+    }
+
     #[test]
     fn should_calculate_scalar_on_vector_from_origin() {
         let vector1 = Vector::new(Position::new(0.0, 0.0), Position::new(3.2, 4.2));
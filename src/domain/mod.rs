@@ -1,6 +1,10 @@
+pub mod error;
+pub mod game;
 pub mod level;
 pub mod maths;
 pub mod resources;
+pub mod save;
+pub mod sound;
 pub mod control;
 pub mod topology;
 pub mod ui;
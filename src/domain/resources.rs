@@ -1,58 +1,177 @@
+use std::collections::HashMap;
+
+use crate::domain::error::EngineError;
+use crate::domain::topology::index::{FontIndex, TextureIndex};
+
+pub trait ResourceRegistryLoader {
+    fn load_texture(&mut self, file: String) -> Result<TextureIndex, EngineError>;
+    fn load_font(&mut self, filename: String) -> Result<FontIndex, EngineError>;
+}
+
+#[derive(Default)]
+pub struct ResourceCache {
+    textures: HashMap<String, TextureIndex>,
+    fonts: HashMap<String, FontIndex>,
+}
+
 #[derive(Copy, Clone)]
 pub struct ResourceLoader {
-    binary_loader: fn(String) -> Vec<u8>,
+    binary_loader: fn(String) -> Result<Vec<u8>, String>,
     path_generator: fn(String) -> String,
+    save_reader: fn(String) -> Result<Vec<u8>, String>,
+    save_writer: fn(String, Vec<u8>) -> Result<(), String>,
 }
 
 impl ResourceLoader {
-    pub fn new(binary_loader: fn(String) -> Vec<u8>, path_generator: fn(String) -> String) -> Self {
+    pub fn new(binary_loader: fn(String) -> Result<Vec<u8>, String>, path_generator: fn(String) -> String) -> Self {
         Self {
             binary_loader,
             path_generator,
+            save_reader: |path| Err(format!("No storage to read '{}'", path)),
+            save_writer: |path, _| Err(format!("No storage to write '{}'", path)),
         }
     }
 
-    pub fn load_as_binary(&self, path: String) -> Vec<u8> {
-        (self.binary_loader)(path)
+    pub fn with_saves(&self, save_reader: fn(String) -> Result<Vec<u8>, String>, save_writer: fn(String, Vec<u8>) -> Result<(), String>) -> Self {
+        Self {
+            binary_loader: self.binary_loader,
+            path_generator: self.path_generator,
+            save_reader,
+            save_writer,
+        }
+    }
+
+    pub fn load_as_binary(&self, path: String) -> Result<Vec<u8>, EngineError> {
+        (self.binary_loader)(path.clone())
+            .map_err(|e| EngineError::Resource(format!("'{}': {}", path, e)))
     }
 
-    pub fn load_as_string(&self, path: String) -> String {
-        let binary = (self.binary_loader)(path);
-        String::from_utf8(binary).unwrap()
+    pub fn load_as_string(&self, path: String) -> Result<String, EngineError> {
+        let binary = self.load_as_binary(path.clone())?;
+        String::from_utf8(binary).map_err(|e| EngineError::Resource(format!("'{}' is not valid text: {}", path, e)))
     }
 
     pub fn load_as_file(&self, path: String) -> String {
         (self.path_generator)(path)
     }
+
+    pub fn read_save(&self, path: String) -> Result<String, String> {
+        let binary = (self.save_reader)(path)?;
+        String::from_utf8(binary).map_err(|e| format!("Save is not valid: {}", e))
+    }
+
+    pub fn write_save(&self, path: String, content: String) -> Result<(), String> {
+        (self.save_writer)(path, content.into_bytes())
+    }
+}
+
+impl ResourceCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn texture(&mut self, file: String, load: impl FnOnce(String) -> Result<TextureIndex, EngineError>) -> Result<TextureIndex, EngineError> {
+        if let Some(index) = self.textures.get(&file) {
+            return Ok(*index);
+        }
+        let index = load(file.clone())?;
+        self.textures.insert(file, index);
+        Ok(index)
+    }
+
+    pub fn font(&mut self, file: String, load: impl FnOnce(String) -> Result<FontIndex, EngineError>) -> Result<FontIndex, EngineError> {
+        if let Some(index) = self.fonts.get(&file) {
+            return Ok(*index);
+        }
+        let index = load(file.clone())?;
+        self.fonts.insert(file, index);
+        Ok(index)
+    }
 }
 
 #[cfg(test)]
 mod resource_loader_test {
     use spectral::prelude::*;
 
-    use crate::domain::resources::ResourceLoader;
+    use crate::domain::error::EngineError;
+    use crate::domain::resources::{ResourceCache, ResourceLoader};
+    use crate::domain::topology::index::TextureIndex;
+
+    #[test]
+    fn same_texture_file_should_be_loaded_once() {
+        let mut cache = ResourceCache::new();
+        let mut loads = 0;
+        let mut load = |_: String| {
+            loads += 1;
+            Ok(TextureIndex::new(loads))
+        };
+
+        let first = cache.texture(String::from("wall.png"), &mut load).unwrap();
+        let second = cache.texture(String::from("wall.png"), &mut load).unwrap();
+        let other = cache.texture(String::from("door.png"), &mut load).unwrap();
+
+        assert_that!(second).is_equal_to(first);
+        assert_that!(other).is_not_equal_to(first);
+        assert_that!(loads).is_equal_to(2);
+    }
+
+    #[test]
+    fn failed_texture_load_should_not_be_cached() {
+        let mut cache = ResourceCache::new();
+
+        let failed = cache.texture(String::from("wall.png"), |file| Err(EngineError::Resource(file)));
+        let loaded = cache.texture(String::from("wall.png"), |_| Ok(TextureIndex::new(1)));
+
+        assert!(matches!(failed, Err(EngineError::Resource(_))));
+        assert_that!(loaded).is_ok().is_equal_to(TextureIndex::new(1));
+    }
 
     #[test]
     fn should_return_binary_as_is() {
-        let loader = ResourceLoader::new(|_| vec![1, 2, 3], |_| String::from("test"));
+        let loader = ResourceLoader::new(|_| Ok(vec![1, 2, 3]), |_| String::from("test"));
 
         let loaded = loader.load_as_binary(String::from("path"));
 
-        assert_that!(loaded).is_equal_to(vec![1, 2, 3]);
+        assert_that!(loaded).is_ok().is_equal_to(vec![1, 2, 3]);
     }
 
     #[test]
     fn should_return_string_as_utf8() {
-        let loader = ResourceLoader::new(|_| String::from("éば~").into_bytes(), |_| String::from("test"));
+        let loader = ResourceLoader::new(|_| Ok(String::from("éば~").into_bytes()), |_| String::from("test"));
 
         let loaded = loader.load_as_string(String::from("path"));
 
-        assert_that!(loaded).is_equal_to(String::from("éば~"));
+        assert_that!(loaded).is_ok().is_equal_to(String::from("éば~"));
+    }
+
+    #[test]
+    fn missing_file_should_be_a_resource_error() {
+        let loader = ResourceLoader::new(|_| Err(String::from("No such file")), |_| String::from("test"));
+
+        let loaded = loader.load_as_string(String::from("game.json"));
+
+        assert!(matches!(loaded, Err(EngineError::Resource(_))));
+    }
+
+    #[test]
+    fn invalid_text_should_be_a_resource_error() {
+        let loader = ResourceLoader::new(|_| Ok(vec![0xff, 0xfe]), |_| String::from("test"));
+
+        let loaded = loader.load_as_string(String::from("game.json"));
+
+        assert!(matches!(loaded, Err(EngineError::Resource(_))));
+    }
+
+    #[test]
+    fn reading_a_save_without_storage_should_fail() {
+        let loader = ResourceLoader::new(|_| Ok(vec![]), |_| String::from("/path"));
+
+        assert_that!(loader.read_save(String::from("quick.sav"))).is_err();
     }
 
     #[test]
     fn should_get_file_path() {
-        let loader = ResourceLoader::new(|_| vec![], |_| String::from("/path"));
+        let loader = ResourceLoader::new(|_| Ok(vec![]), |_| String::from("/path"));
 
         let path = loader.load_as_file(String::from("path"));
 
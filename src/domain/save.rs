@@ -0,0 +1,167 @@
+use crate::domain::actors::actor::EnemyOrigin;
+use crate::domain::maths::Angle;
+use crate::domain::topology::coord::Position;
+
+#[derive(Copy, Clone, Debug)]
+pub struct SavedPlayer {
+    position: Position,
+    orientation: Angle,
+    health: u32,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct SavedEnemy {
+    origin: EnemyOrigin,
+    position: Position,
+    health: u32,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SavedDoor {
+    x: i16,
+    y: i16,
+    activated: bool,
+    opening_percentage: f32,
+}
+
+#[derive(Clone, Debug)]
+pub struct LevelState {
+    map: Option<String>,
+    player: SavedPlayer,
+    enemies: Vec<SavedEnemy>,
+    doors: Vec<SavedDoor>,
+    current_weapon: usize,
+    ammo: Vec<Option<u32>>,
+    spawned: Vec<u32>,
+}
+
+impl SavedPlayer {
+    pub fn new(position: Position, orientation: Angle, health: u32) -> Self {
+        Self {
+            position,
+            orientation,
+            health,
+        }
+    }
+
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    pub fn orientation(&self) -> Angle {
+        self.orientation
+    }
+
+    pub fn health(&self) -> u32 {
+        self.health
+    }
+}
+
+impl SavedEnemy {
+    pub fn new(origin: EnemyOrigin, position: Position, health: u32) -> Self {
+        Self { origin, position, health }
+    }
+
+    pub fn origin(&self) -> EnemyOrigin {
+        self.origin
+    }
+
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    pub fn health(&self) -> u32 {
+        self.health
+    }
+}
+
+impl SavedDoor {
+    pub fn new(x: i16, y: i16, activated: bool, opening_percentage: f32) -> Self {
+        Self {
+            x,
+            y,
+            activated,
+            opening_percentage,
+        }
+    }
+
+    pub fn x(&self) -> i16 {
+        self.x
+    }
+
+    pub fn y(&self) -> i16 {
+        self.y
+    }
+
+    pub fn activated(&self) -> bool {
+        self.activated
+    }
+
+    pub fn opening_percentage(&self) -> f32 {
+        self.opening_percentage
+    }
+}
+
+impl LevelState {
+    pub fn new(player: SavedPlayer, enemies: Vec<SavedEnemy>, doors: Vec<SavedDoor>) -> Self {
+        Self {
+            map: None,
+            player,
+            enemies,
+            doors,
+            current_weapon: 0,
+            ammo: vec![],
+            spawned: vec![],
+        }
+    }
+
+    pub fn with_map(self, map: Option<String>) -> Self {
+        Self {
+            map,
+            ..self
+        }
+    }
+
+    pub fn with_weapons(self, current_weapon: usize, ammo: Vec<Option<u32>>) -> Self {
+        Self {
+            current_weapon,
+            ammo,
+            ..self
+        }
+    }
+
+    pub fn with_spawned(self, spawned: Vec<u32>) -> Self {
+        Self {
+            spawned,
+            ..self
+        }
+    }
+
+    pub fn map(&self) -> Option<&str> {
+        self.map.as_deref()
+    }
+
+    pub fn player(&self) -> SavedPlayer {
+        self.player
+    }
+
+    pub fn enemies(&self) -> &[SavedEnemy] {
+        &self.enemies
+    }
+
+    pub fn doors(&self) -> &[SavedDoor] {
+        &self.doors
+    }
+
+    pub fn current_weapon(&self) -> usize {
+        self.current_weapon
+    }
+
+    pub fn ammo(&self) -> &[Option<u32>] {
+        &self.ammo
+    }
+
+    pub fn spawned(&self) -> &[u32] {
+        &self.spawned
+    }
+}
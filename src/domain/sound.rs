@@ -0,0 +1,107 @@
+use crate::domain::maths::{signed_angle, Angle};
+use crate::domain::topology::coord::Position;
+
+const SOUND_REFERENCE_DISTANCE: f32 = 4.0;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SoundKind {
+    Bump,
+    EnemyShot,
+    PlayerShot,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct SoundEvent {
+    kind: SoundKind,
+    position: Position,
+    volume: f32,
+    pan: f32,
+}
+
+impl SoundEvent {
+    pub fn new(kind: SoundKind, position: Position) -> Self {
+        Self {
+            kind,
+            position,
+            volume: 1.0,
+            pan: 0.0,
+        }
+    }
+
+    pub fn heard_from(&self, listener: Position, orientation: Angle) -> Self {
+        let distance = listener.distance(&self.position);
+        let pan = match signed_angle(listener, self.position) {
+            Some(direction) => -(direction.to_radiant() - orientation.to_radiant()).sin(),
+            None => 0.0,
+        };
+
+        Self {
+            kind: self.kind,
+            position: self.position,
+            volume: 1.0 / (1.0 + distance / SOUND_REFERENCE_DISTANCE),
+            pan,
+        }
+    }
+
+    pub fn kind(&self) -> SoundKind {
+        self.kind
+    }
+
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    pub fn volume(&self) -> f32 {
+        self.volume
+    }
+
+    pub fn pan(&self) -> f32 {
+        self.pan
+    }
+}
+
+pub fn loudest(events: &[SoundEvent], maximum: usize) -> Vec<SoundEvent> {
+    let mut sorted = events.to_vec();
+    sorted.sort_by(|a, b| b.volume.total_cmp(&a.volume));
+    sorted.truncate(maximum);
+    sorted
+}
+
+#[cfg(test)]
+mod sound_event_test {
+    use spectral::prelude::*;
+
+    use crate::domain::maths::ANGLE_UP;
+    use crate::domain::sound::{loudest, SoundEvent, SoundKind};
+    use crate::domain::topology::coord::Position;
+
+    #[test]
+    fn sound_on_the_left_should_be_panned_left() {
+        let event = SoundEvent::new(SoundKind::EnemyShot, Position::new(0.0, 5.0))
+            .heard_from(Position::new(5.0, 5.0), ANGLE_UP);
+
+        assert_that!(event.pan()).is_close_to(-1.0, 0.001);
+    }
+
+    #[test]
+    fn sound_in_front_should_be_centered() {
+        let event = SoundEvent::new(SoundKind::EnemyShot, Position::new(5.0, 9.0))
+            .heard_from(Position::new(5.0, 5.0), ANGLE_UP);
+
+        assert_that!(event.pan()).is_close_to(0.0, 0.001);
+    }
+
+    #[test]
+    fn loudest_should_keep_only_the_nearest_events() {
+        let listener = Position::new(0.0, 0.0);
+        let events: Vec<SoundEvent> = [3.0, 1.0, 5.0, 2.0, 4.0].iter()
+            .map(|x| SoundEvent::new(SoundKind::EnemyShot, Position::new(*x, 0.0)).heard_from(listener, ANGLE_UP))
+            .collect();
+
+        let kept: Vec<f32> = loudest(&events, 3).iter()
+            .map(|event| event.position().x())
+            .collect();
+
+        assert_that!(kept).is_equal_to(vec![1.0, 2.0, 3.0]);
+    }
+}
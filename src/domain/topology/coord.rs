@@ -6,7 +6,7 @@ pub struct ScreenPoint {
     y: i32,
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct MapPoint {
     x: i16,
     y: i16,
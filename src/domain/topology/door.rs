@@ -2,6 +2,10 @@ use dyn_clone::DynClone;
 
 pub trait Openable: DynClone + Sync {
     fn door_column(&self, opening_percentage: f32, offset: f32) -> Option<f32>;
+
+    fn vertical_clip(&self, _opening_percentage: f32) -> f32 {
+        0.0
+    }
 }
 dyn_clone::clone_trait_object!(Openable);
 
@@ -11,6 +15,9 @@ pub struct CentralOpening {}
 #[derive(Clone)]
 pub struct LateralOpening {}
 
+#[derive(Clone, Default)]
+pub struct VerticalOpening {}
+
 impl CentralOpening {
     pub fn default() -> Self {
         Self {}
@@ -52,12 +59,26 @@ impl Openable for LateralOpening {
     }
 }
 
+impl Openable for VerticalOpening {
+    fn door_column(&self, opening_percentage: f32, offset: f32) -> Option<f32> {
+        if opening_percentage >= 1.0 {
+            return None;
+        }
+
+        Some(offset)
+    }
+
+    fn vertical_clip(&self, opening_percentage: f32) -> f32 {
+        opening_percentage
+    }
+}
+
 #[cfg(test)]
 mod openable_test {
     use rand::Rng;
     use spectral::prelude::*;
 
-    use crate::domain::topology::door::{CentralOpening, LateralOpening, Openable};
+    use crate::domain::topology::door::{CentralOpening, LateralOpening, Openable, VerticalOpening};
 
     fn test_on_range(f: impl Fn(f32)) {
         let mut rng = rand::thread_rng();
@@ -155,5 +176,31 @@ mod openable_test {
         let texture = door.door_column(0.25, 0.55);
         assert_that!(texture).is_none();
     }
+
+    #[test]
+    fn vertical_door_should_keep_texture_column_while_opening() {
+        let door = VerticalOpening::default();
+
+        test_on_range(|offset| {
+            let texture = door.door_column(0.5, offset);
+            assert_that!(texture).is_some().is_equal_to(offset);
+        });
+    }
+
+    #[test]
+    fn vertical_door_clip_should_follow_opening() {
+        let door = VerticalOpening::default();
+
+        assert_that!(door.vertical_clip(0.0)).is_equal_to(0.0);
+        assert_that!(door.vertical_clip(0.5)).is_equal_to(0.5);
+        assert_that!(door.vertical_clip(1.0)).is_equal_to(1.0);
+    }
+
+    #[test]
+    fn lateral_door_should_never_be_clipped() {
+        let door = LateralOpening::default();
+
+        assert_that!(door.vertical_clip(0.5)).is_equal_to(0.0);
+    }
 }
 
@@ -1,18 +1,41 @@
 use std::collections::HashMap;
+use std::iter;
 
-use crate::domain::actors::actor::{Enemy, Player, PlayerStats};
+use crate::domain::actors::actor::{Enemy, EnemyOrigin, MeleeAttack, Perception, Player, PlayerStats, RangedAttack};
+use crate::domain::actors::pickup::{Pickup, PickupType};
 use crate::domain::actors::shoot::{Weapon, WeaponConfiguration};
+use crate::domain::actors::spawner::{Spawner, SpawnerType};
 use crate::domain::control::actions::ActionStateBuilder;
-use crate::domain::maths::Angle;
-use crate::domain::topology::coord::Position;
+use crate::domain::maths::{Angle, between};
+use crate::domain::topology::coord::{MapPoint, Position};
 use crate::domain::topology::index::TextureIndex;
+use crate::domain::ui::color::{Color, WHITE};
+use crate::domain::ui::draw_action::Shade;
+
+const DEFAULT_CEILING_COLOR: Color = Color::new(50, 50, 50);
+const DEFAULT_FLOOR_COLOR: Color = Color::new(100, 100, 100);
 
 pub struct Map {
     paving: Vec<Vec<Tile>>,
+    tints: Vec<Vec<Color>>,
+    thicknesses: Vec<Vec<f32>>,
+    chars: Vec<Vec<char>>,
+    locks: HashMap<(i16, i16), KeyColor>,
     border_texture: TextureIndex,
     enemies: Vec<Enemy>,
+    spawners: Vec<Spawner>,
+    checkpoints: Vec<Checkpoint>,
+    exits: Vec<Exit>,
+    teleports: Vec<Teleport>,
+    pickups: Vec<Pickup>,
     player: Option<Player>,
-    weapon: WeaponConfiguration,
+    weapons: Vec<WeaponConfiguration>,
+    metadata: MapMetadata,
+    ambient: f32,
+    backdrop: Option<Backdrop>,
+    fog: Option<Fog>,
+    ceiling_color: Color,
+    floor_color: Color,
     width: i16,
     height: i16,
 }
@@ -21,14 +44,63 @@ pub struct Map {
 pub enum Tile {
     SOLID(TextureIndex),
     DYNAMIC(TextureIndex, TextureIndex, ActionStateBuilder),
+    FIELD(Color),
     NOTHING,
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum KeyColor {
+    Red,
+    Yellow,
+    Blue,
+}
+
 #[derive(Copy, Clone)]
 pub struct EnemyType {
     texture: TextureIndex,
     texture_dead: TextureIndex,
     health: u32,
+    ranged_attack: Option<RangedAttack>,
+    melee_attack: MeleeAttack,
+    perception: Option<Perception>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Checkpoint {
+    name: String,
+    position: Position,
+}
+
+#[derive(Clone, Debug)]
+pub struct Exit {
+    target: String,
+    position: Position,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Teleport {
+    position: Position,
+    destination: Position,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct MapMetadata {
+    name: Option<String>,
+    author: Option<String>,
+    par_time_in_seconds: Option<f32>,
+    description: Option<String>,
+}
+
+#[derive(Copy, Clone)]
+pub struct Backdrop {
+    texture: TextureIndex,
+    height: f32,
+}
+
+#[derive(Copy, Clone)]
+pub struct Fog {
+    color: Color,
+    distance: f32,
 }
 
 #[derive(Copy, Clone)]
@@ -36,38 +108,128 @@ pub struct SpawnPoint {
     orientation: Angle,
 }
 
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SpawnOrientationMode {
+    Free,
+    Snap,
+}
+
+#[derive(Copy, Clone)]
+pub struct SpawnOrientation {
+    mode: SpawnOrientationMode,
+    default_in_degrees: f32,
+}
+
 #[derive(Clone)]
 pub struct MapConfiguration {
     conf: HashMap<char, Tile>,
     enemies: HashMap<char, EnemyType>,
     spawn: HashMap<char, SpawnPoint>,
+    spawners: HashMap<char, SpawnerType>,
+    checkpoints: HashMap<char, String>,
+    exits: HashMap<char, String>,
+    teleports: HashMap<char, char>,
+    pickups: HashMap<char, PickupType>,
+    tints: HashMap<char, Color>,
+    thicknesses: HashMap<char, f32>,
+    locks: HashMap<char, KeyColor>,
     map_border_texture: TextureIndex,
     player_conf: PlayerStats,
-    weapon: WeaponConfiguration,
+    weapons: Vec<WeaponConfiguration>,
+    metadata: MapMetadata,
+    ambient: f32,
+    backdrop: Option<Backdrop>,
+    fog: Option<Fog>,
+    ceiling_color: Color,
+    floor_color: Color,
+    pad_short_rows: bool,
+    comment_prefix: Option<String>,
 }
 
 impl Map {
     pub fn new(paving: &str, configuration: MapConfiguration) -> Result<Self, String> {
         let mut enemies = vec![];
+        let mut spawners = vec![];
+        let mut checkpoints = vec![];
+        let mut exits = vec![];
+        let mut teleport_entries: Vec<(char, Position)> = vec![];
+        let mut pickups = vec![];
         let mut player = None;
+        let mut extra_spawns = vec![];
 
         let mut pav_x: Vec<Vec<Tile>> = vec![];
-        let split: Vec<&str> = paving.split('\n').collect();
+        let mut tint_x: Vec<Vec<Color>> = vec![];
+        let mut thickness_x: Vec<Vec<f32>> = vec![];
+        let mut char_x: Vec<Vec<char>> = vec![];
+        let mut locks = HashMap::new();
+        let mut split: Vec<&str> = paving.split('\n')
+            .filter(|line| !configuration.is_comment(line))
+            .collect();
+        while split.last().is_some_and(|line| line.is_empty()) {
+            split.pop();
+        }
         let mut y: i32 = split.len() as i32 - 1;
+        let padded_width = if configuration.pad_short_rows {
+            split.iter().map(|line| line.chars().count()).max().unwrap_or(0)
+        } else {
+            0
+        };
 
         for line in split {
-            for (x, char) in line.chars().enumerate() {
+            let padding = padded_width.saturating_sub(line.chars().count());
+            for (x, char) in line.chars().map(Some).chain(iter::repeat_n(None, padding)).enumerate() {
                 if pav_x.len() <= x {
                     pav_x.push(vec![]);
+                    tint_x.push(vec![]);
+                    thickness_x.push(vec![]);
+                    char_x.push(vec![]);
+                }
+                let char = match char {
+                    Some(char) => char,
+                    None => {
+                        tint_x[x].push(WHITE);
+                        thickness_x[x].push(1.0);
+                        char_x[x].push(' ');
+                        pav_x[x].push(Tile::NOTHING);
+                        continue;
+                    }
+                };
+                tint_x[x].push(configuration.get_tint(char));
+                thickness_x[x].push(configuration.get_thickness(char));
+                char_x[x].push(char);
+                if let Some(lock) = configuration.get_lock(char) {
+                    locks.insert((x as i16, y as i16), *lock);
                 }
                 if let Some(spawn) = Self::char_to_spawn(&configuration, char) {
                     let position = Position::new(x as f32 + 0.5, y as f32 + 0.5);
                     let orientation = spawn.orientation();
+                    if player.is_some() {
+                        extra_spawns.push(format!("({}, {})", x, y));
+                    }
                     player = Some(Player::new(position, orientation, configuration.player_conf()));
                     pav_x[x].push(Tile::NOTHING);
                 } else if let Some(enemy) = Self::char_to_enemy(&configuration, char) {
                     let position = Position::new(x as f32 + 0.5, y as f32 + 0.5);
-                    enemies.push(Enemy::new(enemy.texture(), enemy.texture_dead(), position, enemy.health()));
+                    enemies.push(enemy.build(position).with_origin(EnemyOrigin::Map(enemies.len())));
+                    pav_x[x].push(Tile::NOTHING)
+                } else if let Some(spawner) = configuration.get_spawner(char) {
+                    let position = Position::new(x as f32 + 0.5, y as f32 + 0.5);
+                    spawners.push(spawner.build(position));
+                    pav_x[x].push(Tile::NOTHING)
+                } else if let Some(name) = configuration.get_checkpoint(char) {
+                    let position = Position::new(x as f32 + 0.5, y as f32 + 0.5);
+                    checkpoints.push(Checkpoint::new(name.clone(), position));
+                    pav_x[x].push(Tile::NOTHING)
+                } else if let Some(target) = configuration.get_exit(char) {
+                    let position = Position::new(x as f32 + 0.5, y as f32 + 0.5);
+                    exits.push(Exit::new(target.clone(), position));
+                    pav_x[x].push(Tile::NOTHING)
+                } else if configuration.get_teleport(char).is_some() {
+                    teleport_entries.push((char, Position::new(x as f32 + 0.5, y as f32 + 0.5)));
+                    pav_x[x].push(Tile::NOTHING)
+                } else if let Some(pickup) = configuration.get_pickup(char) {
+                    let position = Position::new(x as f32 + 0.5, y as f32 + 0.5);
+                    pickups.push(pickup.build(position));
                     pav_x[x].push(Tile::NOTHING)
                 } else {
                     let tile = Self::char_to_tile(&configuration, char)?;
@@ -80,6 +242,15 @@ impl Map {
         for x in &mut pav_x {
             x.reverse();
         }
+        for x in &mut tint_x {
+            x.reverse();
+        }
+        for x in &mut thickness_x {
+            x.reverse();
+        }
+        for x in &mut char_x {
+            x.reverse();
+        }
 
         let mut current_height = 0;
         for line in &pav_x {
@@ -96,14 +267,46 @@ impl Map {
         let height: i16 = current_height;
         let width = pav_x.len() as i16;
 
+        let mut teleports = vec![];
+        for (char, position) in &teleport_entries {
+            let link = configuration.get_teleport(*char).copied().unwrap_or(*char);
+            let destination = teleport_entries.iter()
+                .find(|(other, _)| *other == link)
+                .map(|(_, destination)| *destination)
+                .ok_or_else(|| format!("Level is not valid: teleport '{}' links to '{}' which is not on the map", char, link))?;
+            teleports.push(Teleport::new(*position, destination));
+        }
+
+        if player.is_none() {
+            return Err(String::from("Level is not valid: no spawn point"));
+        }
+        if !extra_spawns.is_empty() {
+            return Err(format!("Level is not valid: extra spawn points at {}", extra_spawns.join(", ")));
+        }
+
         Ok(Self {
             paving: pav_x,
+            tints: tint_x,
+            thicknesses: thickness_x,
+            chars: char_x,
+            locks,
             border_texture: configuration.map_border_texture(),
             enemies,
+            spawners,
+            checkpoints,
+            exits,
+            teleports,
+            pickups,
             player,
             height,
             width,
-            weapon: configuration.weapon,
+            weapons: configuration.weapons,
+            metadata: configuration.metadata,
+            ambient: configuration.ambient,
+            backdrop: configuration.backdrop,
+            fog: configuration.fog,
+            ceiling_color: configuration.ceiling_color,
+            floor_color: configuration.floor_color,
         })
     }
 
@@ -115,6 +318,55 @@ impl Map {
         Some(&self.paving[x as usize][y as usize])
     }
 
+    pub fn tint_at(&self, x: i16, y: i16) -> Color {
+        if x < 0 || y < 0 || x >= self.width || y >= self.height {
+            return WHITE;
+        }
+
+        self.tints[x as usize][y as usize]
+    }
+
+    pub fn thickness_at(&self, x: i16, y: i16) -> f32 {
+        if x < 0 || y < 0 || x >= self.width || y >= self.height {
+            return 1.0;
+        }
+
+        self.thicknesses[x as usize][y as usize]
+    }
+
+    pub fn to_ascii(&self) -> String {
+        (0..self.height as usize).rev()
+            .map(|y| self.chars.iter().map(|column| column[y]).collect::<String>())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    pub fn lock_at(&self, x: i16, y: i16) -> Option<KeyColor> {
+        self.locks.get(&(x, y)).copied()
+    }
+
+    pub fn ray_tiles(&self, position: Position, angle: Angle) -> impl Iterator<Item=(MapPoint, Position)> + '_ {
+        let mut current = position;
+
+        std::iter::from_fn(move || {
+            let (tile, next) = Self::next_tile(current, angle);
+            self.paving_at(tile.x(), tile.y())?;
+            current = next;
+            Some((tile, next))
+        })
+    }
+
+    fn next_tile(position: Position, angle: Angle) -> (MapPoint, Position) {
+        let next_x_position = position.projection_x(angle);
+        let next_y_position = position.projection_y(angle);
+
+        if position.distance(&next_x_position) < position.distance(&next_y_position) {
+            (next_x_position.to_map_point(angle.cos().signum(), 0.0), next_x_position)
+        } else {
+            (next_y_position.to_map_point(0.0, angle.sin().signum()), next_y_position)
+        }
+    }
+
     fn char_to_enemy(configuration: &MapConfiguration, c: char) -> Option<&EnemyType> {
         configuration.get_enemy(c)
     }
@@ -133,12 +385,32 @@ impl Map {
         self.enemies.to_vec()
     }
 
+    pub fn generate_spawners(&self) -> Vec<Spawner> {
+        self.spawners.to_vec()
+    }
+
+    pub fn generate_pickups(&self) -> Vec<Pickup> {
+        self.pickups.to_vec()
+    }
+
+    pub fn checkpoints(&self) -> &[Checkpoint] {
+        &self.checkpoints
+    }
+
+    pub fn exits(&self) -> &[Exit] {
+        &self.exits
+    }
+
+    pub fn teleports(&self) -> &[Teleport] {
+        &self.teleports
+    }
+
     pub fn generate_player(&self) -> Option<Player> {
         self.player.clone()
     }
 
-    pub fn generate_weapon(&self) -> Weapon {
-        Weapon::new(self.weapon)
+    pub fn generate_weapons(&self) -> Vec<Weapon> {
+        self.weapons.iter().map(|weapon| Weapon::new(*weapon)).collect()
     }
 
     pub fn width(&self) -> i16 {
@@ -153,6 +425,30 @@ impl Map {
     pub fn border_texture(&self) -> TextureIndex {
         self.border_texture
     }
+
+    pub fn metadata(&self) -> &MapMetadata {
+        &self.metadata
+    }
+
+    pub fn ambient(&self) -> f32 {
+        self.ambient
+    }
+
+    pub fn backdrop(&self) -> Option<Backdrop> {
+        self.backdrop
+    }
+
+    pub fn fog(&self) -> Option<Fog> {
+        self.fog
+    }
+
+    pub fn ceiling_color(&self) -> Color {
+        self.ceiling_color
+    }
+
+    pub fn floor_color(&self) -> Color {
+        self.floor_color
+    }
 }
 
 impl MapConfiguration {
@@ -162,8 +458,24 @@ impl MapConfiguration {
             map_border_texture,
             enemies: HashMap::new(),
             spawn: HashMap::new(),
+            spawners: HashMap::new(),
+            checkpoints: HashMap::new(),
+            exits: HashMap::new(),
+            teleports: HashMap::new(),
+            pickups: HashMap::new(),
+            tints: HashMap::new(),
+            thicknesses: HashMap::new(),
+            locks: HashMap::new(),
             player_conf,
-            weapon,
+            weapons: vec![weapon],
+            metadata: MapMetadata::default(),
+            ambient: 1.0,
+            backdrop: None,
+            fog: None,
+            ceiling_color: DEFAULT_CEILING_COLOR,
+            floor_color: DEFAULT_FLOOR_COLOR,
+            pad_short_rows: false,
+            comment_prefix: None,
         }
     }
 
@@ -179,6 +491,18 @@ impl MapConfiguration {
         self.spawn.insert(c, spawn_point);
     }
 
+    pub fn add_spawner(&mut self, c: char, spawner: SpawnerType) {
+        self.spawners.insert(c, spawner);
+    }
+
+    pub fn add_lock(&mut self, c: char, key: KeyColor) {
+        self.locks.insert(c, key);
+    }
+
+    pub fn add_tint(&mut self, c: char, tint: Color) {
+        self.tints.insert(c, tint);
+    }
+
     pub fn get(&self, c: char) -> Option<&Tile> {
         self.conf.get(&c)
     }
@@ -191,6 +515,62 @@ impl MapConfiguration {
         self.spawn.get(&c)
     }
 
+    pub fn get_spawner(&self, c: char) -> Option<&SpawnerType> {
+        self.spawners.get(&c)
+    }
+
+    pub fn add_checkpoint(&mut self, c: char, name: String) {
+        self.checkpoints.insert(c, name);
+    }
+
+    pub fn get_checkpoint(&self, c: char) -> Option<&String> {
+        self.checkpoints.get(&c)
+    }
+
+    pub fn add_exit(&mut self, c: char, target: String) {
+        self.exits.insert(c, target);
+    }
+
+    pub fn get_exit(&self, c: char) -> Option<&String> {
+        self.exits.get(&c)
+    }
+
+    pub fn add_teleport(&mut self, c: char, link: char) {
+        self.teleports.insert(c, link);
+    }
+
+    pub fn get_teleport(&self, c: char) -> Option<&char> {
+        self.teleports.get(&c)
+    }
+
+    pub fn add_weapon(&mut self, weapon: WeaponConfiguration) {
+        self.weapons.push(weapon);
+    }
+
+    pub fn add_pickup(&mut self, c: char, pickup: PickupType) {
+        self.pickups.insert(c, pickup);
+    }
+
+    pub fn get_pickup(&self, c: char) -> Option<&PickupType> {
+        self.pickups.get(&c)
+    }
+
+    pub fn get_lock(&self, c: char) -> Option<&KeyColor> {
+        self.locks.get(&c)
+    }
+
+    pub fn get_tint(&self, c: char) -> Color {
+        self.tints.get(&c).copied().unwrap_or(WHITE)
+    }
+
+    pub fn add_thickness(&mut self, c: char, thickness: f32) {
+        self.thicknesses.insert(c, thickness);
+    }
+
+    pub fn get_thickness(&self, c: char) -> f32 {
+        self.thicknesses.get(&c).copied().unwrap_or(1.0)
+    }
+
     pub fn map_border_texture(&self) -> TextureIndex {
         self.map_border_texture
     }
@@ -198,6 +578,101 @@ impl MapConfiguration {
     pub fn player_conf(&self) -> PlayerStats {
         self.player_conf
     }
+
+    pub fn set_metadata(&mut self, metadata: MapMetadata) {
+        self.metadata = metadata;
+    }
+
+    pub fn set_ambient(&mut self, ambient: f32) {
+        self.ambient = ambient;
+    }
+
+    pub fn set_backdrop(&mut self, backdrop: Backdrop) {
+        self.backdrop = Some(backdrop);
+    }
+
+    pub fn set_fog(&mut self, fog: Fog) {
+        self.fog = Some(fog);
+    }
+
+    pub fn set_ceiling_color(&mut self, color: Color) {
+        self.ceiling_color = color;
+    }
+
+    pub fn set_floor_color(&mut self, color: Color) {
+        self.floor_color = color;
+    }
+
+    pub fn set_pad_short_rows(&mut self, pad_short_rows: bool) {
+        self.pad_short_rows = pad_short_rows;
+    }
+
+    pub fn set_comment_prefix(&mut self, prefix: String) {
+        self.comment_prefix = Some(prefix);
+    }
+
+    fn is_comment(&self, line: &str) -> bool {
+        self.comment_prefix.as_ref().is_some_and(|prefix| line.starts_with(prefix.as_str()))
+    }
+}
+
+impl MapMetadata {
+    pub fn new(name: Option<String>, author: Option<String>, par_time_in_seconds: Option<f32>, description: Option<String>) -> Self {
+        Self {
+            name,
+            author,
+            par_time_in_seconds,
+            description,
+        }
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn author(&self) -> Option<&str> {
+        self.author.as_deref()
+    }
+
+    pub fn par_time_in_seconds(&self) -> Option<f32> {
+        self.par_time_in_seconds
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+}
+
+impl Backdrop {
+    pub fn new(texture: TextureIndex, height: f32) -> Self {
+        Self { texture, height }
+    }
+
+    pub fn texture(&self) -> TextureIndex {
+        self.texture
+    }
+
+    pub fn height(&self) -> f32 {
+        self.height
+    }
+}
+
+impl Fog {
+    pub fn new(color: Color, distance: f32) -> Self {
+        Self { color, distance }
+    }
+
+    pub fn color(&self) -> Color {
+        self.color
+    }
+
+    pub fn distance(&self) -> f32 {
+        self.distance
+    }
+
+    pub fn shade(&self, distance: f32) -> Shade {
+        Shade::new(1.0 - between(0.0, distance / self.distance, 1.0), self.color)
+    }
 }
 
 impl EnemyType {
@@ -206,6 +681,55 @@ impl EnemyType {
             texture,
             texture_dead,
             health,
+            ranged_attack: None,
+            melee_attack: MeleeAttack::default(),
+            perception: None,
+        }
+    }
+
+    pub fn with_ranged_attack(&self, ranged_attack: RangedAttack) -> Self {
+        Self {
+            texture: self.texture,
+            texture_dead: self.texture_dead,
+            health: self.health,
+            ranged_attack: Some(ranged_attack),
+            melee_attack: self.melee_attack,
+            perception: self.perception,
+        }
+    }
+
+    pub fn with_melee_attack(&self, melee_attack: MeleeAttack) -> Self {
+        Self {
+            texture: self.texture,
+            texture_dead: self.texture_dead,
+            health: self.health,
+            ranged_attack: self.ranged_attack,
+            melee_attack,
+            perception: self.perception,
+        }
+    }
+
+    pub fn with_perception(&self, perception: Perception) -> Self {
+        Self {
+            texture: self.texture,
+            texture_dead: self.texture_dead,
+            health: self.health,
+            ranged_attack: self.ranged_attack,
+            melee_attack: self.melee_attack,
+            perception: Some(perception),
+        }
+    }
+
+    pub fn build(&self, position: Position) -> Enemy {
+        let enemy = Enemy::new(self.texture, self.texture_dead, position, self.health)
+            .with_melee_attack(self.melee_attack);
+        let enemy = match self.ranged_attack {
+            Some(ranged_attack) => enemy.with_ranged_attack(ranged_attack),
+            None => enemy,
+        };
+        match self.perception {
+            Some(perception) => enemy.with_perception(perception),
+            None => enemy,
         }
     }
 
@@ -222,6 +746,52 @@ impl EnemyType {
     }
 }
 
+impl Checkpoint {
+    pub fn new(name: String, position: Position) -> Self {
+        Self { name, position }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        position.x().floor() == self.position.x().floor() && position.y().floor() == self.position.y().floor()
+    }
+}
+
+impl Exit {
+    pub fn new(target: String, position: Position) -> Self {
+        Self { target, position }
+    }
+
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        position.x().floor() == self.position.x().floor() && position.y().floor() == self.position.y().floor()
+    }
+}
+
+impl Teleport {
+    pub fn new(position: Position, destination: Position) -> Self {
+        Self { position, destination }
+    }
+
+    pub fn destination(&self) -> Position {
+        self.destination
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        position.x().floor() == self.position.x().floor() && position.y().floor() == self.position.y().floor()
+    }
+}
+
 impl SpawnPoint {
     pub fn new(orientation: Angle) -> Self {
         Self { orientation }
@@ -232,6 +802,33 @@ impl SpawnPoint {
     }
 }
 
+impl SpawnOrientation {
+    pub fn new(mode: SpawnOrientationMode, default_in_degrees: f32) -> Self {
+        Self {
+            mode,
+            default_in_degrees,
+        }
+    }
+
+    pub fn to_angle(&self, degrees: Option<f32>) -> Result<Angle, String> {
+        let degrees = degrees.unwrap_or(self.default_in_degrees);
+        if !degrees.is_finite() || degrees.abs() > 360.0 {
+            return Err(format!("Spawn orientation is not valid: {} degrees", degrees));
+        }
+
+        match self.mode {
+            SpawnOrientationMode::Free => Ok(Angle::from_degree(degrees)),
+            SpawnOrientationMode::Snap => Ok(Angle::from_degree((degrees / 90.0).round() * 90.0)),
+        }
+    }
+}
+
+impl Default for SpawnOrientation {
+    fn default() -> Self {
+        Self::new(SpawnOrientationMode::Free, 0.0)
+    }
+}
+
 #[cfg(test)]
 pub mod map_test {
     use spectral::prelude::*;
@@ -239,26 +836,36 @@ pub mod map_test {
     use crate::domain::actors::actor::{AccelerationStats, PlayerStats, SpeedStats};
     use crate::domain::actors::shoot::{AnimationStep, WeaponConfiguration};
     use crate::domain::control::actions::{ActionStateBuilder, LinearActionState, NothingActionState};
-    use crate::domain::maths::{ANGLE_DOWN, ANGLE_LEFT, ANGLE_RIGHT, ANGLE_UP};
+    use crate::domain::maths::{Angle, ANGLE_DOWN, ANGLE_LEFT, ANGLE_RIGHT, ANGLE_UP};
+    use crate::domain::topology::coord::Position;
     use crate::domain::topology::door::LateralOpening;
     use crate::domain::topology::index::TextureIndex;
-    use crate::domain::topology::map::{EnemyType, Map, MapConfiguration, SpawnPoint, Tile};
+    use crate::domain::topology::map::{EnemyType, Map, MapConfiguration, MapMetadata, SpawnOrientation, SpawnOrientationMode, SpawnPoint, Tile};
+    use crate::domain::ui::color::Color;
 
     pub const DOOR_OPENING_SPEED_IN_UNITS_PER_SECONDS: f32 = 3.0;
 
     pub fn default_configuration() -> MapConfiguration {
+        configuration_with_weapon(default_weapon())
+    }
+
+    pub fn default_weapon() -> WeaponConfiguration {
+        let weapon_animation = AnimationStep::new(0.1, TextureIndex::new(0));
+        WeaponConfiguration::new(TextureIndex::new(0), weapon_animation, weapon_animation, weapon_animation, 30)
+    }
+
+    pub fn configuration_with_weapon(weapon_configuration: WeaponConfiguration) -> MapConfiguration {
         let door_state_builder = ActionStateBuilder::new(Box::new(LinearActionState::new(SpeedStats::new(DOOR_OPENING_SPEED_IN_UNITS_PER_SECONDS), Box::new(LateralOpening::default()))));
         let glass_state_builder = ActionStateBuilder::new(Box::new(NothingActionState::new()));
-        let weapon_animation = AnimationStep::new(0.1, TextureIndex::new(0));
-        let weapon_configuration = WeaponConfiguration::new(TextureIndex::new(0), weapon_animation, weapon_animation, weapon_animation, 30);
 
         let mut configuration = MapConfiguration::new(TextureIndex::new(0), default_stats(), weapon_configuration);
         configuration.add('#', Tile::SOLID(TextureIndex::new(1)));
         configuration.add('D', Tile::DYNAMIC(TextureIndex::new(2), TextureIndex::new(4), door_state_builder));
         configuration.add('G', Tile::DYNAMIC(TextureIndex::new(3), TextureIndex::new(4), glass_state_builder));
+        configuration.add('F', Tile::FIELD(Color::new(0, 0, 255)));
         configuration.add(' ', Tile::NOTHING);
 
-        configuration.add_enemy('E', EnemyType::new(TextureIndex::new(5), TextureIndex::new(5), 150));
+        configuration.add_enemy('E', EnemyType::new(TextureIndex::new(5), TextureIndex::new(7), 150));
 
         configuration.add_spawn('u', SpawnPoint::new(ANGLE_UP));
         configuration.add_spawn('d', SpawnPoint::new(ANGLE_DOWN));
@@ -269,7 +876,11 @@ pub mod map_test {
     }
 
     pub fn build_map(paving: &str) -> Map {
-        Map::new(paving, default_configuration()).unwrap()
+        build_map_with_configuration(paving, default_configuration())
+    }
+
+    pub fn build_map_with_configuration(paving: &str, configuration: MapConfiguration) -> Map {
+        Map::new(paving, configuration).unwrap()
     }
 
     pub fn default_stats() -> PlayerStats {
@@ -279,9 +890,93 @@ pub mod map_test {
         PlayerStats::new(acceleration, deceleration, max_speed)
     }
 
+    #[test]
+    fn map_without_spawn_should_be_rejected() {
+        let map = Map::new("#  #", default_configuration());
+
+        assert_that!(map.err()).is_some().is_equal_to(String::from("Level is not valid: no spawn point"));
+    }
+
+    #[test]
+    fn map_with_several_spawns_should_name_the_extra_ones() {
+        let map = Map::new("#r ##\n#u d#", default_configuration());
+
+        assert_that!(map.err()).is_some().is_equal_to(String::from("Level is not valid: extra spawn points at (1, 0), (3, 0)"));
+    }
+
+    #[test]
+    fn short_rows_should_be_rejected_by_default() {
+        let map = Map::new("###\n#r\n###", default_configuration());
+
+        assert_that!(map.err()).is_some().is_equal_to(String::from("Level is not valid: number of column is not consistent in every lines"));
+    }
+
+    #[test]
+    fn short_rows_should_be_padded_with_nothing_when_enabled() {
+        let mut configuration = default_configuration();
+        configuration.set_pad_short_rows(true);
+
+        let map = build_map_with_configuration("####\n#r\n##", configuration);
+
+        assert_that!(map.width()).is_equal_to(4);
+        assert_that!(map.height()).is_equal_to(3);
+        assert!(matches!(map.paving_at(2, 1), Some(Tile::NOTHING)));
+        assert!(matches!(map.paving_at(3, 1), Some(Tile::NOTHING)));
+        assert!(matches!(map.paving_at(1, 0), Some(Tile::SOLID(_))));
+        assert!(matches!(map.paving_at(2, 0), Some(Tile::NOTHING)));
+        assert!(matches!(map.paving_at(3, 2), Some(Tile::SOLID(_))));
+    }
+
+    #[test]
+    fn comment_lines_should_not_change_dimensions_or_positions() {
+        let mut configuration = default_configuration();
+        configuration.set_comment_prefix(String::from(";"));
+
+        let map = build_map_with_configuration("; north wall\n####\n#r #\n; south wall\n####", configuration);
+        let player = map.generate_player().unwrap();
+
+        assert_that!(map.width()).is_equal_to(4);
+        assert_that!(map.height()).is_equal_to(3);
+        assert_that!((player.position().x(), player.position().y())).is_equal_to((1.5, 1.5));
+        assert!(matches!(map.paving_at(2, 1), Some(Tile::NOTHING)));
+    }
+
+    #[test]
+    fn trailing_empty_lines_should_be_ignored() {
+        let map = build_map("####\n#r #\n####\n\n");
+
+        assert_that!(map.height()).is_equal_to(3);
+        assert!(matches!(map.paving_at(1, 0), Some(Tile::SOLID(_))));
+    }
+
+    #[test]
+    fn teleport_should_lead_to_its_linked_tile() {
+        let mut configuration = default_configuration();
+        configuration.add_teleport('A', 'B');
+        configuration.add_teleport('B', 'A');
+
+        let map = build_map_with_configuration("#rA B#", configuration);
+        let destinations: Vec<(f32, f32)> = map.teleports().iter()
+            .map(|teleport| (teleport.destination().x(), teleport.destination().y()))
+            .collect();
+
+        assert_that!(destinations).is_equal_to(vec![(4.5, 0.5), (2.5, 0.5)]);
+    }
+
+    #[test]
+    fn teleport_linked_to_a_missing_tile_should_be_rejected() {
+        let mut configuration = default_configuration();
+        configuration.add_teleport('A', 'B');
+        configuration.add_teleport('B', 'A');
+
+        let map = Map::new("#rA  #", configuration);
+
+        assert_that!(map.err()).is_some().is_equal_to(String::from("Level is not valid: teleport 'A' links to 'B' which is not on the map"));
+    }
+
     #[test]
     fn should_read_paving_information() {
-        let paving = String::from("###\n# #\n# #\n###");
+        let paving = String::from("###\n#r#\n# #\n###");
         let map = build_map(&paving);
 
         assert!(matches!(&map.paving_at(0, 0), Some(Tile::SOLID(_))));
@@ -301,30 +996,38 @@ pub mod map_test {
         assert!(matches!(&map.paving_at(2, 3), Some(Tile::SOLID(_))));
     }
 
+    #[test]
+    fn to_ascii_should_reproduce_the_original_grid() {
+        let paving = "#####\n#r E#\n#D G#\n#####";
+        let map = build_map(paving);
+
+        assert_that!(map.to_ascii()).is_equal_to(String::from(paving));
+    }
+
     #[test]
     fn should_not_get_paving_information_on_tiles_with_x_coordinate_bigger_than_width_map() {
-        let map = build_map("  \n  ");
+        let map = build_map("r \n  ");
         let tile = map.paving_at(0, 2);
         assert!(matches!(tile, None));
     }
 
     #[test]
     fn should_not_get_paving_information_on_tiles_with_x_coordinate_bigger_than_height_map() {
-        let map = build_map("  \n  ");
+        let map = build_map("r \n  ");
         let tile = map.paving_at(2, 0);
         assert!(matches!(tile, None));
     }
 
     #[test]
     fn should_not_get_paving_information_on_tiles_with_negative_x_coordinate() {
-        let map = build_map("  \n  ");
+        let map = build_map("r \n  ");
         let tile = map.paving_at(-1, 0);
         assert!(matches!(tile, None));
     }
 
     #[test]
     fn should_not_get_paving_information_on_tiles_with_negative_y_coordinate() {
-        let map = build_map("  \n  ");
+        let map = build_map("r \n  ");
         let tile = map.paving_at(0, -1);
         assert!(matches!(tile, None));
     }
@@ -340,4 +1043,103 @@ pub mod map_test {
         let map = Map::new("#k\n #", default_configuration());
         assert_that!(map.err()).is_some();
     }
+
+    #[test]
+    fn ray_should_visit_every_tile_in_a_straight_line() {
+        let map = build_map("r    ");
+
+        let tiles: Vec<(i16, i16)> = map.ray_tiles(Position::new(0.5, 0.5), ANGLE_RIGHT)
+            .map(|(tile, _)| (tile.x(), tile.y()))
+            .collect();
+
+        assert_that!(tiles).is_equal_to(vec![(1, 0), (2, 0), (3, 0), (4, 0)]);
+    }
+
+    #[test]
+    fn ray_should_visit_tiles_in_order_on_a_diagonal() {
+        let map = build_map("r  \n   ");
+
+        let tiles: Vec<(i16, i16)> = map.ray_tiles(Position::new(0.5, 0.5), Angle::new(0.3))
+            .map(|(tile, _)| (tile.x(), tile.y()))
+            .collect();
+
+        assert_that!(tiles).is_equal_to(vec![(1, 0), (2, 0), (2, 1)]);
+    }
+
+    #[test]
+    fn ray_should_give_entry_point_of_each_tile() {
+        let map = build_map("r  ");
+
+        let points: Vec<f32> = map.ray_tiles(Position::new(0.5, 0.5), ANGLE_RIGHT)
+            .map(|(_, entry)| entry.x())
+            .collect();
+
+        assert_that!(points).is_equal_to(vec![1.0, 2.0]);
+    }
+
+    #[test]
+    fn spawn_orientation_should_default_to_configured_value_when_missing() {
+        let orientation = SpawnOrientation::new(SpawnOrientationMode::Free, 180.0);
+
+        let angle = orientation.to_angle(None);
+
+        assert_that!(angle.map(|a| a.to_radiant())).is_ok().is_close_to(ANGLE_LEFT.to_radiant(), 0.001);
+    }
+
+    #[test]
+    fn spawn_orientation_should_keep_angle_in_free_mode() {
+        let orientation = SpawnOrientation::new(SpawnOrientationMode::Free, 0.0);
+
+        let angle = orientation.to_angle(Some(100.0));
+
+        assert_that!(angle.map(|a| a.to_radiant())).is_ok().is_close_to(100.0_f32.to_radians(), 0.001);
+    }
+
+    #[test]
+    fn spawn_orientation_should_snap_to_closest_right_angle() {
+        let orientation = SpawnOrientation::new(SpawnOrientationMode::Snap, 0.0);
+
+        let angle = orientation.to_angle(Some(100.0));
+
+        assert_that!(angle.map(|a| a.to_radiant())).is_ok().is_close_to(ANGLE_UP.to_radiant(), 0.001);
+    }
+
+    #[test]
+    fn spawn_orientation_should_not_accept_nan() {
+        let orientation = SpawnOrientation::default();
+
+        let angle = orientation.to_angle(Some(f32::NAN));
+
+        assert_that!(angle.err()).is_some();
+    }
+
+    #[test]
+    fn spawn_orientation_should_not_accept_absurd_value() {
+        let orientation = SpawnOrientation::default();
+
+        let angle = orientation.to_angle(Some(123456.0));
+
+        assert_that!(angle.err()).is_some();
+    }
+
+    #[test]
+    fn map_with_metadata_should_expose_it() {
+        let mut configuration = default_configuration();
+        configuration.set_metadata(MapMetadata::new(Some(String::from("Castle")), Some(String::from("ngirot")), Some(90.0), None));
+
+        let map = Map::new("#r#", configuration).unwrap();
+
+        assert_that!(map.metadata().name()).is_some().is_equal_to("Castle");
+        assert_that!(map.metadata().author()).is_some().is_equal_to("ngirot");
+        assert_that!(map.metadata().par_time_in_seconds()).is_some().is_equal_to(90.0);
+        assert_that!(map.metadata().description()).is_none();
+    }
+
+    #[test]
+    fn map_without_metadata_should_have_none() {
+        let map = build_map("#r#");
+
+        assert_that!(map.metadata().name()).is_none();
+        assert_that!(map.metadata().par_time_in_seconds()).is_none();
+    }
 }
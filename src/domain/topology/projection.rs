@@ -1,9 +1,10 @@
 use crate::domain::control::actions::{Actions, ActionState};
-use crate::domain::maths::{Angle, ANGLE_240, decimal_part};
+use crate::domain::maths::{Angle, ANGLE_240, between, decimal_part};
 use crate::domain::topology::coord::{MapPoint, Position};
 use crate::domain::topology::door::Openable;
 use crate::domain::topology::index::TextureIndex;
 use crate::domain::topology::map::Tile;
+use crate::domain::ui::color::{Color, WHITE};
 
 
 use super::map::Map;
@@ -16,6 +17,16 @@ pub struct ProjectedPoint {
     offset_in_bloc: f32,
     map_point: MapPoint,
     texture: TextureIndex,
+    tint: Color,
+    face: WallFace,
+    clip: f32,
+}
+
+// Vertical faces are crossed along x (east and west), horizontal ones along y
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum WallFace {
+    Vertical,
+    Horizontal,
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -25,6 +36,9 @@ struct Projection {
     offset_in_bloc: f32,
     map_point: MapPoint,
     texture: TextureIndex,
+    tint: Color,
+    face: WallFace,
+    clip: f32,
 }
 
 pub fn project(position: Position, angle: Angle, map: &Map, actions: &Actions) -> Vec<ProjectedPoint> {
@@ -64,34 +78,85 @@ fn projection_concat(position: Position, angle: Angle, map: &Map, actions: &Acti
         next_position = next_y_position
     };
 
+    let face = if door_up { WallFace::Horizontal } else { WallFace::Vertical };
     let bloc_tile = map.paving_at(bloc.x(), bloc.y());
 
     let recursive = match bloc_tile {
         None =>
-            vec![Projection::new(next_position, position_on_texture, true, bloc, map.border_texture())],
-        Some(Tile::SOLID(texture)) =>
-            vec![Projection::new(next_position, position_on_texture, true, bloc, *texture)],
+            vec![Projection::new(next_position, position_on_texture, true, bloc, map.border_texture(), face)],
+        Some(Tile::SOLID(texture)) => {
+            let thickness = map.thickness_at(bloc.x(), bloc.y());
+            if thickness >= 1.0 {
+                vec![Projection::new(next_position, position_on_texture, true, bloc, *texture, face).with_tint(map.tint_at(bloc.x(), bloc.y()))]
+            } else {
+                match projection_on_thin_wall(next_position, angle, bloc, thickness) {
+                    Some((hit_position, offset, face)) =>
+                        vec![Projection::new(hit_position, offset, true, bloc, *texture, face).with_tint(map.tint_at(bloc.x(), bloc.y()))],
+                    None => inner_projection(next_position, angle, map, actions),
+                }
+            }
+        }
         Some(Tile::DYNAMIC(texture_inside, texture_outside, _)) => {
             let projection_inside = projection_on_door(angle, map, actions, next_position, position_on_texture, door_up, bloc, *texture_inside, *texture_outside);
             let projection_behind = inner_projection(next_position, angle, map, actions);
             [projection_inside, projection_behind].concat()
         }
+        Some(Tile::FIELD(color)) => inner_projection(next_position, angle, map, actions)
+            .iter()
+            .map(|projection| projection.with_tint(projection.tint.multiply(*color)))
+            .collect(),
         Some(Tile::NOTHING) => inner_projection(next_position, angle, map, actions)
     };
 
     [previous, recursive].concat()
 }
 
+fn projection_on_thin_wall(position: Position, angle: Angle, bloc: MapPoint, thickness: f32) -> Option<(Position, f32, WallFace)> {
+    let half = thickness / 2.0;
+    let (min_x, max_x) = (bloc.x() as f32 + 0.5 - half, bloc.x() as f32 + 0.5 + half);
+    let (min_y, max_y) = (bloc.y() as f32 + 0.5 - half, bloc.y() as f32 + 0.5 + half);
+
+    let (enter_x, exit_x) = slab(position.x(), angle.cos(), min_x, max_x)?;
+    let (enter_y, exit_y) = slab(position.y(), angle.sin(), min_y, max_y)?;
+
+    let enter = enter_x.max(enter_y).max(0.0);
+    if enter > exit_x.min(exit_y) {
+        return None;
+    }
+
+    let hit = position.with_x(position.x() + angle.cos() * enter)
+        .with_y(position.y() + angle.sin() * enter);
+    let (offset, face) = if enter_x > enter_y {
+        ((hit.y() - min_y) / thickness, WallFace::Vertical)
+    } else {
+        ((hit.x() - min_x) / thickness, WallFace::Horizontal)
+    };
+
+    Some((hit, between(0.0, offset, 1.0), face))
+}
+
+fn slab(origin: f32, direction: f32, min: f32, max: f32) -> Option<(f32, f32)> {
+    if direction.abs() < f32::EPSILON {
+        return if origin >= min && origin <= max { Some((f32::NEG_INFINITY, f32::INFINITY)) } else { None };
+    }
+
+    let a = (min - origin) / direction;
+    let b = (max - origin) / direction;
+    Some((a.min(b), a.max(b)))
+}
+
 fn projection_on_door(angle: Angle, map: &Map, actions: &Actions, next_position: Position, position_on_texture: f32, door_up: bool, map_point: MapPoint, texture: TextureIndex, blocking_texture: TextureIndex) -> Vec<Projection> {
     let action_state = actions.state_at(map_point.x(), map_point.y()).unwrap();
-    let blocking = action_state.activated_percentage() != 1.0;
+    let blocking = action_state.blocking();
+    let tint = map.tint_at(map_point.x(), map_point.y());
 
-    let invisible_wall = vec![Projection::new(next_position, position_on_texture, blocking, map_point, blocking_texture)];
+    let face = if door_up { WallFace::Horizontal } else { WallFace::Vertical };
+    let invisible_wall = vec![Projection::new(next_position, position_on_texture, blocking, map_point, blocking_texture, face).with_tint(tint)];
 
     let actual_door = inner_door_projection(next_position, angle, door_up, map_point, texture, action_state)
         .map_or_else(
             || inner_projection(next_position, angle, map, actions),
-            |d| vec![d],
+            |d| vec![d.with_tint(tint)],
         );
 
     [invisible_wall, actual_door].concat()
@@ -101,6 +166,7 @@ fn inner_door_projection(current_position: Position, angle: Angle, door_up: bool
     let opening_percentage = action_state.activated_percentage();
     let position_inside_tile = 0.5;
     let openable = action_state.openable();
+    let clip = openable.vertical_clip(opening_percentage);
 
     if door_up {
         let a = angle.add(ANGLE_240).tan();
@@ -111,8 +177,7 @@ fn inner_door_projection(current_position: Position, angle: Angle, door_up: bool
             .with_y(current_position.y() + position_inside_tile * angle_sign);
 
         position_on_texture_inside_tile(&openable, door_x, current_position.x(), opening_percentage)
-            .map(|position_on_texture| Projection::new(new_position, position_on_texture, true, map_point, texture),
-            )
+            .map(|position_on_texture| Projection::new(new_position, position_on_texture, true, map_point, texture, WallFace::Horizontal).with_clip(clip))
     } else {
         let a = angle.tan();
         let angle_sign = angle.cos().signum();
@@ -122,7 +187,7 @@ fn inner_door_projection(current_position: Position, angle: Angle, door_up: bool
             .with_y(door_y);
 
         position_on_texture_inside_tile(&openable, door_y, current_position.y(), opening_percentage)
-            .map(|position_on_texture| Projection::new(new_position, position_on_texture, true, map_point, texture))
+            .map(|position_on_texture| Projection::new(new_position, position_on_texture, true, map_point, texture, WallFace::Vertical).with_clip(clip))
     }
 }
 
@@ -149,6 +214,9 @@ impl ProjectedPoint {
             offset_in_bloc: projection.offset_in_bloc,
             map_point: projection.map_point,
             texture: projection.texture,
+            tint: projection.tint,
+            face: projection.face,
+            clip: projection.clip,
         }
     }
 
@@ -172,6 +240,17 @@ impl ProjectedPoint {
         self.texture
     }
 
+    pub fn tint(&self) -> Color {
+        self.tint
+    }
+
+    pub fn face(&self) -> WallFace {
+        self.face
+    }
+
+    pub fn clip(&self) -> f32 {
+        self.clip
+    }
 
     pub fn blocking(&self) -> bool {
         self.blocking
@@ -179,13 +258,42 @@ impl ProjectedPoint {
 }
 
 impl Projection {
-    pub fn new(projected_point: Position, offset_in_bloc: f32, blocking: bool, map_point: MapPoint, texture: TextureIndex) -> Self {
+    pub fn new(projected_point: Position, offset_in_bloc: f32, blocking: bool, map_point: MapPoint, texture: TextureIndex, face: WallFace) -> Self {
         Self {
             projected_point,
             blocking,
             offset_in_bloc,
             texture,
             map_point,
+            tint: WHITE,
+            face,
+            clip: 0.0,
+        }
+    }
+
+    fn with_tint(&self, tint: Color) -> Self {
+        Self {
+            projected_point: self.projected_point,
+            blocking: self.blocking,
+            offset_in_bloc: self.offset_in_bloc,
+            texture: self.texture,
+            map_point: self.map_point,
+            tint,
+            face: self.face,
+            clip: self.clip,
+        }
+    }
+
+    fn with_clip(&self, clip: f32) -> Self {
+        Self {
+            projected_point: self.projected_point,
+            blocking: self.blocking,
+            offset_in_bloc: self.offset_in_bloc,
+            texture: self.texture,
+            map_point: self.map_point,
+            tint: self.tint,
+            face: self.face,
+            clip,
         }
     }
 }
@@ -197,13 +305,16 @@ mod project_test {
 
     use spectral::prelude::*;
 
-    use crate::domain::control::actions::Actions;
+    use crate::domain::actors::actor::SpeedStats;
+    use crate::domain::control::actions::{ActionStateBuilder, Actions, LinearActionState};
     use crate::domain::maths::{Angle, ANGLE_DOWN, ANGLE_LEFT, ANGLE_RIGHT, ANGLE_UP};
     use crate::domain::topology::coord::Position;
+    use crate::domain::topology::door::LateralOpening;
     use crate::domain::topology::index::TextureIndex;
-    use crate::domain::topology::map::Map;
-    use crate::domain::topology::map::map_test::{build_map, DOOR_OPENING_SPEED_IN_UNITS_PER_SECONDS};
-    use crate::domain::topology::projection::ProjectedPoint;
+    use crate::domain::topology::map::{Map, Tile};
+    use crate::domain::topology::map::map_test::{build_map, build_map_with_configuration, default_configuration, DOOR_OPENING_SPEED_IN_UNITS_PER_SECONDS};
+    use crate::domain::topology::projection::{ProjectedPoint, WallFace};
+    use crate::domain::ui::color::{Color, WHITE};
 
     use super::project;
 
@@ -219,7 +330,7 @@ mod project_test {
         let map = build_map(
             "\
             #####\n\
-            # # #\n\
+            #r# #\n\
             #   #\n\
             #   #\n\
             #####");
@@ -234,7 +345,7 @@ mod project_test {
         let map = build_map(
             "\
             #####\n\
-            # # #\n\
+            #r# #\n\
             #   #\n\
             #   #\n\
             #####");
@@ -249,7 +360,7 @@ mod project_test {
         let map = build_map(
             "\
             #####\n\
-            #   #\n\
+            #r  #\n\
             #   #\n\
             # # #\n\
             #####");
@@ -264,7 +375,7 @@ mod project_test {
         let map = build_map(
             "\
             #####\n\
-            #   #\n\
+            #r  #\n\
             #   #\n\
             # # #\n\
             #####");
@@ -279,7 +390,7 @@ mod project_test {
         let map = build_map(
             "\
             #####\n\
-            #   #\n\
+            #r  #\n\
             ##  #\n\
             #   #\n\
             #####");
@@ -294,7 +405,7 @@ mod project_test {
         let map = build_map(
             "\
             #####\n\
-            #   #\n\
+            #r  #\n\
             ##  #\n\
             #   #\n\
             #####");
@@ -309,7 +420,7 @@ mod project_test {
         let map = build_map(
             "\
             #####\n\
-            #   #\n\
+            #r  #\n\
             #  ##\n\
             #   #\n\
             #####");
@@ -324,7 +435,7 @@ mod project_test {
         let map = build_map(
             "\
             #####\n\
-            #   #\n\
+            #r  #\n\
             #  ##\n\
             #   #\n\
             #####");
@@ -339,7 +450,7 @@ mod project_test {
         let map = build_map(
             "\
             #####\n\
-            #  ##\n\
+            #r ##\n\
             #   #\n\
             #   #\n\
             #####");
@@ -354,7 +465,7 @@ mod project_test {
         let map = build_map(
             "\
             #####\n\
-            ##  #\n\
+            ##r #\n\
             #   #\n\
             #   #\n\
             #####");
@@ -369,7 +480,7 @@ mod project_test {
         let map = build_map(
             "\
             #####\n\
-            #   #\n\
+            #r  #\n\
             #   #\n\
             #  ##\n\
             #####");
@@ -384,7 +495,7 @@ mod project_test {
         let map = build_map(
             "\
             #####\n\
-            #   #\n\
+            #r  #\n\
             #   #\n\
             ##  #\n\
             #####");
@@ -399,7 +510,7 @@ mod project_test {
         let map = build_map(
             "\
             #####\n\
-            #    \n\
+            #r   \n\
             #####");
         let center = Position::new(1.5, 1.5);
         let projected = project_single_wall(center, ANGLE_RIGHT, &map, &Actions::new(&map));
@@ -413,7 +524,7 @@ mod project_test {
         let map = build_map(
             "\
             #####\n\
-            # # #\n\
+            #r# #\n\
             #   #\n\
             #   #\n\
             #####");
@@ -428,7 +539,7 @@ mod project_test {
         let map = build_map(
             "\
             #####\n\
-            # # #\n\
+            #r# #\n\
             #   #\n\
             #   #\n\
             #####");
@@ -443,7 +554,7 @@ mod project_test {
         let map = build_map(
             "\
             ##D##\n\
-            #   #\n\
+            #r  #\n\
             #   #\n\
             #####");
         let center = Position::new(2.5, 2.5);
@@ -458,7 +569,7 @@ mod project_test {
         let map = build_map(
             "\
             #####\n\
-            #   #\n\
+            #r  #\n\
             #   #\n\
             ##D##");
         let center = Position::new(2.5, 2.5);
@@ -473,7 +584,7 @@ mod project_test {
         let map = build_map(
             "\
             ####\n\
-            D  #\n\
+            Dr #\n\
             ####");
         let center = Position::new(2.0, 1.1);
 
@@ -488,7 +599,7 @@ mod project_test {
         let map = build_map(
             "\
             ####\n\
-            #  D\n\
+            #r D\n\
             ####");
         let center = Position::new(2.0, 1.1);
         let projected = project(center, ANGLE_RIGHT, &map, &Actions::new(&map));
@@ -502,7 +613,7 @@ mod project_test {
         let map = build_map(
             "\
             ####\n\
-            #  D\n\
+            #r D\n\
             ####");
         let center = Position::new(2.0, 1.1);
         let projected = project(center, ANGLE_RIGHT, &map, &Actions::new(&map));
@@ -516,7 +627,7 @@ mod project_test {
         let map = build_map(
             "\
             #####\n\
-            #  D#\n\
+            #r D#\n\
             #####");
         let center = Position::new(2.0, 1.1);
         let projected = project(center, ANGLE_RIGHT, &map, &Actions::new(&map));
@@ -530,12 +641,12 @@ mod project_test {
         let door_map = build_map(
             "\
             ##D##\n\
-            #   #\n\
+            #r  #\n\
             #####");
 
         let no_door_map = build_map(
             "\
-            ## ##\n\
+            ##r##\n\
             #   #\n\
             #####");
 
@@ -547,9 +658,56 @@ mod project_test {
         assert_that!(projected_door[1].distance()).is_equal_to(&projected_no_door.distance());
     }
 
+    #[test]
+    fn field_should_tint_walls_behind_it() {
+        let map = build_map("r F #");
+        let position = Position::new(0.5, 0.5);
+
+        let projected = project_single_wall(position, ANGLE_RIGHT, &map, &Actions::new(&map));
+
+        assert_that!(projected.tint()).is_equal_to(Color::new(0, 0, 255));
+        assert_that!(projected.distance()).is_close_to(3.5, 0.001);
+    }
+
+    #[test]
+    fn wall_without_field_should_not_be_tinted() {
+        let map = build_map("r   #");
+        let position = Position::new(0.5, 0.5);
+
+        let projected = project_single_wall(position, ANGLE_RIGHT, &map, &Actions::new(&map));
+
+        assert_that!(projected.tint()).is_equal_to(WHITE);
+    }
+
+    #[test]
+    fn palette_tint_should_be_combined_with_field_tint() {
+        let mut configuration = default_configuration();
+        configuration.add_tint('#', Color::new(255, 128, 128));
+        let map = build_map_with_configuration("r F #", configuration);
+        let position = Position::new(0.5, 0.5);
+
+        let projected = project_single_wall(position, ANGLE_RIGHT, &map, &Actions::new(&map));
+
+        assert_that!(projected.tint()).is_equal_to(Color::new(0, 0, 128));
+    }
+
+    #[test]
+    fn palette_tint_should_apply_on_doors() {
+        let mut configuration = default_configuration();
+        configuration.add_tint('D', Color::new(10, 20, 30));
+        let map = build_map_with_configuration("rD #", configuration);
+        let position = Position::new(0.5, 0.5);
+
+        let projected = project(position, ANGLE_RIGHT, &map, &Actions::new(&map));
+
+        assert_that!(projected[0].tint()).is_equal_to(Color::new(10, 20, 30));
+        assert_that!(projected[1].tint()).is_equal_to(Color::new(10, 20, 30));
+        assert_that!(projected[projected.len() - 1].tint()).is_equal_to(WHITE);
+    }
+
     #[test]
     fn closed_door_should_be_blocking() {
-        let map = build_map(" D    ");
+        let map = build_map("rD    ");
         let position = Position::new(0.5, 0.5);
         let actions = Actions::new(&map);
 
@@ -560,7 +718,7 @@ mod project_test {
 
     #[test]
     fn open_door_should_not_be_blocking() {
-        let map = build_map(" D    ");
+        let map = build_map("rD    ");
         let position = Position::new(0.5, 0.5);
         let mut actions = Actions::new(&map);
         actions.activate(1, 0);
@@ -572,7 +730,7 @@ mod project_test {
 
     #[test]
     fn half_open_door_should_be_blocking() {
-        let map = build_map(" D    ");
+        let map = build_map("rD    ");
         let position = Position::new(0.5, 0.5);
         let mut actions = Actions::new(&map);
         actions.activate(1, 0);
@@ -581,6 +739,61 @@ mod project_test {
         let projected = project(position, ANGLE_RIGHT, &map, &actions);
         assert_that!(projected[0].blocking()).is_true();
     }
+
+    #[test]
+    fn thin_wall_should_be_hit_at_its_centered_surface() {
+        let full = project_single_wall(Position::new(0.5, 0.5), ANGLE_RIGHT, &map_with_thickness(1.0), &Actions::new(&map_with_thickness(1.0)));
+        let thin = project_single_wall(Position::new(0.5, 0.5), ANGLE_RIGHT, &map_with_thickness(0.5), &Actions::new(&map_with_thickness(0.5)));
+
+        assert_that!(full.distance()).is_close_to(0.5, 0.001);
+        assert_that!(thin.distance()).is_close_to(0.75, 0.001);
+    }
+
+    #[test]
+    fn ray_beside_a_thin_wall_should_go_through_its_cell() {
+        let map = map_with_thickness(0.5);
+
+        let projected = project_single_wall(Position::new(0.5, 0.1), ANGLE_RIGHT, &map, &Actions::new(&map));
+
+        assert_that!(projected.distance()).is_close_to(2.5, 0.001);
+    }
+
+    fn map_with_thickness(thickness: f32) -> Map {
+        let mut configuration = default_configuration();
+        configuration.add('p', Tile::SOLID(TextureIndex::new(1)));
+        configuration.add_thickness('p', thickness);
+
+        build_map_with_configuration("rp #", configuration)
+    }
+
+    #[test]
+    fn nearly_open_door_should_not_be_blocking_above_its_passable_threshold() {
+        assert_that!(door_blocking_when_opened_at(0.85)).is_false();
+        assert_that!(door_blocking_when_opened_at(0.75)).is_true();
+    }
+
+    fn door_blocking_when_opened_at(opening_percentage: f32) -> bool {
+        let door = LinearActionState::new(SpeedStats::new(DOOR_OPENING_SPEED_IN_UNITS_PER_SECONDS), Box::new(LateralOpening::default()))
+            .with_passable_threshold(0.8);
+        let mut configuration = default_configuration();
+        configuration.add('P', Tile::DYNAMIC(TextureIndex::new(2), TextureIndex::new(4), ActionStateBuilder::new(Box::new(door))));
+        let map = build_map_with_configuration("rP    ", configuration);
+        let mut actions = Actions::new(&map);
+        actions.activate(1, 0);
+        actions.notify_elapsed(((1000000.0 * opening_percentage) / DOOR_OPENING_SPEED_IN_UNITS_PER_SECONDS) as u128);
+
+        project(Position::new(0.5, 0.5), ANGLE_RIGHT, &map, &actions)[0].blocking()
+    }
+
+    #[test]
+    fn walls_crossed_along_x_and_y_should_have_different_faces() {
+        let map = build_map("#####\n#r  #\n#   #\n#   #\n#####");
+        let center = Position::new(2.5, 2.5);
+        let actions = Actions::new(&map);
+
+        assert_that!(project_single_wall(center, ANGLE_RIGHT, &map, &actions).face()).is_equal_to(WallFace::Vertical);
+        assert_that!(project_single_wall(center, ANGLE_UP, &map, &actions).face()).is_equal_to(WallFace::Horizontal);
+    }
 }
 
 #[cfg(test)]
@@ -590,7 +803,7 @@ mod projected_point_test {
     use crate::domain::maths::{ANGLE_RIGHT, ANGLE_UP};
     use crate::domain::topology::coord::{MapPoint, Position};
     use crate::domain::topology::index::TextureIndex;
-    use crate::domain::topology::projection::{ProjectedPoint, Projection};
+    use crate::domain::topology::projection::{ProjectedPoint, Projection, WallFace};
 
     #[test]
     fn distance_fisheye_should_be_the_same_as_regular_distance_if_angle_is_the_same() {
@@ -626,6 +839,7 @@ mod projected_point_test {
                 true,
                 MapPoint::new(0, 0),
                 TextureIndex::new(0),
+                WallFace::Vertical,
             ),
         )
     }
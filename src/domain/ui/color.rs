@@ -1,4 +1,6 @@
-#[derive(Clone)]
+pub const WHITE: Color = Color::new(255, 255, 255);
+
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Color {
     red: u8,
     green: u8,
@@ -6,7 +8,7 @@ pub struct Color {
 }
 
 impl Color {
-    pub fn new(red: u8, green: u8, blue: u8) -> Self {
+    pub const fn new(red: u8, green: u8, blue: u8) -> Self {
         Self { red, green, blue }
     }
 
@@ -21,4 +23,58 @@ impl Color {
     pub fn blue(&self) -> u8 {
         self.blue
     }
+
+    pub fn multiply(&self, other: Color) -> Self {
+        Self {
+            red: Self::multiply_channel(self.red, other.red),
+            green: Self::multiply_channel(self.green, other.green),
+            blue: Self::multiply_channel(self.blue, other.blue),
+        }
+    }
+
+    pub fn lerp(&self, other: Color, ratio: f32) -> Self {
+        let channel = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * ratio).round() as u8;
+        Self {
+            red: channel(self.red, other.red),
+            green: channel(self.green, other.green),
+            blue: channel(self.blue, other.blue),
+        }
+    }
+
+    pub fn scale(&self, factor: f32) -> Self {
+        Self {
+            red: Self::scale_channel(self.red, factor),
+            green: Self::scale_channel(self.green, factor),
+            blue: Self::scale_channel(self.blue, factor),
+        }
+    }
+
+    fn scale_channel(channel: u8, factor: f32) -> u8 {
+        (channel as f32 * factor).clamp(0.0, 255.0) as u8
+    }
+
+    fn multiply_channel(a: u8, b: u8) -> u8 {
+        (a as u16 * b as u16 / 255) as u8
+    }
+}
+
+#[cfg(test)]
+mod color_test {
+    use spectral::prelude::*;
+
+    use crate::domain::ui::color::{Color, WHITE};
+
+    #[test]
+    fn multiply_by_white_should_keep_color() {
+        let color = Color::new(12, 34, 56);
+
+        assert_that!(color.multiply(WHITE)).is_equal_to(color);
+    }
+
+    #[test]
+    fn multiply_should_combine_each_channel() {
+        let color = Color::new(255, 128, 0).multiply(Color::new(128, 255, 255));
+
+        assert_that!(color).is_equal_to(Color::new(128, 128, 0));
+    }
 }
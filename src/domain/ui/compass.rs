@@ -0,0 +1,110 @@
+use std::f32::consts::PI;
+
+use crate::domain::maths::Angle;
+use crate::domain::topology::coord::ScreenPoint;
+use crate::domain::topology::index::FontIndex;
+use crate::domain::ui::color::{Color, WHITE};
+use crate::domain::ui::draw_action::DrawAction;
+use crate::domain::ui::view::ViewScreen;
+
+const COMPASS_MARGIN: i32 = 5;
+const COMPASS_HALF_WIDTH: i32 = 40;
+const COMPASS_HEIGHT: i32 = 25;
+const CARDINALS: [&str; 4] = ["N", "E", "S", "W"];
+
+#[derive(Copy, Clone)]
+pub struct Compass {
+    font: FontIndex,
+    color: Color,
+    visible: bool,
+}
+
+impl Compass {
+    pub fn new(font: FontIndex) -> Self {
+        Self {
+            font,
+            color: WHITE,
+            visible: false,
+        }
+    }
+
+    pub fn toggle(&self) -> Self {
+        Self {
+            font: self.font,
+            color: self.color,
+            visible: !self.visible,
+        }
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    pub fn generate_actions(&self, view: ViewScreen, orientation: Angle) -> Vec<DrawAction> {
+        if !self.visible {
+            return vec![];
+        }
+
+        let (cardinal, degrees) = heading(orientation);
+        let center_x = view.width() / 2;
+
+        vec![
+            DrawAction::Text(
+                format!("{} {}", cardinal, degrees),
+                ScreenPoint::new(center_x - COMPASS_HALF_WIDTH, COMPASS_MARGIN),
+                ScreenPoint::new(center_x + COMPASS_HALF_WIDTH, COMPASS_MARGIN + COMPASS_HEIGHT),
+                self.font,
+                self.color,
+            ),
+            DrawAction::Line(
+                ScreenPoint::new(center_x - COMPASS_HALF_WIDTH, COMPASS_MARGIN + COMPASS_HEIGHT),
+                ScreenPoint::new(center_x + COMPASS_HALF_WIDTH, COMPASS_MARGIN + COMPASS_HEIGHT),
+                self.color,
+            ),
+        ]
+    }
+}
+
+// Headings are clockwise degrees from north, north being the top of the map
+pub fn heading(orientation: Angle) -> (&'static str, u32) {
+    let clockwise = (PI / 2.0 - orientation.to_radiant()).rem_euclid(2.0 * PI);
+    let degrees = (clockwise.to_degrees().round() as u32) % 360;
+    let cardinal = CARDINALS[((degrees as f32 / 90.0).round() as usize) % CARDINALS.len()];
+
+    (cardinal, degrees)
+}
+
+#[cfg(test)]
+mod compass_test {
+    use spectral::prelude::*;
+
+    use crate::domain::maths::{Angle, ANGLE_90, ANGLE_DOWN, ANGLE_RIGHT, ANGLE_UP};
+    use crate::domain::topology::index::FontIndex;
+    use crate::domain::ui::compass::{heading, Compass};
+    use crate::domain::ui::view::ViewScreen;
+
+    #[test]
+    fn up_should_be_north() {
+        assert_that!(heading(ANGLE_UP)).is_equal_to(("N", 0));
+    }
+
+    #[test]
+    fn headings_should_go_clockwise() {
+        assert_that!(heading(ANGLE_RIGHT)).is_equal_to(("E", 90));
+        assert_that!(heading(ANGLE_DOWN)).is_equal_to(("S", 180));
+    }
+
+    #[test]
+    fn heading_should_wrap_around_north() {
+        assert_that!(heading(Angle::from_degree(91.0))).is_equal_to(("N", 359));
+    }
+
+    #[test]
+    fn compass_should_be_hidden_until_toggled() {
+        let compass = Compass::new(FontIndex::new(0));
+        let view = ViewScreen::new(100, 200, ANGLE_90);
+
+        assert_that!(compass.generate_actions(view, ANGLE_UP)).is_empty();
+        assert_that!(compass.toggle().generate_actions(view, ANGLE_UP)).has_length(2);
+    }
+}
@@ -0,0 +1,92 @@
+use crate::domain::topology::coord::ScreenPoint;
+use crate::domain::ui::color::{Color, WHITE};
+use crate::domain::ui::draw_action::DrawAction;
+use crate::domain::ui::view::ViewScreen;
+
+const CROSSHAIR_SIZE: i32 = 8;
+const CROSSHAIR_THICKNESS: i32 = 1;
+
+#[derive(Copy, Clone)]
+pub struct Crosshair {
+    color: Color,
+    openable_color: Color,
+    locked_color: Color,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum InteractionKind {
+    Door,
+    LockedDoor,
+    Pickup,
+}
+
+impl Crosshair {
+    pub fn new(color: Color, openable_color: Color, locked_color: Color) -> Self {
+        Self {
+            color,
+            openable_color,
+            locked_color,
+        }
+    }
+
+    pub fn color(&self, interaction: Option<InteractionKind>) -> Color {
+        match interaction {
+            Some(InteractionKind::Door) | Some(InteractionKind::Pickup) => self.openable_color,
+            Some(InteractionKind::LockedDoor) => self.locked_color,
+            None => self.color,
+        }
+    }
+
+    pub fn generate_actions(&self, view: ViewScreen, interaction: Option<InteractionKind>) -> Vec<DrawAction> {
+        let color = self.color(interaction);
+        let center_x = view.width() / 2;
+        let center_y = view.height() / 2;
+
+        vec![
+            DrawAction::Rectangle(
+                ScreenPoint::new(center_x - CROSSHAIR_SIZE, center_y - CROSSHAIR_THICKNESS),
+                ScreenPoint::new(center_x + CROSSHAIR_SIZE, center_y + CROSSHAIR_THICKNESS),
+                color,
+            ),
+            DrawAction::Rectangle(
+                ScreenPoint::new(center_x - CROSSHAIR_THICKNESS, center_y - CROSSHAIR_SIZE),
+                ScreenPoint::new(center_x + CROSSHAIR_THICKNESS, center_y + CROSSHAIR_SIZE),
+                color,
+            ),
+        ]
+    }
+}
+
+impl Default for Crosshair {
+    fn default() -> Self {
+        Self::new(WHITE, Color::new(0, 255, 0), Color::new(255, 0, 0))
+    }
+}
+
+#[cfg(test)]
+mod crosshair_test {
+    use spectral::prelude::*;
+
+    use crate::domain::maths::ANGLE_90;
+    use crate::domain::ui::color::{Color, WHITE};
+    use crate::domain::ui::crosshair::{Crosshair, InteractionKind};
+    use crate::domain::ui::draw_action::DrawAction;
+    use crate::domain::ui::view::ViewScreen;
+
+    #[test]
+    fn crosshair_should_be_centered() {
+        let actions = Crosshair::default().generate_actions(ViewScreen::new(100, 200, ANGLE_90), None);
+
+        assert_that!(actions).has_length(2);
+        assert!(matches!(&actions[0], DrawAction::Rectangle(start, end, color)
+            if start.x() + end.x() == 200 && start.y() + end.y() == 100 && *color == WHITE));
+    }
+
+    #[test]
+    fn crosshair_color_should_follow_interaction() {
+        let crosshair = Crosshair::new(WHITE, Color::new(1, 2, 3), Color::new(4, 5, 6));
+
+        assert_that!(crosshair.color(Some(InteractionKind::Door))).is_equal_to(Color::new(1, 2, 3));
+        assert_that!(crosshair.color(Some(InteractionKind::LockedDoor))).is_equal_to(Color::new(4, 5, 6));
+    }
+}
@@ -0,0 +1,88 @@
+use crate::domain::maths::Angle;
+use crate::domain::topology::coord::ScreenPoint;
+use crate::domain::ui::color::Color;
+use crate::domain::ui::draw_action::DrawAction;
+use crate::domain::ui::view::ViewScreen;
+
+const INDICATOR_DURATION_IN_MICROSECONDS: u128 = 1000000;
+const INDICATOR_DISTANCE_IN_FRACTION_OF_HEIGHT: f32 = 0.35;
+const INDICATOR_SIZE: f32 = 10.0;
+const INDICATOR_COLOR: Color = Color::new(200, 0, 0);
+
+#[derive(Copy, Clone)]
+struct Hit {
+    direction: Angle,
+    remaining_in_microseconds: u128,
+}
+
+#[derive(Clone, Default)]
+pub struct DamageIndicator {
+    hits: Vec<Hit>,
+}
+
+impl DamageIndicator {
+    pub fn new() -> Self {
+        Self { hits: vec![] }
+    }
+
+    pub fn with_hit(&self, direction: Angle) -> Self {
+        let hit = Hit {
+            direction,
+            remaining_in_microseconds: INDICATOR_DURATION_IN_MICROSECONDS,
+        };
+
+        Self {
+            hits: [self.hits.clone(), vec![hit]].concat(),
+        }
+    }
+
+    pub fn notify_elapsed(&self, microseconds: u128) -> Self {
+        let hits = self.hits.iter()
+            .filter(|hit| hit.remaining_in_microseconds > microseconds)
+            .map(|hit| Hit {
+                direction: hit.direction,
+                remaining_in_microseconds: hit.remaining_in_microseconds - microseconds,
+            })
+            .collect();
+
+        Self { hits }
+    }
+
+    pub fn generate_actions(&self, view: ViewScreen) -> Vec<DrawAction> {
+        let center_x = view.width() as f32 / 2.0;
+        let center_y = view.height() as f32 / 2.0;
+        let distance = view.height() as f32 * INDICATOR_DISTANCE_IN_FRACTION_OF_HEIGHT;
+
+        self.hits.iter()
+            .map(|hit| {
+                let fading = hit.remaining_in_microseconds as f32 / INDICATOR_DURATION_IN_MICROSECONDS as f32;
+                let size = INDICATOR_SIZE * fading;
+                let x = center_x - hit.direction.sin() * distance;
+                let y = center_y - hit.direction.cos() * distance;
+
+                DrawAction::Rectangle(
+                    ScreenPoint::new((x - size) as i32, (y - size) as i32),
+                    ScreenPoint::new((x + size) as i32, (y + size) as i32),
+                    INDICATOR_COLOR,
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod damage_indicator_test {
+    use spectral::prelude::*;
+
+    use crate::domain::maths::{ANGLE_0, ANGLE_90};
+    use crate::domain::ui::damage_indicator::DamageIndicator;
+    use crate::domain::ui::view::ViewScreen;
+
+    #[test]
+    fn indicator_should_fade_out_after_its_duration() {
+        let indicator = DamageIndicator::new().with_hit(ANGLE_0);
+
+        assert_that!(indicator.notify_elapsed(500000).generate_actions(ViewScreen::new(100, 100, ANGLE_90))).has_length(1);
+        assert_that!(indicator.notify_elapsed(1000000).generate_actions(ViewScreen::new(100, 100, ANGLE_90))).is_empty();
+    }
+}
@@ -1,8 +1,11 @@
 use crate::domain::topology::coord::ScreenPoint;
 use crate::domain::topology::index::FontIndex;
+use crate::domain::ui::color::Color;
 use crate::domain::ui::draw_action::DrawAction;
 
 const MARGIN: i32 = 3;
+const DEFAULT_TEXT_COLOR: Color = Color::new(255, 0, 0);
+const TIMING_LINE_HEIGHT: i32 = 25;
 
 pub struct DebugInfo {
     font: FontIndex,
@@ -10,6 +13,17 @@ pub struct DebugInfo {
     frame_displayed: u128,
     last_fps: u128,
     display_fps: bool,
+    text_color: Color,
+    timing: FrameTiming,
+    last_timing: FrameTiming,
+    fps_smoothing: Option<f32>,
+    smoothed_fps: f32,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct FrameTiming {
+    update_in_microseconds: u128,
+    render_in_microseconds: u128,
 }
 
 impl DebugInfo {
@@ -20,18 +34,87 @@ impl DebugInfo {
             frame_displayed: 0,
             last_fps: 0,
             display_fps: false,
+            text_color: DEFAULT_TEXT_COLOR,
+            timing: FrameTiming::new(0, 0),
+            last_timing: FrameTiming::new(0, 0),
+            fps_smoothing: None,
+            smoothed_fps: 0.0,
+        }
+    }
+
+    pub fn with_text_color(&self, text_color: Color) -> Self {
+        Self {
+            font: self.font,
+            elapsed_time_in_microseconds: self.elapsed_time_in_microseconds,
+            frame_displayed: self.frame_displayed,
+            last_fps: self.last_fps,
+            display_fps: self.display_fps,
+            text_color,
+            timing: self.timing,
+            last_timing: self.last_timing,
+            fps_smoothing: self.fps_smoothing,
+            smoothed_fps: self.smoothed_fps,
+        }
+    }
+
+    pub fn with_frame_timing(&self, update_in_microseconds: u128, render_in_microseconds: u128) -> Self {
+        Self {
+            font: self.font,
+            elapsed_time_in_microseconds: self.elapsed_time_in_microseconds,
+            frame_displayed: self.frame_displayed,
+            last_fps: self.last_fps,
+            display_fps: self.display_fps,
+            text_color: self.text_color,
+            timing: self.timing.add(FrameTiming::new(update_in_microseconds, render_in_microseconds)),
+            last_timing: self.last_timing,
+            fps_smoothing: self.fps_smoothing,
+            smoothed_fps: self.smoothed_fps,
+        }
+    }
+
+    pub fn with_fps_smoothing(&self, fps_smoothing: f32) -> Self {
+        Self {
+            font: self.font,
+            elapsed_time_in_microseconds: self.elapsed_time_in_microseconds,
+            frame_displayed: self.frame_displayed,
+            last_fps: self.last_fps,
+            display_fps: self.display_fps,
+            text_color: self.text_color,
+            timing: self.timing,
+            last_timing: self.last_timing,
+            fps_smoothing: Some(fps_smoothing),
+            smoothed_fps: self.smoothed_fps,
         }
     }
 
     pub fn generate_actions(&self) -> Vec<DrawAction> {
         if self.display_fps && self.last_fps != 0 {
             let fps = format!("{} fps", self.last_fps);
-            vec![DrawAction::Text(
-                fps,
-                ScreenPoint::new(MARGIN, 0),
-                ScreenPoint::new(100, 50),
-                self.font,
-            )]
+            let update = format!("update {} us", self.last_timing.update_in_microseconds());
+            let render = format!("render {} us", self.last_timing.render_in_microseconds());
+            vec![
+                DrawAction::Text(
+                    fps,
+                    ScreenPoint::new(MARGIN, 0),
+                    ScreenPoint::new(100, 50),
+                    self.font,
+                    self.text_color,
+                ),
+                DrawAction::Text(
+                    update,
+                    ScreenPoint::new(MARGIN, 50),
+                    ScreenPoint::new(150, 50 + TIMING_LINE_HEIGHT),
+                    self.font,
+                    self.text_color,
+                ),
+                DrawAction::Text(
+                    render,
+                    ScreenPoint::new(MARGIN, 50 + TIMING_LINE_HEIGHT),
+                    ScreenPoint::new(150, 50 + 2 * TIMING_LINE_HEIGHT),
+                    self.font,
+                    self.text_color,
+                ),
+            ]
         } else {
             vec![]
         }
@@ -44,10 +127,19 @@ impl DebugInfo {
             frame_displayed: self.frame_displayed,
             last_fps: self.last_fps,
             display_fps: !self.display_fps,
+            text_color: self.text_color,
+            timing: self.timing,
+            last_timing: self.last_timing,
+            fps_smoothing: self.fps_smoothing,
+            smoothed_fps: self.smoothed_fps,
         }
     }
 
     pub fn with_another_frame_displayed(&self, elapsed_time_in_microseconds: u128) -> Self {
+        if let Some(smoothing) = self.fps_smoothing {
+            return self.with_smoothed_frame(smoothing, elapsed_time_in_microseconds);
+        }
+
         if self.elapsed_time_in_microseconds > 500000 {
             let fps = self.frame_displayed * 1000000 / self.elapsed_time_in_microseconds;
             Self {
@@ -56,6 +148,11 @@ impl DebugInfo {
                 frame_displayed: 0,
                 last_fps: fps,
                 display_fps: self.display_fps,
+                text_color: self.text_color,
+                timing: FrameTiming::new(0, 0),
+                last_timing: self.timing.average(self.frame_displayed),
+                fps_smoothing: self.fps_smoothing,
+                smoothed_fps: self.smoothed_fps,
             }
         } else {
             Self {
@@ -65,7 +162,153 @@ impl DebugInfo {
                 frame_displayed: self.frame_displayed + 1,
                 last_fps: self.last_fps,
                 display_fps: self.display_fps,
+                text_color: self.text_color,
+                timing: self.timing,
+                last_timing: self.last_timing,
+                fps_smoothing: self.fps_smoothing,
+                smoothed_fps: self.smoothed_fps,
             }
         }
     }
+
+    fn with_smoothed_frame(&self, smoothing: f32, elapsed_time_in_microseconds: u128) -> Self {
+        let instant_fps = 1000000.0 / elapsed_time_in_microseconds.max(1) as f32;
+        let smoothed_fps = if self.smoothed_fps == 0.0 {
+            instant_fps
+        } else {
+            smoothing * instant_fps + (1.0 - smoothing) * self.smoothed_fps
+        };
+        Self {
+            font: self.font,
+            elapsed_time_in_microseconds: 0,
+            frame_displayed: 0,
+            last_fps: smoothed_fps.round() as u128,
+            display_fps: self.display_fps,
+            text_color: self.text_color,
+            timing: FrameTiming::new(0, 0),
+            last_timing: self.timing,
+            fps_smoothing: self.fps_smoothing,
+            smoothed_fps,
+        }
+    }
+}
+
+impl FrameTiming {
+    pub fn new(update_in_microseconds: u128, render_in_microseconds: u128) -> Self {
+        Self { update_in_microseconds, render_in_microseconds }
+    }
+
+    pub fn update_in_microseconds(&self) -> u128 {
+        self.update_in_microseconds
+    }
+
+    pub fn render_in_microseconds(&self) -> u128 {
+        self.render_in_microseconds
+    }
+
+    fn add(&self, other: FrameTiming) -> Self {
+        Self::new(
+            self.update_in_microseconds + other.update_in_microseconds,
+            self.render_in_microseconds + other.render_in_microseconds,
+        )
+    }
+
+    fn average(&self, frames: u128) -> Self {
+        if frames == 0 {
+            return *self;
+        }
+        Self::new(self.update_in_microseconds / frames, self.render_in_microseconds / frames)
+    }
+}
+
+#[cfg(test)]
+mod debug_info_test {
+    use spectral::prelude::*;
+
+    use crate::domain::topology::index::FontIndex;
+    use crate::domain::ui::color::Color;
+    use crate::domain::ui::debug::DebugInfo;
+    use crate::domain::ui::draw_action::DrawAction;
+
+    #[test]
+    fn fps_should_be_displayed_in_red_by_default() {
+        let debug = displayed_fps(DebugInfo::new(FontIndex::new(0)));
+
+        let actions = debug.generate_actions();
+
+        assert_that!(actions).has_length(3);
+        assert!(matches!(actions[0], DrawAction::Text(_, _, _, _, color) if color == Color::new(255, 0, 0)));
+    }
+
+    #[test]
+    fn fps_should_be_displayed_with_configured_color() {
+        let debug = displayed_fps(DebugInfo::new(FontIndex::new(0)).with_text_color(Color::new(0, 255, 0)));
+
+        let actions = debug.generate_actions();
+
+        assert_that!(actions).has_length(3);
+        assert!(matches!(actions[0], DrawAction::Text(_, _, _, _, color) if color == Color::new(0, 255, 0)));
+    }
+
+    #[test]
+    fn every_generated_text_should_carry_the_configured_color() {
+        let debug = displayed_fps(DebugInfo::new(FontIndex::new(0)).with_text_color(Color::new(0, 0, 255)))
+            .with_frame_timing(1000, 4000);
+
+        let colors: Vec<Color> = debug.generate_actions()
+            .into_iter()
+            .filter_map(|action| match action {
+                DrawAction::Text(_, _, _, _, color) => Some(color),
+                _ => None,
+            })
+            .collect();
+
+        assert_that!(colors).is_equal_to(vec![Color::new(0, 0, 255); 3]);
+    }
+
+    #[test]
+    fn update_and_render_timings_should_be_displayed() {
+        let debug = DebugInfo::new(FontIndex::new(0))
+            .toggle_fps()
+            .with_frame_timing(1000, 4000)
+            .with_another_frame_displayed(300000)
+            .with_frame_timing(3000, 6000)
+            .with_another_frame_displayed(300000)
+            .with_another_frame_displayed(300000);
+
+        let texts: Vec<String> = debug.generate_actions()
+            .into_iter()
+            .filter_map(|action| match action {
+                DrawAction::Text(text, _, _, _, _) => Some(text),
+                _ => None,
+            })
+            .collect();
+
+        assert_that!(texts).contains(String::from("update 2000 us"));
+        assert_that!(texts).contains(String::from("render 5000 us"));
+    }
+
+    #[test]
+    fn smoothed_fps_should_stay_between_instantaneous_values() {
+        let debug = DebugInfo::new(FontIndex::new(0))
+            .toggle_fps()
+            .with_fps_smoothing(0.1)
+            .with_another_frame_displayed(10000)
+            .with_another_frame_displayed(10000)
+            .with_another_frame_displayed(50000);
+
+        let fps = match &debug.generate_actions()[0] {
+            DrawAction::Text(text, _, _, _, _) => text.clone(),
+            _ => String::new(),
+        };
+
+        assert_that!(fps).is_equal_to(String::from("92 fps"));
+    }
+
+    fn displayed_fps(debug: DebugInfo) -> DebugInfo {
+        debug.toggle_fps()
+            .with_another_frame_displayed(300000)
+            .with_another_frame_displayed(300000)
+            .with_another_frame_displayed(300000)
+    }
 }
@@ -6,8 +6,125 @@ use crate::domain::ui::color::Color;
 pub enum DrawAction {
     Rectangle(ScreenPoint, ScreenPoint, Color),
     Line(ScreenPoint, ScreenPoint, Color),
-    TexturedLine(ScreenPoint, ScreenPoint, TextureIndex, f32),
-    Text(String, ScreenPoint, ScreenPoint, FontIndex),
+    TexturedLine(ScreenPoint, ScreenPoint, TextureIndex, f32, Color, Shade),
+    Text(String, ScreenPoint, ScreenPoint, FontIndex, Color),
     Clear(Color),
-    Sprite(ScreenPoint, ScreenPoint, TextureIndex),
+    Sprite(ScreenPoint, ScreenPoint, TextureIndex, Color),
+    AdditiveSprite(ScreenPoint, ScreenPoint, TextureIndex),
+}
+
+// An amount of 1 keeps the tint, 0 replaces it with the shade color
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Shade {
+    amount: f32,
+    color: Color,
+}
+
+#[derive(Copy, Clone)]
+pub enum DrawLayer {
+    Background,
+    World,
+    Frame,
+    Overlay,
+    Hud,
+}
+
+pub struct LayeredActions {
+    background: Vec<DrawAction>,
+    world: Vec<DrawAction>,
+    frame: Vec<DrawAction>,
+    overlay: Vec<DrawAction>,
+    hud: Vec<DrawAction>,
+}
+
+impl Shade {
+    pub fn new(amount: f32, color: Color) -> Self {
+        Self { amount, color }
+    }
+
+    pub fn amount(&self) -> f32 {
+        self.amount
+    }
+
+    pub fn color(&self) -> Color {
+        self.color
+    }
+
+    pub fn apply(&self, tint: Color) -> Color {
+        tint.lerp(self.color, 1.0 - self.amount)
+    }
+}
+
+impl Default for Shade {
+    fn default() -> Self {
+        Self::new(1.0, Color::new(0, 0, 0))
+    }
+}
+
+impl LayeredActions {
+    pub fn new() -> Self {
+        Self {
+            background: vec![],
+            world: vec![],
+            frame: vec![],
+            overlay: vec![],
+            hud: vec![],
+        }
+    }
+
+    pub fn add(&mut self, layer: DrawLayer, actions: impl IntoIterator<Item=DrawAction>) {
+        self.layer(layer).extend(actions);
+    }
+
+    pub fn map(&mut self, layer: DrawLayer, transform: impl Fn(&DrawAction) -> DrawAction) {
+        let layer = self.layer(layer);
+        *layer = layer.iter().map(transform).collect();
+    }
+
+    pub fn into_actions(self) -> Vec<DrawAction> {
+        let mut actions = self.background;
+        actions.extend(self.world);
+        actions.extend(self.frame);
+        actions.extend(self.overlay);
+        actions.extend(self.hud);
+        actions
+    }
+
+    fn layer(&mut self, layer: DrawLayer) -> &mut Vec<DrawAction> {
+        match layer {
+            DrawLayer::Background => &mut self.background,
+            DrawLayer::World => &mut self.world,
+            DrawLayer::Frame => &mut self.frame,
+            DrawLayer::Overlay => &mut self.overlay,
+            DrawLayer::Hud => &mut self.hud,
+        }
+    }
+}
+
+impl Default for LayeredActions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod draw_action_test {
+    use spectral::prelude::*;
+
+    use crate::domain::topology::coord::ScreenPoint;
+    use crate::domain::ui::color::Color;
+    use crate::domain::ui::draw_action::{DrawAction, DrawLayer, LayeredActions};
+
+    #[test]
+    fn hud_actions_should_come_after_world_actions_whatever_the_insertion_order() {
+        let mut layers = LayeredActions::new();
+        layers.add(DrawLayer::Hud, vec![DrawAction::Clear(Color::new(1, 1, 1))]);
+        layers.add(DrawLayer::World, vec![DrawAction::Line(ScreenPoint::new(0, 0), ScreenPoint::new(1, 1), Color::new(2, 2, 2))]);
+
+        let actions = layers.into_actions();
+
+        assert_that!(actions).has_length(2);
+        assert_that!(matches!(actions[0], DrawAction::Line(..))).is_true();
+        assert_that!(matches!(actions[1], DrawAction::Clear(..))).is_true();
+    }
 }
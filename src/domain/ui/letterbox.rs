@@ -0,0 +1,136 @@
+use crate::domain::actors::actor::SpeedStats;
+use crate::domain::maths::between;
+use crate::domain::topology::coord::ScreenPoint;
+use crate::domain::ui::color::Color;
+use crate::domain::ui::draw_action::DrawAction;
+use crate::domain::ui::view::ViewScreen;
+
+const BAR_COLOR: Color = Color::new(0, 0, 0);
+
+#[derive(Copy, Clone)]
+pub struct Letterbox {
+    fraction: f32,
+    target: f32,
+    speed: SpeedStats,
+}
+
+impl Letterbox {
+    pub fn new(speed: SpeedStats) -> Self {
+        Self {
+            fraction: 0.0,
+            target: 0.0,
+            speed,
+        }
+    }
+
+    pub fn with_target(&self, target: f32) -> Self {
+        Self {
+            fraction: self.fraction,
+            target: between(0.0, target, 1.0),
+            speed: self.speed,
+        }
+    }
+
+    pub fn notify_elapsed(&self, microseconds: u128) -> Self {
+        let step = self.speed.to_units(microseconds);
+        let fraction = if self.fraction < self.target {
+            between(self.fraction, self.fraction + step, self.target)
+        } else {
+            between(self.target, self.fraction - step, self.fraction)
+        };
+
+        Self {
+            fraction,
+            target: self.target,
+            speed: self.speed,
+        }
+    }
+
+    pub fn fraction(&self) -> f32 {
+        self.fraction
+    }
+
+    pub fn bar_height(&self, view: ViewScreen) -> i32 {
+        (view.height() as f32 * self.fraction / 2.0) as i32
+    }
+
+    pub fn generate_actions(&self, view: ViewScreen) -> Vec<DrawAction> {
+        let bar_height = self.bar_height(view);
+        if bar_height == 0 {
+            return vec![];
+        }
+
+        vec![
+            DrawAction::Rectangle(
+                ScreenPoint::new(0, 0),
+                ScreenPoint::new(view.width(), bar_height),
+                BAR_COLOR,
+            ),
+            DrawAction::Rectangle(
+                ScreenPoint::new(0, view.height() - bar_height),
+                ScreenPoint::new(view.width(), view.height()),
+                BAR_COLOR,
+            ),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod letterbox_test {
+    use spectral::prelude::*;
+
+    use crate::domain::actors::actor::SpeedStats;
+    use crate::domain::maths::ANGLE_90;
+    use crate::domain::ui::letterbox::Letterbox;
+    use crate::domain::ui::view::ViewScreen;
+
+    #[test]
+    fn letterbox_should_be_hidden_by_default() {
+        let letterbox = Letterbox::new(SpeedStats::new(1.0));
+
+        let actions = letterbox.generate_actions(ViewScreen::new(100, 200, ANGLE_90));
+
+        assert_that!(actions).is_empty();
+    }
+
+    #[test]
+    fn letterbox_should_grow_toward_target_over_time() {
+        let letterbox = Letterbox::new(SpeedStats::new(1.0))
+            .with_target(0.5)
+            .notify_elapsed(250000);
+
+        assert_that!(letterbox.fraction()).is_close_to(0.25, 0.001);
+    }
+
+    #[test]
+    fn letterbox_should_not_go_beyond_target() {
+        let letterbox = Letterbox::new(SpeedStats::new(1.0))
+            .with_target(0.5)
+            .notify_elapsed(10000000);
+
+        assert_that!(letterbox.fraction()).is_close_to(0.5, 0.001);
+    }
+
+    #[test]
+    fn letterbox_should_shrink_back_when_target_is_lowered() {
+        let letterbox = Letterbox::new(SpeedStats::new(1.0))
+            .with_target(0.5)
+            .notify_elapsed(10000000)
+            .with_target(0.0)
+            .notify_elapsed(100000);
+
+        assert_that!(letterbox.fraction()).is_close_to(0.4, 0.001);
+    }
+
+    #[test]
+    fn bars_should_share_hidden_fraction_between_top_and_bottom() {
+        let letterbox = Letterbox::new(SpeedStats::new(1.0))
+            .with_target(0.5)
+            .notify_elapsed(10000000);
+
+        let view = ViewScreen::new(100, 200, ANGLE_90);
+
+        assert_that!(letterbox.bar_height(view)).is_equal_to(25);
+        assert_that!(letterbox.generate_actions(view)).has_length(2);
+    }
+}
@@ -1,4 +1,9 @@
 pub mod color;
+pub mod compass;
+pub mod crosshair;
+pub mod damage_indicator;
 pub mod debug;
 pub mod draw_action;
+pub mod letterbox;
 pub mod view;
+pub mod view_model;
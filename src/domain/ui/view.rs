@@ -7,6 +7,14 @@ pub struct ViewScreen {
     width: u16,
     angle: Angle,
     ratio: f32,
+    horizon_shift: f32,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum AspectMode {
+    Maintain,
+    AdjustFov,
+    KeepFov,
 }
 
 impl ViewScreen {
@@ -16,6 +24,7 @@ impl ViewScreen {
             width,
             angle,
             ratio: height as f32 / width as f32,
+            horizon_shift: 0.0,
         }
     }
 
@@ -35,6 +44,64 @@ impl ViewScreen {
         self.ratio
     }
 
+    // The horizontal field of view is the configured one, the vertical one follows the screen proportions
+    pub fn vertical_angle(&self) -> Angle {
+        Angle::new((self.angle.multiplication(0.5).tan() * self.ratio).atan() * 2.0)
+    }
+
+    pub fn with_horizon_shift(&self, horizon_shift: f32) -> Self {
+        Self {
+            height: self.height,
+            width: self.width,
+            angle: self.angle,
+            ratio: self.ratio,
+            horizon_shift,
+        }
+    }
+
+    pub fn with_angle(&self, angle: Angle) -> Self {
+        Self {
+            height: self.height,
+            width: self.width,
+            angle,
+            ratio: self.ratio,
+            horizon_shift: self.horizon_shift,
+        }
+    }
+
+    pub fn cropped(&self, height: u16) -> Self {
+        Self::new(height.max(1), self.width, self.angle).with_horizon_shift(self.horizon_shift)
+    }
+
+    pub fn horizon(&self) -> i32 {
+        (self.height as f32 * (0.5 + self.horizon_shift)) as i32
+    }
+
+    pub fn resize(&self, width: u16, height: u16, mode: AspectMode) -> Self {
+        let width = width.max(1);
+        let height = height.max(1);
+        match mode {
+            AspectMode::Maintain => *self,
+            AspectMode::AdjustFov => {
+                let aspect_change = (width as f32 / height as f32) * self.ratio;
+                let half_angle = (self.angle.multiplication(0.5).tan() * aspect_change).atan();
+                Self::new(height, width, Angle::new(half_angle * 2.0)).with_horizon_shift(self.horizon_shift)
+            }
+            AspectMode::KeepFov => Self::new(height, width, self.angle).with_horizon_shift(self.horizon_shift),
+        }
+    }
+
+    pub fn scaled(&self, scale: f32) -> Self {
+        let height = ((self.height as f32 * scale).round() as u16).max(1);
+        let width = ((self.width as f32 * scale).round() as u16).max(1);
+        Self::new(height, width, self.angle).with_horizon_shift(self.horizon_shift)
+    }
+
+    pub fn frustum(&self, orientation: &Angle) -> (Angle, Angle) {
+        let edges = orientation.discreet_cone_straight_space(self.angle, 2);
+        (edges[0], edges[1])
+    }
+
     pub fn view_plane(&self, orientation: &Angle) -> Position {
         let norm = self.angle.multiplication(0.5).tan();
         let direction = orientation.addition(ANGLE_90);
@@ -51,7 +118,7 @@ mod viewscreen_test {
     use spectral::prelude::*;
 
     use crate::domain::maths::{Angle, ANGLE_90};
-    use crate::domain::ui::view::ViewScreen;
+    use crate::domain::ui::view::{AspectMode, ViewScreen};
 
     #[test]
     fn should_get_plane_from_90_degrees_view() {
@@ -82,5 +149,89 @@ mod viewscreen_test {
         assert_that!(plane.x()).is_close_to(-0.005, 0.001);
         assert_that!(plane.y()).is_close_to(0.0, 0.001);
     }
-}
 
+    #[test]
+    fn frustum_should_be_symmetric_around_orientation_and_span_the_view() {
+        let view = ViewScreen::new(400, 400, ANGLE_90);
+        let orientation = Angle::new(0.3);
+
+        let (left, right) = view.frustum(&orientation);
+
+        assert_that!(left.to_radiant() - orientation.to_radiant())
+            .is_close_to(orientation.to_radiant() - right.to_radiant(), 0.001);
+        assert_that!((left.to_radiant() - right.to_radiant()).abs()).is_close_to(ANGLE_90.to_radiant(), 0.001);
+    }
+
+    #[test]
+    fn narrower_angle_should_narrow_the_frustum_and_the_plane() {
+        let view = ViewScreen::new(400, 400, ANGLE_90);
+        let zoomed = view.with_angle(Angle::from_degree(45.0));
+
+        let (left, right) = zoomed.frustum(&ANGLE_90);
+
+        assert_that!((left.to_radiant() - right.to_radiant()).abs()).is_close_to(PI / 4.0, 0.001);
+        assert_that!(zoomed.view_plane(&ANGLE_90).x().abs()).is_less_than(view.view_plane(&ANGLE_90).x().abs());
+    }
+
+    #[test]
+    fn vertical_angle_should_follow_the_screen_proportions() {
+        let square = ViewScreen::new(400, 400, ANGLE_90);
+        let widescreen = ViewScreen::new(500, 800, ANGLE_90);
+
+        assert_that!(square.vertical_angle().to_radiant()).is_close_to(ANGLE_90.to_radiant(), 0.001);
+        assert_that!(widescreen.vertical_angle().multiplication(0.5).tan()).is_close_to(0.625, 0.001);
+    }
+
+    #[test]
+    fn maintained_aspect_should_keep_the_view_size_on_wider_window() {
+        let view = ViewScreen::new(500, 800, ANGLE_90);
+
+        let resized = view.resize(1600, 500, AspectMode::Maintain);
+
+        assert_that!(resized.width()).is_equal_to(800);
+        assert_that!(resized.height()).is_equal_to(500);
+    }
+
+    #[test]
+    fn kept_fov_should_take_the_window_size_with_the_same_angle() {
+        let view = ViewScreen::new(500, 800, ANGLE_90);
+
+        let resized = view.resize(1024, 768, AspectMode::KeepFov);
+
+        assert_that!(resized.width()).is_equal_to(1024);
+        assert_that!(resized.height()).is_equal_to(768);
+        assert_that!(resized.angle().to_radiant()).is_close_to(ANGLE_90.to_radiant(), 0.001);
+        assert_that!(resized.ratio()).is_close_to(0.75, 0.001);
+    }
+
+    #[test]
+    fn resized_view_should_keep_its_horizon_shift() {
+        let view = ViewScreen::new(400, 400, ANGLE_90).with_horizon_shift(0.1);
+
+        for mode in [AspectMode::Maintain, AspectMode::AdjustFov, AspectMode::KeepFov] {
+            let resized = view.resize(800, 600, mode);
+
+            assert_that!(resized.horizon()).is_equal_to((resized.height() as f32 * 0.6) as i32);
+        }
+    }
+
+    #[test]
+    fn minimized_window_should_keep_a_drawable_view() {
+        let view = ViewScreen::new(500, 800, ANGLE_90);
+
+        let resized = view.resize(0, 0, AspectMode::KeepFov);
+
+        assert_that!(resized.width()).is_equal_to(1);
+        assert_that!(resized.height()).is_equal_to(1);
+    }
+
+    #[test]
+    fn adjusted_fov_should_widen_the_angle_on_wider_window() {
+        let view = ViewScreen::new(400, 400, ANGLE_90);
+
+        let resized = view.resize(800, 400, AspectMode::AdjustFov);
+
+        assert_that!(resized.width()).is_equal_to(800);
+        assert_that!(resized.angle().multiplication(0.5).tan()).is_close_to(2.0, 0.001);
+    }
+}
@@ -0,0 +1,61 @@
+use crate::domain::topology::coord::ScreenPoint;
+use crate::domain::ui::view::ViewScreen;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ViewModel {
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+}
+
+impl ViewModel {
+    pub fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self { x, y, width, height }
+    }
+
+    pub fn fullscreen() -> Self {
+        Self::new(0.0, 0.0, 1.0, 1.0)
+    }
+
+    pub fn rect(&self, view: ViewScreen) -> (ScreenPoint, ScreenPoint) {
+        let width = view.width() as f32;
+        let height = view.height() as f32;
+
+        let start = ScreenPoint::new((self.x * width) as i32, (self.y * height) as i32);
+        let end = ScreenPoint::new(((self.x + self.width) * width) as i32, ((self.y + self.height) * height) as i32);
+
+        (start, end)
+    }
+}
+
+impl Default for ViewModel {
+    fn default() -> Self {
+        Self::fullscreen()
+    }
+}
+
+#[cfg(test)]
+mod view_model_test {
+    use spectral::prelude::*;
+
+    use crate::domain::maths::ANGLE_90;
+    use crate::domain::ui::view::ViewScreen;
+    use crate::domain::ui::view_model::ViewModel;
+
+    #[test]
+    fn fullscreen_should_cover_the_whole_view() {
+        let (start, end) = ViewModel::fullscreen().rect(ViewScreen::new(300, 400, ANGLE_90));
+
+        assert_that!((start.x(), start.y())).is_equal_to((0, 0));
+        assert_that!((end.x(), end.y())).is_equal_to((400, 300));
+    }
+
+    #[test]
+    fn rect_should_be_relative_to_view_size() {
+        let (start, end) = ViewModel::new(0.5, 0.6, 0.5, 0.4).rect(ViewScreen::new(300, 400, ANGLE_90));
+
+        assert_that!((start.x(), start.y())).is_equal_to((200, 180));
+        assert_that!((end.x(), end.y())).is_equal_to((400, 300));
+    }
+}
@@ -1,13 +1,13 @@
 use std::env::current_dir;
 use std::fs;
 
-pub fn load_as_binary(path: String) -> Vec<u8> {
+pub fn load_as_binary(path: String) -> Result<Vec<u8>, String> {
     let real_path = current_dir()
-        .unwrap()
+        .map_err(|e| e.to_string())?
         .join("res")
         .join(path);
 
-    fs::read(real_path).unwrap()
+    fs::read(real_path).map_err(|e| e.to_string())
 }
 
 pub fn load_as_file(path: String) -> String {
@@ -18,4 +18,20 @@ pub fn load_as_file(path: String) -> String {
         .into_os_string()
         .into_string()
         .unwrap()
-}
\ No newline at end of file
+}
+
+pub fn read_save(path: String) -> Result<Vec<u8>, String> {
+    let real_path = current_dir()
+        .map_err(|e| e.to_string())?
+        .join(path);
+
+    fs::read(real_path).map_err(|e| e.to_string())
+}
+
+pub fn write_save(path: String, content: Vec<u8>) -> Result<(), String> {
+    let real_path = current_dir()
+        .map_err(|e| e.to_string())?
+        .join(path);
+
+    fs::write(real_path, content).map_err(|e| e.to_string())
+}
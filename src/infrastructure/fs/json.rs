@@ -1,18 +1,46 @@
 use serde::{Deserialize, Serialize};
 
-use crate::domain::actors::actor::{AccelerationStats, PlayerStats, SpeedStats};
-use crate::domain::actors::shoot::{AnimationStep, WeaponConfiguration};
-use crate::domain::control::actions::{ActionStateBuilder, LinearActionState, NothingActionState};
-use crate::domain::maths::Angle;
-use crate::domain::topology::door::{CentralOpening, LateralOpening, Openable};
-use crate::domain::topology::map::{EnemyType, MapConfiguration, SpawnPoint};
-use crate::infrastructure::sdl::texture::ResourceRegistryLoader;
+use crate::domain::actors::actor::{AccelerationStats, AimAssist, Despawn, EnemyOrigin, MeleeAttack, Perception, PlayerStats, RangedAttack, SpeedStats, StaminaStats, WallBump};
+use crate::domain::actors::pickup::{PickupKind, PickupType};
+use crate::domain::actors::shoot::{AnimationStep, WeaponConfiguration, WeaponKind};
+use crate::domain::actors::spawner::SpawnerType;
+use crate::domain::control::actions::{ActionStateBuilder, EasedActionState, LinearActionState, NothingActionState};
+use crate::domain::control::bindings::{Chord, KeyBindings, Modifiers};
+use crate::domain::control::force::InputForce;
+use crate::domain::control::input::Input;
+use crate::domain::error::EngineError;
+use crate::domain::game::GameConfiguration;
+use crate::domain::maths::{Angle, between};
+use crate::domain::resources::ResourceRegistryLoader;
+use crate::domain::save::{LevelState, SavedDoor, SavedEnemy, SavedPlayer};
+use crate::domain::topology::coord::Position;
+use crate::domain::topology::door::{CentralOpening, LateralOpening, Openable, VerticalOpening};
+use crate::domain::topology::index::TextureIndex;
+use crate::domain::topology::map::{Backdrop, EnemyType, Fog, KeyColor, MapConfiguration, MapMetadata, SpawnOrientation, SpawnOrientationMode, SpawnPoint};
+use crate::domain::ui::color::Color;
+use crate::domain::ui::crosshair::Crosshair;
+use crate::domain::ui::view::{AspectMode, ViewScreen};
+use crate::domain::ui::view_model::ViewModel;
+
+const DEFAULT_TRIGGER_RADIUS: f32 = 1.5;
+const DEFAULT_AIM_ASSIST_STRENGTH: f32 = 0.5;
 
 #[derive(Serialize, Deserialize)]
 pub struct State {
     pub state_type: String,
     pub opening_mode: String,
     pub speed: f32,
+    pub passable_threshold: Option<f32>,
+    pub auto_open: Option<bool>,
+    pub trigger_radius: Option<f32>,
+    pub auto_close_after: Option<f32>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct JsonColor {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -24,6 +52,45 @@ pub struct Tile {
     pub state: Option<State>,
     pub orientation_in_degrees: Option<f32>,
     pub health: Option<u32>,
+    pub color: Option<JsonColor>,
+    pub tint: Option<JsonColor>,
+    pub thickness: Option<f32>,
+    pub ranged: Option<JsonRangedAttack>,
+    pub melee: Option<JsonMeleeAttack>,
+    pub perception: Option<JsonPerception>,
+    pub interval: Option<f32>,
+    pub max_count: Option<u32>,
+    pub name: Option<String>,
+    pub weapon: Option<Weapon>,
+    pub subtype: Option<String>,
+    pub amount: Option<u32>,
+    pub key: Option<String>,
+    pub locked: Option<String>,
+    pub target: Option<String>,
+    pub link: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct JsonRangedAttack {
+    pub texture: String,
+    pub speed: f32,
+    pub damage: u32,
+    pub cooldown: f32,
+    pub range: f32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct JsonMeleeAttack {
+    pub damage: u32,
+    pub cooldown: f32,
+    pub range: f32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct JsonPerception {
+    pub sight_range: f32,
+    pub sight_cone_in_degrees: f32,
+    pub hearing_range: f32,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -31,6 +98,17 @@ pub struct JsonPlayer {
     acceleration: f32,
     deceleration: f32,
     maximum_speed: f32,
+    run_factor: Option<f32>,
+    stamina: Option<JsonStamina>,
+    health: Option<u32>,
+    collision_radius: Option<f32>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct JsonStamina {
+    maximum: f32,
+    drain: f32,
+    regeneration: f32,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -39,49 +117,438 @@ pub struct Animation {
     pub duration: f32,
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum JsonWeapons {
+    Single(Box<Weapon>),
+    Many(Vec<Weapon>),
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct Weapon {
     idle: String,
     damage: u32,
+    knockback: Option<f32>,
+    view_model: Option<JsonViewModel>,
+    muzzle_flash: Option<String>,
+    kind: Option<String>,
+    range: Option<f32>,
+    angle_in_degrees: Option<f32>,
+    arc_degrees: Option<f32>,
+    radius: Option<f32>,
+    ammo: Option<u32>,
+    speed: Option<f32>,
+    projectile: Option<String>,
     startup: Animation,
     active: Animation,
     recovery: Animation,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct JsonViewModel {
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Spawn {
+    orientation_mode: String,
+    default_orientation_in_degrees: f32,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct Json {
     player: JsonPlayer,
     tiles: Vec<Tile>,
-    weapon: Weapon,
+    weapon: JsonWeapons,
+    spawn: Option<Spawn>,
+    metadata: Option<JsonMetadata>,
+    ambient: Option<f32>,
+    backdrop: Option<JsonBackdrop>,
+    fog: Option<JsonFog>,
+    ceiling_color: Option<JsonColor>,
+    floor_color: Option<JsonColor>,
+    pad_short_rows: Option<bool>,
+    comment_prefix: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct JsonFog {
+    color: JsonColor,
+    distance: f32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct JsonBackdrop {
+    texture: String,
+    height: f32,
 }
 
-pub fn load_configuration(content: String, resource_registry: &mut dyn ResourceRegistryLoader) -> MapConfiguration {
-    let data = load(content);
-    to_conf(data, resource_registry)
+#[derive(Serialize, Deserialize)]
+pub struct JsonMetadata {
+    name: Option<String>,
+    author: Option<String>,
+    par_time: Option<f32>,
+    description: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct JsonWindow {
+    width: u16,
+    height: u16,
+    fov_in_degrees: f32,
+    aspect_mode: Option<String>,
+    render_scale: Option<f32>,
+    fps_smoothing: Option<f32>,
+    vsync: Option<bool>,
+    fps_cap: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct JsonInput {
+    movement_speed: f32,
+    rotation_speed: f32,
+    turn_snap: Option<bool>,
+    aim_assist_in_degrees: Option<f32>,
+    aim_assist_strength: Option<f32>,
+    mouse_sensitivity: Option<f32>,
+    invert_rotation: Option<bool>,
+    bindings: Option<Vec<JsonBinding>>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct JsonBinding {
+    input: String,
+    key: String,
+    modifiers: Option<Vec<String>>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct JsonCrosshair {
+    color: JsonColor,
+    openable_color: JsonColor,
+    locked_color: JsonColor,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct JsonWallBump {
+    speed: f32,
+    damage: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct JsonDespawn {
+    distance: f32,
+    grace: f32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct JsonGame {
+    window: JsonWindow,
+    input: JsonInput,
+    font: String,
+    configuration: String,
+    map: String,
+    animation_distance: Option<f32>,
+    crosshair: Option<JsonCrosshair>,
+    wall_bump: Option<JsonWallBump>,
+    despawn: Option<JsonDespawn>,
+    max_sounds_per_frame: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct JsonSavedPlayer {
+    x: f32,
+    y: f32,
+    orientation: f32,
+    health: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct JsonSavedEnemy {
+    map_index: Option<usize>,
+    spawner: Option<usize>,
+    x: f32,
+    y: f32,
+    health: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct JsonSavedDoor {
+    x: i16,
+    y: i16,
+    activated: bool,
+    opening_percentage: f32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct JsonLevelState {
+    map: Option<String>,
+    player: JsonSavedPlayer,
+    enemies: Vec<JsonSavedEnemy>,
+    doors: Vec<JsonSavedDoor>,
+    current_weapon: Option<usize>,
+    ammo: Option<Vec<Option<u32>>>,
+    spawned: Option<Vec<u32>>,
+}
+
+pub fn load_game_configuration(content: String) -> Result<GameConfiguration, EngineError> {
+    game_conf(content).map_err(EngineError::Configuration)
+}
+
+fn game_conf(content: String) -> Result<GameConfiguration, String> {
+    let data: JsonGame = serde_json::from_str(&content)
+        .map_err(|e| format!("Game configuration is not valid: {}", e))?;
+
+    let view = ViewScreen::new(data.window.height, data.window.width, Angle::from_degree(data.window.fov_in_degrees));
+    let input_force = InputForce::new(data.input.movement_speed, data.input.rotation_speed);
+    let input_force = match data.input.mouse_sensitivity {
+        Some(sensitivity) if sensitivity > 0.0 => input_force.with_sensitivity(sensitivity),
+        Some(sensitivity) => return Err(format!("Mouse sensitivity must be greater than zero, got {}", sensitivity)),
+        None => input_force,
+    };
+    let input_force = if data.input.invert_rotation.unwrap_or(false) { input_force.with_inverted_rotation() } else { input_force };
+
+    let aspect_mode = match data.window.aspect_mode.as_deref() {
+        None | Some("MAINTAIN") => AspectMode::Maintain,
+        Some("ADJUST_FOV") => AspectMode::AdjustFov,
+        Some("KEEP_FOV") => AspectMode::KeepFov,
+        Some(other) => return Err(format!("Unknown aspect mode '{}'", other)),
+    };
+    let game = GameConfiguration::new(view, input_force, data.font, data.configuration, data.map)
+        .with_aspect_mode(aspect_mode);
+
+    let game = if data.input.turn_snap.unwrap_or(false) { game.with_turn_snap() } else { game };
+
+    let mut key_bindings = KeyBindings::new();
+    for binding in data.input.bindings.unwrap_or_default() {
+        let chord = Chord::new(binding.key, modifiers(&binding.modifiers.unwrap_or_default())?);
+        match binding.input.as_str() {
+            "FORWARD" => key_bindings.bind_held(chord, Input::Forward),
+            "BACKWARD" => key_bindings.bind_held(chord, Input::Backward),
+            "STRAFE_LEFT" => key_bindings.bind_held(chord, Input::StrafeLeft),
+            "STRAFE_RIGHT" => key_bindings.bind_held(chord, Input::StrafeRight),
+            "RUN" => key_bindings.bind_held(chord, Input::Run),
+            "CROUCH" => key_bindings.bind_held(chord, Input::Crouch),
+            "ZOOM" => key_bindings.bind_held(chord, Input::Zoom),
+            "ACTION" => key_bindings.bind_pressed(chord, Input::Action),
+            "FULLSCREEN" => key_bindings.bind_pressed(chord, Input::ToggleFullscreen),
+            "SHOW_FPS" => key_bindings.bind_pressed(chord, Input::ShowFps),
+            "COMPASS" => key_bindings.bind_pressed(chord, Input::ToggleCompass),
+            "MINIMAP" => key_bindings.bind_pressed(chord, Input::ToggleMinimap),
+            "SHOOT" => key_bindings.bind_pressed(chord, Input::Shoot),
+            "QUICK_SAVE" => key_bindings.bind_pressed(chord, Input::QuickSave),
+            "QUICK_LOAD" => key_bindings.bind_pressed(chord, Input::QuickLoad),
+            "QUIT" => key_bindings.bind_pressed(chord, Input::Quit),
+            other => return Err(format!("Unknown input '{}'", other)),
+        }
+    }
+    let game = game.with_key_bindings(key_bindings);
+
+    let game = match data.input.aim_assist_in_degrees {
+        Some(degrees) if non_negative("Aim assist", degrees)? > 0.0 => {
+            let strength = data.input.aim_assist_strength.unwrap_or(DEFAULT_AIM_ASSIST_STRENGTH);
+            if !(strength > 0.0 && strength <= 1.0) {
+                return Err(format!("Aim assist strength must be between 0 and 1, got {}", strength));
+            }
+            game.with_aim_assist(AimAssist::new(Angle::from_degree(degrees), strength))
+        }
+        _ => game,
+    };
+
+    let game = match data.window.render_scale {
+        Some(scale) if scale > 0.0 && scale <= 1.0 => game.with_render_scale(scale),
+        Some(scale) => return Err(format!("Render scale must be between 0 and 1, got {}", scale)),
+        None => game,
+    };
+
+    let game = match data.window.fps_smoothing {
+        Some(smoothing) if smoothing > 0.0 && smoothing <= 1.0 => game.with_fps_smoothing(smoothing),
+        Some(smoothing) => return Err(format!("FPS smoothing must be between 0 and 1, got {}", smoothing)),
+        None => game,
+    };
+
+    let game = game.with_vsync(data.window.vsync.unwrap_or(true));
+    let game = match data.window.fps_cap {
+        Some(0) => return Err(String::from("FPS cap must be greater than zero")),
+        Some(fps_cap) => game.with_fps_cap(fps_cap),
+        None => game,
+    };
+
+    let game = match data.max_sounds_per_frame {
+        Some(maximum) => game.with_max_sounds_per_frame(maximum),
+        None => game,
+    };
+
+    let game = match data.animation_distance {
+        Some(distance) => game.with_animation_distance(distance),
+        None => game,
+    };
+
+    let game = match data.crosshair {
+        Some(crosshair) => game.with_crosshair(Crosshair::new(
+            to_color(crosshair.color),
+            to_color(crosshair.openable_color),
+            to_color(crosshair.locked_color),
+        )),
+        None => game,
+    };
+
+    let game = match data.wall_bump {
+        Some(wall_bump) => game.with_wall_bump(WallBump::new(
+            SpeedStats::new(non_negative("Wall bump speed", wall_bump.speed)?),
+            wall_bump.damage.unwrap_or(0),
+        )),
+        None => game,
+    };
+
+    Ok(match data.despawn {
+        Some(despawn) => game.with_despawn(Despawn::new(
+            non_negative("Despawn distance", despawn.distance)?,
+            non_negative("Despawn grace", despawn.grace)?,
+        )),
+        None => game,
+    })
 }
 
-fn load(content: String) -> Json {
-    serde_json::from_str(&content).unwrap()
+pub fn load_configuration(content: String, resource_registry: &mut dyn ResourceRegistryLoader) -> Result<MapConfiguration, EngineError> {
+    load(content)
+        .and_then(|data| to_conf(data, resource_registry))
+        .map_err(EngineError::Configuration)
 }
 
-fn to_conf(data: Json, resource_registry: &mut dyn ResourceRegistryLoader) -> MapConfiguration {
-    let player_conf = player_conf(data.player);
-    let transparency = resource_registry.load_texture(String::from("transparency.png"));
+pub fn save_level_state(state: &LevelState) -> Result<String, String> {
+    let player = state.player();
+    let data = JsonLevelState {
+        map: state.map().map(String::from),
+        player: JsonSavedPlayer {
+            x: player.position().x(),
+            y: player.position().y(),
+            orientation: player.orientation().to_radiant(),
+            health: player.health(),
+        },
+        enemies: state.enemies().iter()
+            .map(|enemy| {
+                let (map_index, spawner) = match enemy.origin() {
+                    EnemyOrigin::Map(index) => (Some(index), None),
+                    EnemyOrigin::Spawner(index) => (None, Some(index)),
+                };
+                JsonSavedEnemy { map_index, spawner, x: enemy.position().x(), y: enemy.position().y(), health: enemy.health() }
+            })
+            .collect(),
+        doors: state.doors().iter()
+            .map(|door| JsonSavedDoor { x: door.x(), y: door.y(), activated: door.activated(), opening_percentage: door.opening_percentage() })
+            .collect(),
+        current_weapon: Some(state.current_weapon()),
+        ammo: Some(state.ammo().to_vec()),
+        spawned: Some(state.spawned().to_vec()),
+    };
+
+    serde_json::to_string(&data).map_err(|e| format!("Level state can not be saved: {}", e))
+}
+
+pub fn load_level_state(content: String) -> Result<LevelState, String> {
+    let data: JsonLevelState = serde_json::from_str(&content)
+        .map_err(|e| format!("Saved level state is not valid: {}", e))?;
+
+    let player = SavedPlayer::new(
+        Position::new(finite("Saved player x", data.player.x)?, finite("Saved player y", data.player.y)?),
+        Angle::new(finite("Saved player orientation", data.player.orientation)?),
+        data.player.health,
+    );
+    let enemies = data.enemies.iter()
+        .enumerate()
+        .map(|(index, enemy)| {
+            // Saves without an origin listed the map enemies in order
+            let origin = match enemy.spawner {
+                Some(spawner) => EnemyOrigin::Spawner(spawner),
+                None => EnemyOrigin::Map(enemy.map_index.unwrap_or(index)),
+            };
+            Ok(SavedEnemy::new(origin, Position::new(finite("Saved enemy x", enemy.x)?, finite("Saved enemy y", enemy.y)?), enemy.health))
+        })
+        .collect::<Result<Vec<SavedEnemy>, String>>()?;
+    let doors = data.doors.iter()
+        .map(|door| Ok(SavedDoor::new(door.x, door.y, door.activated, finite("Saved door opening", door.opening_percentage)?)))
+        .collect::<Result<Vec<SavedDoor>, String>>()?;
+
+    Ok(LevelState::new(player, enemies, doors)
+        .with_map(data.map)
+        .with_weapons(data.current_weapon.unwrap_or(0), data.ammo.unwrap_or_default())
+        .with_spawned(data.spawned.unwrap_or_default()))
+}
+
+fn load(content: String) -> Result<Json, String> {
+    serde_json::from_str(&content).map_err(|e| format!("Map configuration is not valid: {}", e))
+}
+
+fn to_conf(data: Json, resource_registry: &mut dyn ResourceRegistryLoader) -> Result<MapConfiguration, String> {
+    let player_conf = player_conf(data.player)?;
+    let spawn_orientation = spawn_conf(data.spawn)?;
+    let transparency = load_texture(resource_registry, String::from("transparency.png"))?;
+    let mut weapons = match data.weapon {
+        JsonWeapons::Single(weapon) => vec![*weapon],
+        JsonWeapons::Many(weapons) => weapons,
+    }.into_iter();
+    let shoot_configuration = weapon_conf(weapons.next().ok_or("At least one weapon is required")?, resource_registry)?;
 
-    let shoot_configuration = WeaponConfiguration::new(
-        resource_registry.load_texture(data.weapon.idle),
-        AnimationStep::new(data.weapon.startup.duration, resource_registry.load_texture(data.weapon.startup.texture)),
-        AnimationStep::new(data.weapon.active.duration, resource_registry.load_texture(data.weapon.active.texture)),
-        AnimationStep::new(data.weapon.recovery.duration, resource_registry.load_texture(data.weapon.recovery.texture)),
-        data.weapon.damage);
     let mut conf = MapConfiguration::new(transparency, player_conf, shoot_configuration);
+    for weapon in weapons {
+        conf.add_weapon(weapon_conf(weapon, resource_registry)?);
+    }
+
+    if let Some(metadata) = data.metadata {
+        let par_time = match metadata.par_time {
+            Some(par_time) => Some(non_negative("Map par time", par_time)?),
+            None => None,
+        };
+        conf.set_metadata(MapMetadata::new(metadata.name, metadata.author, par_time, metadata.description));
+    }
+
+    if let Some(ambient) = data.ambient {
+        conf.set_ambient(at_most("Ambient light", non_negative("Ambient light", ambient)?, 1.0)?);
+    }
+
+    if let Some(backdrop) = data.backdrop {
+        let height = at_most("Backdrop height", non_negative("Backdrop height", backdrop.height)?, 0.5)?;
+        conf.set_backdrop(Backdrop::new(load_texture(resource_registry, backdrop.texture)?, height));
+    }
+
+    if let Some(fog) = data.fog {
+        let distance = non_negative("Fog distance", fog.distance)?;
+        if distance == 0.0 {
+            return Err(String::from("Fog distance must not be zero"));
+        }
+        conf.set_fog(Fog::new(to_color(fog.color), distance));
+    }
+
+    if let Some(color) = data.ceiling_color {
+        conf.set_ceiling_color(to_color(color));
+    }
+
+    if let Some(color) = data.floor_color {
+        conf.set_floor_color(to_color(color));
+    }
+
+    conf.set_pad_short_rows(data.pad_short_rows.unwrap_or(false));
+    if let Some(prefix) = data.comment_prefix.filter(|prefix| !prefix.is_empty()) {
+        conf.set_comment_prefix(prefix);
+    }
 
     for tile in data.tiles {
         let texture = tile.texture
-            .map_or_else(
-                || transparency,
-                |id| resource_registry.load_texture(id));
-        let id_char = tile.id.as_bytes()[0] as char;
+            .map_or(
+                Ok(transparency),
+                |id| load_texture(resource_registry, id))?;
+        let id_char = tile.id.chars().next().ok_or("Tile id must not be empty")?;
+
+        if let Some(tint) = tile.tint {
+            conf.add_tint(id_char, to_color(tint));
+        }
+        if let Some(thickness) = tile.thickness {
+            conf.add_thickness(id_char, between(0.0, non_negative("Wall thickness", thickness)?, 1.0));
+        }
 
         if tile.tile_type == "NOTHING" {
             conf.add(id_char, crate::domain::topology::map::Tile::NOTHING)
@@ -89,44 +556,824 @@ fn to_conf(data: Json, resource_registry: &mut dyn ResourceRegistryLoader) -> Ma
         if tile.tile_type == "SOLID" {
             conf.add(id_char, crate::domain::topology::map::Tile::SOLID(texture))
         }
-        if tile.tile_type == "ENEMY" {
+        if tile.tile_type == "FIELD" {
+            let color = tile.color
+                .map(to_color)
+                .ok_or_else(|| format!("Tile '{}' is a field without color", tile.id))?;
+            conf.add(id_char, crate::domain::topology::map::Tile::FIELD(color))
+        }
+        if tile.tile_type == "ENEMY" || tile.tile_type == "SPAWNER" {
             let texture_dead = tile.texture_dead
-                .map_or_else(
-                    || transparency,
-                    |id| resource_registry.load_texture(id));
+                .map_or(
+                    Ok(transparency),
+                    |id| load_texture(resource_registry, id))?;
+
+            let health = tile.health
+                .ok_or_else(|| format!("Tile '{}' is an enemy without health", tile.id))?;
+            let enemy_type = EnemyType::new(texture, texture_dead, health);
+            let enemy_type = match tile.ranged {
+                Some(ranged) => enemy_type.with_ranged_attack(RangedAttack::new(
+                    load_texture(resource_registry, ranged.texture)?,
+                    SpeedStats::new(non_negative("Ranged attack speed", ranged.speed)?),
+                    ranged.damage,
+                    non_negative("Ranged attack cooldown", ranged.cooldown)?,
+                    non_negative("Ranged attack range", ranged.range)?,
+                )),
+                None => enemy_type,
+            };
+            let enemy_type = match tile.melee {
+                Some(melee) => enemy_type.with_melee_attack(MeleeAttack::new(
+                    melee.damage,
+                    non_negative("Melee attack cooldown", melee.cooldown)?,
+                    non_negative("Melee attack range", melee.range)?,
+                )),
+                None => enemy_type,
+            };
+            let enemy_type = match tile.perception {
+                Some(perception) => enemy_type.with_perception(Perception::new(
+                    non_negative("Enemy sight range", perception.sight_range)?,
+                    Angle::from_degree(between(0.0, non_negative("Enemy sight cone", perception.sight_cone_in_degrees)?, 360.0)),
+                    non_negative("Enemy hearing range", perception.hearing_range)?,
+                )),
+                None => enemy_type,
+            };
 
-            let health = tile.health.unwrap();
-            conf.add_enemy(id_char, EnemyType::new(texture, texture_dead, health));
+            if tile.tile_type == "SPAWNER" {
+                let interval = tile.interval
+                    .ok_or_else(|| format!("Tile '{}' is a spawner without interval", tile.id))
+                    .and_then(|interval| non_negative("Spawner interval", interval))?;
+                let max_count = tile.max_count
+                    .ok_or_else(|| format!("Tile '{}' is a spawner without max_count", tile.id))?;
+                conf.add_spawner(id_char, SpawnerType::new(enemy_type, interval, max_count));
+            } else {
+                conf.add_enemy(id_char, enemy_type);
+            }
+        }
+        if tile.tile_type == "CHECKPOINT" {
+            conf.add_checkpoint(id_char, tile.name.clone().unwrap_or_else(|| tile.id.clone()));
+        }
+        if tile.tile_type == "EXIT" {
+            let target = tile.target.clone()
+                .ok_or_else(|| format!("Tile '{}' is an exit without target", tile.id))?;
+            conf.add_exit(id_char, target);
+        }
+        if tile.tile_type == "TELEPORT" {
+            let link = tile.link.as_ref()
+                .and_then(|link| link.chars().next())
+                .ok_or_else(|| format!("Tile '{}' is a teleport without link", tile.id))?;
+            conf.add_teleport(id_char, link);
+        }
+        if tile.tile_type == "WEAPON" {
+            let weapon = tile.weapon
+                .ok_or_else(|| format!("Tile '{}' is a weapon pickup without weapon", tile.id))?;
+            conf.add_pickup(id_char, PickupType::new(texture, PickupKind::Weapon(Box::new(weapon_conf(weapon, resource_registry)?))));
+        }
+        if tile.tile_type == "PICKUP" {
+            let amount = || tile.amount
+                .ok_or_else(|| format!("Tile '{}' is a pickup without amount", tile.id));
+            let kind = match tile.subtype.as_deref() {
+                Some("HEALTH") => PickupKind::Health(amount()?),
+                Some("AMMO") => PickupKind::Ammo(amount()?),
+                Some("KEY") => PickupKind::Key(key_color(tile.key.as_deref()
+                    .ok_or_else(|| format!("Tile '{}' is a key pickup without key", tile.id))?)?),
+                _ => return Err(format!("Tile '{}' is a pickup without a known subtype", tile.id)),
+            };
+            conf.add_pickup(id_char, PickupType::new(texture, kind));
         }
         if tile.tile_type == "PLAYER" {
-            let angle = Angle::from_degree(tile.orientation_in_degrees.unwrap());
+            let angle = spawn_orientation.to_angle(tile.orientation_in_degrees)?;
             conf.add_spawn(id_char, SpawnPoint::new(angle));
         }
         if tile.tile_type == "DYNAMIC" {
+            let auto_close_after = match tile.state.as_ref().and_then(|state| state.auto_close_after) {
+                Some(delay) => Some(non_negative("Door auto close delay", delay)?),
+                None => None,
+            };
             let state = tile.state.map_or_else(
                 || {
                     ActionStateBuilder::new(Box::new(NothingActionState::new()))
                 },
                 |state| {
-                    let openable: Box<dyn Openable> = if state.opening_mode == "CENTER" {
-                        Box::new(CentralOpening::default())
+                    let openable: Box<dyn Openable> = match state.opening_mode.as_str() {
+                        "CENTER" => Box::new(CentralOpening::default()),
+                        "VERTICAL" => Box::new(VerticalOpening::default()),
+                        _ => Box::new(LateralOpening::default()),
+                    };
+                    let passable_threshold = between(0.0, state.passable_threshold.unwrap_or(1.0), 1.0);
+                    let door = LinearActionState::new(SpeedStats::new(state.speed), openable)
+                        .with_passable_threshold(passable_threshold);
+                    let door = if state.auto_open.unwrap_or(false) {
+                        door.with_auto_open(state.trigger_radius.unwrap_or(DEFAULT_TRIGGER_RADIUS))
                     } else {
-                        Box::new(LateralOpening::default())
+                        door
                     };
-                    ActionStateBuilder::new(Box::new(LinearActionState::new(SpeedStats::new(state.speed), openable)))
+                    let door = match auto_close_after {
+                        Some(delay) => door.with_auto_close(delay),
+                        None => door,
+                    };
+                    if state.state_type == "EASED" {
+                        ActionStateBuilder::new(Box::new(EasedActionState::new(Box::new(door))))
+                    } else {
+                        ActionStateBuilder::new(Box::new(door))
+                    }
                 });
 
+            if let Some(locked) = tile.locked {
+                conf.add_lock(id_char, key_color(&locked)?);
+            }
             conf.add(id_char, crate::domain::topology::map::Tile::DYNAMIC(texture, transparency, state))
         }
     }
 
-    conf
+    Ok(conf)
+}
+
+fn modifiers(names: &[String]) -> Result<Modifiers, String> {
+    let mut alt = false;
+    let mut ctrl = false;
+    let mut shift = false;
+    for name in names {
+        match name.as_str() {
+            "ALT" => alt = true,
+            "CTRL" => ctrl = true,
+            "SHIFT" => shift = true,
+            other => return Err(format!("Unknown modifier '{}'", other)),
+        }
+    }
+
+    Ok(Modifiers::new(alt, ctrl, shift))
+}
+
+fn to_color(data: JsonColor) -> Color {
+    Color::new(data.red, data.green, data.blue)
+}
+
+fn key_color(name: &str) -> Result<KeyColor, String> {
+    match name {
+        "RED" => Ok(KeyColor::Red),
+        "YELLOW" => Ok(KeyColor::Yellow),
+        "BLUE" => Ok(KeyColor::Blue),
+        other => Err(format!("Unknown key color '{}'", other)),
+    }
+}
+
+fn spawn_conf(data: Option<Spawn>) -> Result<SpawnOrientation, String> {
+    data.map_or_else(
+        || Ok(SpawnOrientation::default()),
+        |spawn| {
+            let mode = match spawn.orientation_mode.as_str() {
+                "FREE" => SpawnOrientationMode::Free,
+                "SNAP" => SpawnOrientationMode::Snap,
+                other => return Err(format!("Unknown orientation mode '{}'", other)),
+            };
+            Ok(SpawnOrientation::new(mode, spawn.default_orientation_in_degrees))
+        })
+}
+
+fn load_texture(resource_registry: &mut dyn ResourceRegistryLoader, file: String) -> Result<TextureIndex, String> {
+    resource_registry.load_texture(file).map_err(|e| e.to_string())
+}
+
+fn weapon_conf(data: Weapon, resource_registry: &mut dyn ResourceRegistryLoader) -> Result<WeaponConfiguration, String> {
+    let startup = AnimationStep::new(non_negative("Weapon startup duration", data.startup.duration)?, load_texture(resource_registry, data.startup.texture)?);
+    let active = AnimationStep::new(non_negative("Weapon active duration", data.active.duration)?, load_texture(resource_registry, data.active.texture)?);
+    let recovery = AnimationStep::new(non_negative("Weapon recovery duration", data.recovery.duration)?, load_texture(resource_registry, data.recovery.texture)?);
+    let knockback = non_negative("Weapon knockback", data.knockback.unwrap_or(0.0))?;
+    let view_model = match data.view_model {
+        Some(view_model) => ViewModel::new(
+            between(0.0, finite("Weapon view model x", view_model.x)?, 1.0),
+            between(0.0, finite("Weapon view model y", view_model.y)?, 1.0),
+            between(0.0, finite("Weapon view model width", view_model.width)?, 1.0),
+            between(0.0, finite("Weapon view model height", view_model.height)?, 1.0),
+        ),
+        None => ViewModel::default(),
+    };
+
+    let kind = match data.kind.as_deref() {
+        None | Some("MELEE") => match (data.range, melee_angle(data.angle_in_degrees, data.arc_degrees)?) {
+            (None, None) => WeaponKind::default(),
+            (range, angle) => WeaponKind::Melee {
+                range: non_negative("Melee weapon range", range.unwrap_or(0.5))?,
+                angle: Angle::from_degree(non_negative("Melee weapon angle", angle.unwrap_or(45.0))?),
+            },
+        },
+        Some("HITSCAN") => {
+            let range = non_negative("Hitscan weapon range", data.range.ok_or("Hitscan weapon requires a range")?)?;
+            match data.radius {
+                Some(radius) => WeaponKind::Hitscan { range, radius: non_negative("Hitscan weapon radius", radius)? },
+                None => WeaponKind::hitscan(range),
+            }
+        }
+        Some("PROJECTILE") => WeaponKind::Projectile {
+            speed: non_negative("Projectile weapon speed", data.speed.ok_or("Projectile weapon requires a speed")?)?,
+            texture: load_texture(resource_registry, data.projectile.ok_or("Projectile weapon requires a projectile texture")?)?,
+        },
+        Some(other) => return Err(format!("Unknown weapon kind '{}'", other)),
+    };
+
+    let weapon = WeaponConfiguration::new(load_texture(resource_registry, data.idle)?, startup, active, recovery, data.damage)
+        .with_knockback(knockback)
+        .with_view_model(view_model)
+        .with_kind(kind);
+    let weapon = match data.ammo {
+        Some(ammo) => weapon.with_ammo(ammo),
+        None => weapon,
+    };
+
+    Ok(match data.muzzle_flash {
+        Some(muzzle_flash) => weapon.with_muzzle_flash(load_texture(resource_registry, muzzle_flash)?),
+        None => weapon,
+    })
+}
+
+fn player_conf(data: JsonPlayer) -> Result<PlayerStats, String> {
+    let stamina = match data.stamina {
+        Some(stamina) => StaminaStats::new(
+            non_negative("Player stamina maximum", stamina.maximum)?,
+            SpeedStats::new(non_negative("Player stamina drain", stamina.drain)?),
+            SpeedStats::new(non_negative("Player stamina regeneration", stamina.regeneration)?),
+        ),
+        None => StaminaStats::default(),
+    };
+    let run_factor = non_negative("Player run factor", data.run_factor.unwrap_or(1.0))?.max(1.0);
+
+    let stats = PlayerStats::new(
+        AccelerationStats::new(non_negative("Player acceleration", data.acceleration)?),
+        AccelerationStats::new(non_negative("Player deceleration", data.deceleration)?),
+        SpeedStats::new(non_negative("Player maximum speed", data.maximum_speed)?),
+    ).with_running(run_factor, stamina);
+
+    let stats = match data.collision_radius {
+        Some(radius) => stats.with_collision_radius(non_negative("Player collision radius", radius)?),
+        None => stats,
+    };
+
+    Ok(match data.health {
+        Some(0) => return Err(String::from("Player health must be greater than zero")),
+        Some(health) => stats.with_health(health),
+        None => stats,
+    })
+}
+
+fn melee_angle(angle_in_degrees: Option<f32>, arc_degrees: Option<f32>) -> Result<Option<f32>, String> {
+    match (angle_in_degrees, arc_degrees) {
+        (Some(_), Some(_)) => Err(String::from("Melee weapon accepts either angle_in_degrees or arc_degrees, not both")),
+        (angle, arc) => Ok(angle.or(arc.map(|arc| arc / 2.0))),
+    }
+}
+
+fn finite(name: &str, value: f32) -> Result<f32, String> {
+    if value.is_finite() {
+        Ok(value)
+    } else {
+        Err(format!("{} must be a finite number, got {}", name, value))
+    }
+}
+
+fn non_negative(name: &str, value: f32) -> Result<f32, String> {
+    let value = finite(name, value)?;
+    if value < 0.0 {
+        return Err(format!("{} must not be negative, got {}", name, value));
+    }
+    Ok(value)
+}
+
+fn at_most(name: &str, value: f32, max: f32) -> Result<f32, String> {
+    if value > max {
+        return Err(format!("{} must be at most {}, got {}", name, max, value));
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod stats_validation_test {
+    use spectral::prelude::*;
+
+    use crate::infrastructure::fs::json::{JsonPlayer, player_conf};
+
+    #[test]
+    fn negative_maximum_speed_should_be_rejected() {
+        let stats = player_conf(build_player(70.0, 40.0, -6.0));
+
+        assert_that!(stats.err()).is_some().is_equal_to(String::from("Player maximum speed must not be negative, got -6"));
+    }
+
+    #[test]
+    fn nan_acceleration_should_be_rejected() {
+        let stats = player_conf(build_player(f32::NAN, 40.0, 6.0));
+
+        assert_that!(stats.err()).is_some().is_equal_to(String::from("Player acceleration must be a finite number, got NaN"));
+    }
+
+    #[test]
+    fn run_factor_below_one_should_be_clamped() {
+        let mut player = build_player(70.0, 40.0, 6.0);
+        player.run_factor = Some(0.5);
+
+        let stats = player_conf(player);
+
+        assert_that!(stats.map(|stats| stats.run_factor())).is_ok().is_equal_to(1.0);
+    }
+
+    #[test]
+    fn configured_health_should_be_loaded() {
+        let mut player = build_player(70.0, 40.0, 6.0);
+        player.health = Some(250);
+
+        let stats = player_conf(player);
+
+        assert_that!(stats.map(|stats| stats.health())).is_ok().is_equal_to(250);
+    }
+
+    #[test]
+    fn collision_radius_should_default_and_be_loaded() {
+        let mut player = build_player(70.0, 40.0, 6.0);
+        player.collision_radius = Some(0.3);
+
+        let default = player_conf(build_player(70.0, 40.0, 6.0));
+        let configured = player_conf(player);
+
+        assert_that!(default.map(|stats| stats.collision_radius())).is_ok().is_equal_to(0.1);
+        assert_that!(configured.map(|stats| stats.collision_radius())).is_ok().is_equal_to(0.3);
+    }
+
+    #[test]
+    fn zero_health_should_be_rejected() {
+        let mut player = build_player(70.0, 40.0, 6.0);
+        player.health = Some(0);
+
+        let stats = player_conf(player);
+
+        assert_that!(stats.err()).is_some().is_equal_to(String::from("Player health must be greater than zero"));
+    }
+
+    fn build_player(acceleration: f32, deceleration: f32, maximum_speed: f32) -> JsonPlayer {
+        JsonPlayer {
+            acceleration,
+            deceleration,
+            maximum_speed,
+            run_factor: None,
+            stamina: None,
+            health: None,
+            collision_radius: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod weapon_kind_test {
+    use std::f32::consts::PI;
+
+    use spectral::prelude::*;
+
+    use crate::domain::actors::shoot::WeaponKind;
+    use crate::domain::error::EngineError;
+    use crate::domain::resources::ResourceRegistryLoader;
+    use crate::domain::topology::index::{FontIndex, TextureIndex};
+    use crate::infrastructure::fs::json::{weapon_conf, Weapon};
+
+    const WEAPON: &str = r#"{
+        "idle": "sword.png",
+        "damage": 30,
+        "startup": {"texture": "sword_startup.png", "duration": 0.1},
+        "active": {"texture": "sword_active.png", "duration": 0.1},
+        "recovery": {"texture": "sword_recovery.png", "duration": 0.1}
+    }"#;
+
+    pub(super) struct CountingRegistry {
+        pub(super) loaded: u128,
+    }
+
+    impl ResourceRegistryLoader for CountingRegistry {
+        fn load_texture(&mut self, _file: String) -> Result<TextureIndex, EngineError> {
+            self.loaded += 1;
+            Ok(TextureIndex::new(self.loaded))
+        }
+
+        fn load_font(&mut self, _filename: String) -> Result<FontIndex, EngineError> {
+            Ok(FontIndex::new(0))
+        }
+    }
+
+    struct MissingRegistry;
+
+    impl ResourceRegistryLoader for MissingRegistry {
+        fn load_texture(&mut self, file: String) -> Result<TextureIndex, EngineError> {
+            Err(EngineError::Resource(file))
+        }
+
+        fn load_font(&mut self, filename: String) -> Result<FontIndex, EngineError> {
+            Err(EngineError::Resource(filename))
+        }
+    }
+
+    #[test]
+    fn missing_weapon_texture_should_be_an_error() {
+        let weapon: Weapon = serde_json::from_str(WEAPON).unwrap();
+
+        assert!(weapon_conf(weapon, &mut MissingRegistry).is_err());
+    }
+
+    #[test]
+    fn weapon_without_kind_should_be_melee() {
+        let kind = kind_of(r#""damage": 30"#);
+
+        assert!(matches!(kind, Ok(WeaponKind::Melee { .. })));
+    }
+
+    #[test]
+    fn melee_arc_should_be_split_on_both_sides_of_the_aim() {
+        let kind = kind_of(r#""damage": 30, "range": 1.2, "arc_degrees": 120.0"#);
+
+        assert!(matches!(kind, Ok(WeaponKind::Melee { range, angle }) if range == 1.2 && (angle.to_radiant() - PI / 3.0).abs() < 0.001));
+    }
+
+    #[test]
+    fn melee_angle_and_arc_together_should_be_rejected() {
+        let kind = kind_of(r#""damage": 30, "angle_in_degrees": 45.0, "arc_degrees": 120.0"#);
+
+        assert_that!(kind.err()).is_some().is_equal_to(String::from("Melee weapon accepts either angle_in_degrees or arc_degrees, not both"));
+    }
+
+    #[test]
+    fn should_read_hitscan_weapon_range() {
+        let kind = kind_of(r#""damage": 30, "kind": "HITSCAN", "range": 12.0"#);
+
+        assert!(matches!(kind, Ok(WeaponKind::Hitscan { range, radius }) if range == 12.0 && radius == 0.5));
+    }
+
+    #[test]
+    fn should_read_hitscan_weapon_radius() {
+        let kind = kind_of(r#""damage": 30, "kind": "HITSCAN", "range": 12.0, "radius": 0.8"#);
+
+        assert!(matches!(kind, Ok(WeaponKind::Hitscan { radius, .. }) if radius == 0.8));
+    }
+
+    #[test]
+    fn should_read_projectile_weapon_speed() {
+        let kind = kind_of(r#""damage": 30, "kind": "PROJECTILE", "speed": 8.0, "projectile": "bolt.png""#);
+
+        assert!(matches!(kind, Ok(WeaponKind::Projectile { speed, .. }) if speed == 8.0));
+    }
+
+    #[test]
+    fn hitscan_weapon_without_range_should_be_rejected() {
+        let kind = kind_of(r#""damage": 30, "kind": "HITSCAN""#);
+
+        assert_that!(kind.err()).is_some().is_equal_to(String::from("Hitscan weapon requires a range"));
+    }
+
+    fn kind_of(fields: &str) -> Result<WeaponKind, String> {
+        let data: Weapon = serde_json::from_str(&WEAPON.replace(r#""damage": 30"#, fields)).unwrap();
+        weapon_conf(data, &mut CountingRegistry { loaded: 0 }).map(|weapon| weapon.kind())
+    }
 }
 
-fn player_conf(data: JsonPlayer) -> PlayerStats {
-    PlayerStats::new(
-        AccelerationStats::new(data.acceleration),
-        AccelerationStats::new(data.deceleration),
-        SpeedStats::new(data.maximum_speed),
-    )
-}
\ No newline at end of file
+#[cfg(test)]
+mod map_configuration_test {
+    use crate::domain::error::EngineError;
+    use crate::infrastructure::fs::json::load_configuration;
+    use crate::infrastructure::fs::json::weapon_kind_test::CountingRegistry;
+
+    const CONFIGURATION: &str = r#"{
+        "player": { "acceleration": 1.0, "deceleration": 1.0, "maximum_speed": 1.0 },
+        "weapon": {
+            "idle": "sword.png",
+            "damage": 30,
+            "startup": {"texture": "sword_startup.png", "duration": 0.1},
+            "active": {"texture": "sword_active.png", "duration": 0.1},
+            "recovery": {"texture": "sword_recovery.png", "duration": 0.1}
+        },
+        "tiles": [TILES]
+    }"#;
+
+    #[test]
+    fn enemy_without_health_should_be_rejected() {
+        let content = CONFIGURATION.replace("TILES", r#"{ "id": "E", "tile_type": "ENEMY", "texture": "enemy.png" }"#);
+
+        let configuration = load_configuration(content, &mut CountingRegistry { loaded: 0 });
+
+        assert_eq!(configuration.err(), Some(EngineError::Configuration(String::from("Tile 'E' is an enemy without health"))));
+    }
+
+    #[test]
+    fn negative_enemy_melee_range_should_be_rejected() {
+        let content = CONFIGURATION.replace("TILES", r#"{ "id": "E", "tile_type": "ENEMY", "texture": "enemy.png", "health": 50,
+            "melee": { "damage": 15, "cooldown": 0.5, "range": -1.0 } }"#);
+
+        let configuration = load_configuration(content, &mut CountingRegistry { loaded: 0 });
+
+        assert_eq!(configuration.err(), Some(EngineError::Configuration(String::from("Melee attack range must not be negative, got -1"))));
+    }
+
+    #[test]
+    fn empty_tile_id_should_be_rejected() {
+        let content = CONFIGURATION.replace("TILES", r#"{ "id": "", "tile_type": "NOTHING" }"#);
+
+        let configuration = load_configuration(content, &mut CountingRegistry { loaded: 0 });
+
+        assert_eq!(configuration.err(), Some(EngineError::Configuration(String::from("Tile id must not be empty"))));
+    }
+
+    #[test]
+    fn unknown_spawn_orientation_mode_should_be_rejected() {
+        let content = CONFIGURATION
+            .replace("TILES", "")
+            .replace(r#""tiles""#, r#""spawn": { "orientation_mode": "SNAPPED", "default_orientation_in_degrees": 0.0 }, "tiles""#);
+
+        let configuration = load_configuration(content, &mut CountingRegistry { loaded: 0 });
+
+        assert_eq!(configuration.err(), Some(EngineError::Configuration(String::from("Unknown orientation mode 'SNAPPED'"))));
+    }
+
+    #[test]
+    fn ambient_light_above_one_should_be_rejected() {
+        let content = CONFIGURATION
+            .replace("TILES", "")
+            .replace(r#""tiles""#, r#""ambient": 1.5, "tiles""#);
+
+        let configuration = load_configuration(content, &mut CountingRegistry { loaded: 0 });
+
+        assert_eq!(configuration.err(), Some(EngineError::Configuration(String::from("Ambient light must be at most 1, got 1.5"))));
+    }
+
+    #[test]
+    fn backdrop_higher_than_half_the_screen_should_be_rejected() {
+        let content = CONFIGURATION
+            .replace("TILES", "")
+            .replace(r#""tiles""#, r#""backdrop": { "texture": "sky.png", "height": 0.8 }, "tiles""#);
+
+        let configuration = load_configuration(content, &mut CountingRegistry { loaded: 0 });
+
+        assert_eq!(configuration.err(), Some(EngineError::Configuration(String::from("Backdrop height must be at most 0.5, got 0.8"))));
+    }
+
+    #[test]
+    fn negative_door_auto_close_delay_should_be_rejected() {
+        let content = CONFIGURATION.replace("TILES", r#"{ "id": "D", "tile_type": "DYNAMIC", "texture": "door.png",
+            "state": { "state_type": "LINEAR", "opening_mode": "LATERAL", "speed": 1.0, "auto_close_after": -2.0 } }"#);
+
+        let configuration = load_configuration(content, &mut CountingRegistry { loaded: 0 });
+
+        assert_eq!(configuration.err(), Some(EngineError::Configuration(String::from("Door auto close delay must not be negative, got -2"))));
+    }
+
+    #[test]
+    fn invalid_json_should_be_rejected() {
+        let configuration = load_configuration(String::from("{ not json"), &mut CountingRegistry { loaded: 0 });
+
+        assert!(matches!(configuration, Err(EngineError::Configuration(_))));
+    }
+
+    #[test]
+    fn valid_configuration_should_be_loaded() {
+        let content = CONFIGURATION.replace("TILES", r#"{ "id": "E", "tile_type": "ENEMY", "texture": "enemy.png", "health": 50 }"#);
+
+        let configuration = load_configuration(content, &mut CountingRegistry { loaded: 0 });
+
+        assert!(configuration.is_ok());
+    }
+}
+
+#[cfg(test)]
+mod game_configuration_test {
+    use spectral::prelude::*;
+
+    use crate::domain::control::bindings::Modifiers;
+    use crate::domain::control::input::Input;
+    use crate::domain::error::EngineError;
+    use crate::domain::resources::ResourceLoader;
+    use crate::domain::ui::view::AspectMode;
+    use crate::infrastructure::fs::json::load_game_configuration;
+
+    const GAME: &str = r#"{
+        "window": { "width": 640, "height": 480, "fov_in_degrees": 90 },
+        "input": { "movement_speed": 0.004, "rotation_speed": 0.005 },
+        "font": "font.otf",
+        "configuration": "conf.json",
+        "map": "start.map"
+    }"#;
+
+    #[test]
+    fn should_read_view_dimensions() {
+        let loader = ResourceLoader::new(|_| Ok(String::from(GAME).into_bytes()), |_| String::from(""));
+
+        let game = load_game_configuration(loader.load_as_string(String::from("game.json")).unwrap()).unwrap();
+
+        assert_that!(game.view().width()).is_equal_to(640);
+        assert_that!(game.view().height()).is_equal_to(480);
+        assert_that!(game.view().angle().to_radiant()).is_close_to(std::f32::consts::PI / 2.0, 0.001);
+    }
+
+    #[test]
+    fn should_read_starting_map_and_resources() {
+        let loader = ResourceLoader::new(|_| Ok(String::from(GAME).into_bytes()), |_| String::from(""));
+
+        let game = load_game_configuration(loader.load_as_string(String::from("game.json")).unwrap()).unwrap();
+
+        assert_that!(game.map()).is_equal_to(String::from("start.map"));
+        assert_that!(game.configuration()).is_equal_to(String::from("conf.json"));
+        assert_that!(game.font()).is_equal_to(String::from("font.otf"));
+        assert_that!(game.input_force().movement_speed()).is_equal_to(0.004);
+    }
+
+    #[test]
+    fn should_maintain_aspect_by_default() {
+        let game = load_game_configuration(String::from(GAME)).unwrap();
+
+        assert_that!(game.aspect_mode()).is_equal_to(AspectMode::Maintain);
+    }
+
+    #[test]
+    fn should_not_read_render_scale_above_one() {
+        let content = GAME.replace(r#""fov_in_degrees": 90"#, r#""fov_in_degrees": 90, "render_scale": 2.0"#);
+
+        assert_that!(load_game_configuration(content).err()).is_some();
+    }
+
+    #[test]
+    fn should_not_read_unknown_aspect_mode() {
+        let content = GAME.replace(r#""fov_in_degrees": 90"#, r#""fov_in_degrees": 90, "aspect_mode": "STRETCH""#);
+
+        assert_that!(load_game_configuration(content).err()).is_some();
+    }
+
+    #[test]
+    fn should_read_custom_key_bindings() {
+        let content = GAME.replace(
+            r#""rotation_speed": 0.005"#,
+            r#""rotation_speed": 0.005, "bindings": [{ "input": "FORWARD", "key": "Up" }, { "input": "FULLSCREEN", "key": "F", "modifiers": ["CTRL"] }]"#,
+        );
+
+        let bindings = load_game_configuration(content).unwrap().key_bindings();
+
+        assert_that!(bindings.resolve_held(String::from("Up"), Modifiers::none())).is_equal_to(Some(Input::Forward));
+        assert_that!(bindings.resolve_pressed(String::from("F"), Modifiers::ctrl())).is_equal_to(Some(Input::ToggleFullscreen));
+    }
+
+    #[test]
+    fn should_read_vsync_and_fps_cap() {
+        let content = GAME.replace(r#""fov_in_degrees": 90"#, r#""fov_in_degrees": 90, "vsync": false, "fps_cap": 144"#);
+
+        let game = load_game_configuration(content).unwrap();
+
+        assert_that!(game.vsync()).is_false();
+        assert_that!(game.fps_cap()).is_equal_to(Some(144));
+    }
+
+    #[test]
+    fn should_enable_vsync_without_cap_by_default() {
+        let game = load_game_configuration(String::from(GAME)).unwrap();
+
+        assert_that!(game.vsync()).is_true();
+        assert_that!(game.fps_cap()).is_none();
+    }
+
+    #[test]
+    fn should_read_mouse_sensitivity_and_inversion() {
+        let content = GAME.replace(r#""rotation_speed": 0.005"#, r#""rotation_speed": 0.005, "mouse_sensitivity": 2.0, "invert_rotation": true"#);
+
+        let input_force = load_game_configuration(content).unwrap().input_force();
+
+        assert_that!(input_force.sensitivity()).is_equal_to(2.0);
+        assert_that!(input_force.rotate(10).rotation().to_radiant()).is_close_to(0.1, 0.0001);
+    }
+
+    #[test]
+    fn should_not_read_negative_mouse_sensitivity() {
+        let content = GAME.replace(r#""rotation_speed": 0.005"#, r#""rotation_speed": 0.005, "mouse_sensitivity": -1.0"#);
+
+        assert_that!(load_game_configuration(content).err()).is_some();
+    }
+
+    #[test]
+    fn should_not_read_aim_assist_strength_above_one() {
+        let content = GAME.replace(r#""rotation_speed": 0.005"#, r#""rotation_speed": 0.005, "aim_assist_in_degrees": 10.0, "aim_assist_strength": 1.5"#);
+
+        assert_that!(load_game_configuration(content).err()).is_some();
+    }
+
+    #[test]
+    fn should_not_read_unknown_input_binding() {
+        let content = GAME.replace(r#""rotation_speed": 0.005"#, r#""rotation_speed": 0.005, "bindings": [{ "input": "JUMP", "key": "Space" }]"#);
+
+        assert_that!(load_game_configuration(content).err()).is_some();
+    }
+
+    #[test]
+    fn should_not_read_invalid_game_configuration() {
+        let game = load_game_configuration(String::from("{}"));
+
+        assert!(matches!(game, Err(EngineError::Configuration(_))));
+    }
+}
+
+#[cfg(test)]
+mod level_state_test {
+    use spectral::prelude::*;
+
+    use crate::domain::actors::actor::EnemyOrigin;
+    use crate::domain::actors::spawner::SpawnerType;
+    use crate::domain::level::Level;
+    use crate::domain::maths::ANGLE_90;
+    use crate::domain::save::{LevelState, SavedEnemy};
+    use crate::domain::topology::coord::Position;
+    use crate::domain::topology::index::TextureIndex;
+    use crate::domain::topology::map::EnemyType;
+    use crate::domain::topology::map::map_test::{build_map, build_map_with_configuration, configuration_with_weapon, default_configuration, default_weapon};
+    use crate::domain::ui::view::ViewScreen;
+    use crate::infrastructure::fs::json::{load_level_state, save_level_state};
+
+    #[test]
+    fn quick_save_then_quick_load_should_restore_player_and_doors() {
+        let mut level = level();
+        level.handle_action();
+        level.notify_elapsed(100000);
+        level.teleport(Position::new(1.2, 0.7));
+        let saved = save_level_state(&level.save_state()).unwrap();
+
+        let mut restored = self::level();
+        restored.load_state(&load_level_state(saved).unwrap()).unwrap();
+
+        assert_that!(restored.player().position().x()).is_close_to(1.2, 0.001);
+        assert_that!(restored.player().position().y()).is_close_to(0.7, 0.001);
+        assert_that!(restored.save_state().doors().to_vec()).is_equal_to(level.save_state().doors().to_vec());
+        assert_that!(restored.save_state().doors()[0].opening_percentage()).is_greater_than(0.0);
+    }
+
+    #[test]
+    fn quick_save_then_quick_load_should_restore_enemies_and_weapons() {
+        let mut configuration = configuration_with_weapon(default_weapon().with_ammo(5));
+        configuration.add_weapon(default_weapon().with_ammo(8));
+        let build = || {
+            let mut level = Level::new(ViewScreen::new(100, 100, ANGLE_90), build_map_with_configuration("#rD E#", configuration.clone())).unwrap();
+            level.notify_elapsed(1000000);
+            level
+        };
+        let mut level = build();
+        level.handle_shoot();
+        level.notify_elapsed(1000000);
+        level.select_weapon(1);
+        let state = level.save_state();
+        let state = LevelState::new(state.player(), vec![SavedEnemy::new(EnemyOrigin::Map(0), Position::new(4.2, 0.3), 40)], state.doors().to_vec())
+            .with_weapons(state.current_weapon(), state.ammo().to_vec());
+        let saved = save_level_state(&state).unwrap();
+
+        let mut restored = build();
+        restored.load_state(&load_level_state(saved).unwrap()).unwrap();
+
+        assert_that!(restored.weapons().iter().map(|weapon| weapon.ammo()).collect::<Vec<Option<u32>>>()).is_equal_to(vec![Some(4), Some(8)]);
+        assert_that!(restored.current_weapon().ammo()).is_equal_to(Some(8));
+        assert_that!(restored.enemies()[0].position().x()).is_close_to(4.2, 0.001);
+        assert_that!(restored.enemies()[0].position().y()).is_close_to(0.3, 0.001);
+        assert_that!(restored.enemies()[0].health()).is_equal_to(40);
+    }
+
+    #[test]
+    fn quick_load_should_rebuild_spawned_enemies_and_spawner_counts() {
+        let mut configuration = default_configuration();
+        configuration.add_spawner('S', SpawnerType::new(EnemyType::new(TextureIndex::new(5), TextureIndex::new(5), 100), 1.0, 2));
+        let build = || Level::new(ViewScreen::new(100, 100, ANGLE_90), build_map_with_configuration("#r   S#", configuration.clone())).unwrap();
+        let mut level = build();
+        level.notify_elapsed(1000000);
+        let saved = save_level_state(&level.save_state()).unwrap();
+
+        let mut restored = build();
+        restored.notify_elapsed(1000000);
+        restored.notify_elapsed(1000000);
+        restored.load_state(&load_level_state(saved).unwrap()).unwrap();
+
+        assert_that!(restored.enemies().len()).is_equal_to(1);
+        assert_that!(restored.enemies()[0].origin()).is_equal_to(EnemyOrigin::Spawner(0));
+        assert_that!(restored.save_state().spawned().to_vec()).is_equal_to(vec![1]);
+    }
+
+    #[test]
+    fn quick_load_should_reject_a_save_from_another_map() {
+        let level = level().with_map_name(String::from("first.map"));
+        let saved = save_level_state(&level.save_state()).unwrap();
+
+        let mut other = self::level().with_map_name(String::from("second.map"));
+
+        assert_that!(other.load_state(&load_level_state(saved).unwrap())).is_err();
+    }
+
+    #[test]
+    fn save_without_weapons_should_still_load() {
+        let save = r#"{ "player": { "x": 1.5, "y": 0.5, "orientation": 0.0, "health": 100 }, "enemies": [], "doors": [] }"#;
+
+        let state = load_level_state(String::from(save)).unwrap();
+
+        assert_that!(state.current_weapon()).is_equal_to(0);
+        assert_that!(state.ammo().to_vec()).is_empty();
+    }
+
+    #[test]
+    fn corrupt_save_should_be_rejected() {
+        assert_that!(load_level_state(String::from("{ not a save"))).is_err();
+    }
+
+    fn level() -> Level {
+        Level::new(ViewScreen::new(100, 100, ANGLE_90), build_map("#rD #")).unwrap()
+    }
+}
@@ -1,2 +1,3 @@
+#[cfg(feature = "sdl")]
 pub mod sdl;
 pub mod fs;
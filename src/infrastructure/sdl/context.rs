@@ -1,4 +1,5 @@
 use sdl2::{
+    controller::GameController,
     render::WindowCanvas,
     video::{DisplayMode, FullscreenType},
     EventPump,
@@ -8,10 +9,11 @@ use crate::domain::ui::view::ViewScreen;
 pub struct SdlContext {
     canvas: WindowCanvas,
     event_pump: EventPump,
+    controller: Option<GameController>,
 }
 
 impl SdlContext {
-    pub fn new(view: ViewScreen) -> Result<Self, String> {
+    pub fn new(view: ViewScreen, vsync: bool) -> Result<Self, String> {
         let sdl_context = sdl2::init()?;
 
         let video_subsystem = sdl_context.video()?;
@@ -23,6 +25,7 @@ impl SdlContext {
                 view.height() as u32,
             )
             .position_centered()
+            .resizable()
             .build()
             .expect("could not initialize video subsystem");
 
@@ -33,17 +36,24 @@ impl SdlContext {
             60,
         ))?;
 
-        let canvas = window
-            .into_canvas()
-            .present_vsync()
+        let canvas = window.into_canvas();
+        let canvas = if vsync { canvas.present_vsync() } else { canvas };
+        let mut canvas = canvas
             .build()
             .expect("could not make a canvas");
+        canvas.set_logical_size(view.width() as u32, view.height() as u32)
+            .map_err(|e| e.to_string())?;
 
         let event_pump = sdl_context.event_pump()?;
 
         sdl_context.mouse().set_relative_mouse_mode(true);
 
-        Ok(Self { canvas, event_pump })
+        let controllers = sdl_context.game_controller()?;
+        let controller = (0..controllers.num_joysticks()?)
+            .filter(|index| controllers.is_game_controller(*index))
+            .find_map(|index| controllers.open(index).ok());
+
+        Ok(Self { canvas, event_pump, controller })
     }
 
     pub fn canvas(&mut self) -> &mut WindowCanvas {
@@ -54,6 +64,16 @@ impl SdlContext {
         &mut self.event_pump
     }
 
+    pub fn controller(&self) -> Option<&GameController> {
+        self.controller.as_ref()
+    }
+
+    pub fn set_logical_size(&mut self, view: ViewScreen) {
+        self.canvas
+            .set_logical_size(view.width() as u32, view.height() as u32)
+            .expect("Unable to change logical size");
+    }
+
     pub fn toggle_fullscreen(&mut self) {
         let state = self.canvas().window().fullscreen_state();
         if state == FullscreenType::True {
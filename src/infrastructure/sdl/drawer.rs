@@ -1,21 +1,24 @@
 use sdl2::pixels::Color;
+use sdl2::render::BlendMode;
 use sdl2::rect::Rect;
 
 use crate::domain::topology::coord::ScreenPoint;
 use crate::domain::topology::index::{FontIndex, TextureIndex};
+use crate::domain::ui::color::Color as UiColor;
 use crate::domain::ui::draw_action::DrawAction;
 
 use super::context::SdlContext;
 use super::texture::ResourceRegistry;
 
-pub fn draw(context: &mut SdlContext, registry: &ResourceRegistry, actions: Vec<DrawAction>) {
+pub fn draw(context: &mut SdlContext, registry: &mut ResourceRegistry, actions: Vec<DrawAction>) {
     let canvas = context.canvas();
 
     for action in actions.iter() {
         match action {
             DrawAction::Rectangle(start, end, color) => draw_rectangle(canvas, color, start, end),
             DrawAction::Line(start, end, color) => draw_line(canvas, color, start, end),
-            DrawAction::TexturedLine(start, end, texture_index, position_on_texture) => {
+            DrawAction::TexturedLine(start, end, texture_index, position_on_texture, tint, shade) => {
+                // Color modulation only darkens, so the shade filters the texture rather than covering it
                 draw_textured_line(
                     canvas,
                     position_on_texture,
@@ -23,12 +26,16 @@ pub fn draw(context: &mut SdlContext, registry: &ResourceRegistry, actions: Vec<
                     end,
                     registry,
                     *texture_index,
+                    &shade.apply(*tint),
                 )
             }
             DrawAction::Clear(color) => clear_screen(canvas, color),
-            DrawAction::Text(text, start, end, font) => draw_text(canvas, registry, text, start, end, font),
-            DrawAction::Sprite(start, end, texture) => {
-                draw_sprite(canvas, *start, *end, registry, *texture)
+            DrawAction::Text(text, start, end, font, color) => draw_text(canvas, registry, text, start, end, font, color),
+            DrawAction::Sprite(start, end, texture, tint) => {
+                draw_sprite(canvas, *start, *end, registry, *texture, *tint)
+            }
+            DrawAction::AdditiveSprite(start, end, texture) => {
+                draw_additive_sprite(canvas, *start, *end, registry, *texture)
             }
         }
     }
@@ -44,14 +51,15 @@ fn draw_text(
     text: &str,
     start: &ScreenPoint,
     end: &ScreenPoint,
-    font: &FontIndex
+    font: &FontIndex,
+    color: &UiColor,
 ) {
     let display_zone = to_sdl_rect(start, end);
     let font = registry.get_font(*font).unwrap();
 
     let surface = font
         .render(text)
-        .blended(Color::RGBA(255, 0, 0, 255))
+        .blended(to_sdl_color(color))
         .unwrap();
     let texture_creator = canvas.texture_creator();
     let texture = texture_creator
@@ -85,12 +93,14 @@ fn draw_textured_line(
     position_on_texture: &f32,
     start: &ScreenPoint,
     end: &ScreenPoint,
-    registry: &ResourceRegistry,
+    registry: &mut ResourceRegistry,
     texture_index: TextureIndex,
+    tint: &crate::domain::ui::color::Color,
 ) {
     let texture = registry
-        .get_texture(texture_index)
+        .get_texture_mut(texture_index)
         .expect("No texture loaded");
+    texture.data_mut().set_color_mod(tint.red(), tint.green(), tint.blue());
 
     let rect_texture = Rect::new(
         (texture.width() as f32 * (*position_on_texture)) as i32,
@@ -111,18 +121,40 @@ fn draw_sprite(
     canvas: &mut sdl2::render::Canvas<sdl2::video::Window>,
     start: ScreenPoint,
     end: ScreenPoint,
-    registry: &ResourceRegistry,
+    registry: &mut ResourceRegistry,
     texture_index: TextureIndex,
+    tint: UiColor,
 ) {
     let texture = registry
-        .get_texture(texture_index)
+        .get_texture_mut(texture_index)
         .expect("No texture loaded");
+    texture.data_mut().set_color_mod(tint.red(), tint.green(), tint.blue());
 
     canvas
         .copy(texture.data(), None, Some(to_sdl_rect(&start, &end)))
         .expect("Cannot render a sprite");
 }
 
+fn draw_additive_sprite(
+    canvas: &mut sdl2::render::Canvas<sdl2::video::Window>,
+    start: ScreenPoint,
+    end: ScreenPoint,
+    registry: &mut ResourceRegistry,
+    texture_index: TextureIndex,
+) {
+    let texture = registry
+        .get_texture_mut(texture_index)
+        .expect("No texture loaded");
+    texture.data_mut().set_color_mod(255, 255, 255);
+    texture.data_mut().set_blend_mode(BlendMode::Add);
+
+    canvas
+        .copy(texture.data(), None, Some(to_sdl_rect(&start, &end)))
+        .expect("Cannot render a sprite");
+
+    texture.data_mut().set_blend_mode(BlendMode::Blend);
+}
+
 fn draw_rectangle(
     canvas: &mut sdl2::render::Canvas<sdl2::video::Window>,
     color: &crate::domain::ui::color::Color,
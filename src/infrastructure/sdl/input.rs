@@ -1,10 +1,56 @@
-use sdl2::event::Event;
+use sdl2::controller::{Axis, Button};
+use sdl2::event::{Event, WindowEvent};
 use sdl2::keyboard::Keycode;
+
+use crate::domain::control::bindings::{Chord, KeyBindings, Modifiers};
 use crate::domain::control::input::Input;
 
 use super::context::SdlContext;
 
-pub fn poll_input(sdl_context: &mut SdlContext) -> Vec<Input> {
+const STICK_DEADZONE: i16 = 8000;
+
+pub fn default_bindings() -> KeyBindings<Keycode> {
+    let mut bindings = KeyBindings::new();
+
+    bindings.bind_held(Chord::key(Keycode::W), Input::Forward);
+    bindings.bind_held(Chord::key(Keycode::Z), Input::Forward);
+
+    bindings.bind_held(Chord::key(Keycode::S), Input::Backward);
+
+    bindings.bind_held(Chord::key(Keycode::Q), Input::StrafeLeft);
+    bindings.bind_held(Chord::key(Keycode::A), Input::StrafeLeft);
+
+    bindings.bind_held(Chord::key(Keycode::D), Input::StrafeRight);
+
+    bindings.bind_held(Chord::key(Keycode::LShift), Input::Run);
+    bindings.bind_held(Chord::key(Keycode::C), Input::Crouch);
+    bindings.bind_held(Chord::key(Keycode::X), Input::Zoom);
+
+    bindings.bind_pressed(Chord::key(Keycode::H), Input::ShowFps);
+    bindings.bind_pressed(Chord::key(Keycode::N), Input::ToggleCompass);
+    bindings.bind_pressed(Chord::key(Keycode::M), Input::ToggleMinimap);
+    bindings.bind_pressed(Chord::new(Keycode::Return, Modifiers::alt()), Input::ToggleFullscreen);
+    bindings.bind_pressed(Chord::key(Keycode::Escape), Input::Quit);
+    bindings.bind_pressed(Chord::key(Keycode::E), Input::Action);
+    bindings.bind_pressed(Chord::key(Keycode::F6), Input::QuickSave);
+    bindings.bind_pressed(Chord::key(Keycode::F9), Input::QuickLoad);
+    let weapon_keys = [Keycode::Num1, Keycode::Num2, Keycode::Num3, Keycode::Num4, Keycode::Num5, Keycode::Num6, Keycode::Num7, Keycode::Num8, Keycode::Num9];
+    for (index, key) in weapon_keys.into_iter().enumerate() {
+        bindings.bind_pressed(Chord::key(key), Input::SelectWeapon(index));
+    }
+
+    bindings
+}
+
+pub fn configured_bindings(custom: &KeyBindings<String>) -> Result<KeyBindings<Keycode>, String> {
+    let custom = custom.try_map_keys(|name| Keycode::from_name(name).ok_or_else(|| format!("Unknown key '{}'", name)))?;
+    let mut bindings = default_bindings();
+    bindings.override_with(custom);
+
+    Ok(bindings)
+}
+
+pub fn poll_input(sdl_context: &mut SdlContext, bindings: &KeyBindings<Keycode>) -> Vec<Input> {
     let mut inputs = vec![];
 
     let event_pump = sdl_context.event_pump();
@@ -15,23 +61,11 @@ pub fn poll_input(sdl_context: &mut SdlContext) -> Vec<Input> {
         .filter_map(Keycode::from_scancode)
         .collect();
 
-    let mut alt_pressed = false;
+    let modifiers = modifiers(&keys);
 
     for key in keys {
-        match key {
-            Keycode::W => inputs.push(Input::Forward),
-            Keycode::Z => inputs.push(Input::Forward),
-
-            Keycode::S => inputs.push(Input::Backward),
-
-            Keycode::Q => inputs.push(Input::StrafeLeft),
-            Keycode::A => inputs.push(Input::StrafeLeft),
-
-            Keycode::D => inputs.push(Input::StrafeRight),
-
-            Keycode::LAlt => alt_pressed = true,
-
-            _ => (),
+        if let Some(input) = bindings.resolve_held(key, modifiers) {
+            inputs.push(input);
         }
     }
 
@@ -39,36 +73,145 @@ pub fn poll_input(sdl_context: &mut SdlContext) -> Vec<Input> {
         match event {
             Event::Quit { .. } => inputs.push(Input::Quit),
             Event::MouseMotion { xrel, .. } => inputs.push(Input::Rotate(xrel)),
-            Event::KeyDown {
-                keycode: Some(Keycode::H),
+            Event::Window {
+                win_event: WindowEvent::Resized(width, height),
                 ..
-            } => inputs.push(Input::ShowFps),
+            } => inputs.push(Input::Resize(width, height)),
             Event::KeyDown {
-                keycode: Some(Keycode::Return),
+                keycode: Some(key),
                 ..
             } => {
-                if alt_pressed {
-                    inputs.push(Input::ToggleFullscreen);
+                if let Some(input) = bindings.resolve_pressed(key, modifiers) {
+                    inputs.push(input);
                 }
             }
-            Event::KeyDown {
-                keycode: Some(Keycode::Escape),
-                ..
-            } => {
-                inputs.push(Input::Quit);
-            }
-            Event::KeyDown {
-                keycode: Some(Keycode::E),
-                ..
-            } => {
-                inputs.push(Input::Action);
-            }
             Event::MouseButtonDown { .. } => {
                 inputs.push(Input::Shoot);
             }
+            Event::ControllerButtonDown { button, .. } => {
+                if let Some(input) = controller_button_input(button) {
+                    inputs.push(input);
+                }
+            }
             _ => {}
         }
     }
 
+    if let Some(controller) = sdl_context.controller() {
+        inputs.extend(stick_inputs(
+            controller.axis(Axis::LeftX),
+            controller.axis(Axis::LeftY),
+            controller.axis(Axis::RightX),
+        ));
+    }
+
     inputs
 }
+
+pub fn stick_inputs(left_x: i16, left_y: i16, right_x: i16) -> Vec<Input> {
+    let mut inputs = vec![];
+
+    // Stick axes point down on Y, so pushing the stick away from the player is negative
+    if left_y < -STICK_DEADZONE {
+        inputs.push(Input::Forward);
+    } else if left_y > STICK_DEADZONE {
+        inputs.push(Input::Backward);
+    }
+
+    if left_x < -STICK_DEADZONE {
+        inputs.push(Input::StrafeLeft);
+    } else if left_x > STICK_DEADZONE {
+        inputs.push(Input::StrafeRight);
+    }
+
+    if right_x.unsigned_abs() > STICK_DEADZONE as u16 {
+        inputs.push(Input::Turn(right_x as f32 / i16::MAX as f32));
+    }
+
+    inputs
+}
+
+fn controller_button_input(button: Button) -> Option<Input> {
+    match button {
+        Button::A => Some(Input::Action),
+        Button::RightShoulder => Some(Input::Shoot),
+        _ => None,
+    }
+}
+
+fn modifiers(keys: &[Keycode]) -> Modifiers {
+    let held = |left: Keycode, right: Keycode| keys.contains(&left) || keys.contains(&right);
+
+    Modifiers::new(
+        held(Keycode::LAlt, Keycode::RAlt),
+        held(Keycode::LCtrl, Keycode::RCtrl),
+        held(Keycode::LShift, Keycode::RShift),
+    )
+}
+
+#[cfg(test)]
+mod input_test {
+    use sdl2::keyboard::Keycode;
+    use spectral::prelude::*;
+
+    use crate::domain::control::bindings::{Chord, Modifiers};
+    use crate::domain::control::input::Input;
+
+    use super::{default_bindings, modifiers, stick_inputs};
+
+    #[test]
+    fn alt_enter_should_toggle_fullscreen() {
+        let bindings = default_bindings();
+
+        assert_that!(bindings.resolve_pressed(Keycode::Return, modifiers(&[Keycode::LAlt])))
+            .is_equal_to(Some(Input::ToggleFullscreen));
+    }
+
+    #[test]
+    fn enter_alone_should_not_toggle_fullscreen() {
+        let bindings = default_bindings();
+
+        assert_that!(bindings.resolve_pressed(Keycode::Return, modifiers(&[]))).is_none();
+    }
+
+    #[test]
+    fn shift_chord_should_resolve_only_with_shift_held() {
+        let mut bindings = default_bindings();
+        bindings.bind_pressed(Chord::new(Keycode::H, Modifiers::shift()), Input::Quit);
+
+        assert_that!(bindings.resolve_pressed(Keycode::H, modifiers(&[Keycode::RShift]))).is_equal_to(Some(Input::Quit));
+        assert_that!(bindings.resolve_pressed(Keycode::H, modifiers(&[]))).is_equal_to(Some(Input::ShowFps));
+    }
+
+    #[test]
+    fn sticks_inside_the_deadzone_should_not_move() {
+        assert_that!(stick_inputs(2000, -7000, 7999)).is_empty();
+    }
+
+    #[test]
+    fn left_stick_should_move_and_strafe() {
+        assert_that!(stick_inputs(-20000, -20000, 0)).is_equal_to(vec![Input::Forward, Input::StrafeLeft]);
+        assert_that!(stick_inputs(20000, 20000, 0)).is_equal_to(vec![Input::Backward, Input::StrafeRight]);
+    }
+
+    #[test]
+    fn right_stick_should_turn_proportionally() {
+        assert_that!(stick_inputs(0, 0, 32767)).is_equal_to(vec![Input::Turn(1.0)]);
+        assert_that!(stick_inputs(0, 0, -16384)).is_equal_to(vec![Input::Turn(-16384.0 / 32767.0)]);
+    }
+
+    #[test]
+    fn m_should_toggle_the_minimap() {
+        let bindings = default_bindings();
+
+        assert_that!(bindings.resolve_pressed(Keycode::M, modifiers(&[]))).is_equal_to(Some(Input::ToggleMinimap));
+    }
+
+    #[test]
+    fn number_keys_should_select_weapons_from_zero() {
+        let bindings = default_bindings();
+
+        assert_that!(bindings.resolve_pressed(Keycode::Num1, modifiers(&[]))).is_equal_to(Some(Input::SelectWeapon(0)));
+        assert_that!(bindings.resolve_pressed(Keycode::Num9, modifiers(&[]))).is_equal_to(Some(Input::SelectWeapon(8)));
+    }
+}
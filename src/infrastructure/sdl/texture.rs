@@ -7,20 +7,17 @@ use sdl2::{
 };
 use sdl2::ttf::{Font, Sdl2TtfContext};
 
-use crate::domain::resources::ResourceLoader;
+use crate::domain::error::EngineError;
+use crate::domain::resources::{ResourceCache, ResourceLoader, ResourceRegistryLoader};
 use crate::domain::topology::index::{FontIndex, TextureIndex};
 
-pub trait ResourceRegistryLoader {
-    fn load_texture(&mut self, file: String) -> TextureIndex;
-    fn load_font(&mut self, filename: String) -> FontIndex;
-}
-
 pub struct ResourceRegistry<'a> {
     id: u128,
     texture_creator: &'a TextureCreator<WindowContext>,
     ttf_context: &'a Sdl2TtfContext,
     resource_loader: &'a ResourceLoader,
 
+    cache: ResourceCache,
     texture_registry: HashMap<u128, LoadedTexture<'a>>,
     font_registry: HashMap<u128, Font<'a, 'a>>,
 }
@@ -42,6 +39,7 @@ impl<'s> ResourceRegistry<'s> {
             texture_creator,
             resource_loader,
             ttf_context: ttf_creator,
+            cache: ResourceCache::new(),
             texture_registry: HashMap::new(),
             font_registry: HashMap::new(),
         }
@@ -51,41 +49,48 @@ impl<'s> ResourceRegistry<'s> {
         self.texture_registry.get(&index.id())
     }
 
-    pub fn get_font(&self, index: FontIndex) -> Option<&Font<'s, 's>> {
-        self.font_registry.get(&index.id())
+    pub fn get_texture_mut(&mut self, index: TextureIndex) -> Option<&mut LoadedTexture<'s>> {
+        self.texture_registry.get_mut(&index.id())
     }
 
-    fn generate_id(&mut self) -> u128 {
-        let generated = self.id;
-        self.id += 1;
-
-        generated
+    pub fn get_font(&self, index: FontIndex) -> Option<&Font<'s, 's>> {
+        self.font_registry.get(&index.id())
     }
 }
 
 impl<'s> ResourceRegistryLoader for ResourceRegistry<'s> {
-    fn load_texture(&mut self, file: String) -> TextureIndex {
-        let texture = load_texture(self.texture_creator, file, self.resource_loader);
-        let query = texture.query();
+    fn load_texture(&mut self, file: String) -> Result<TextureIndex, EngineError> {
+        self.cache.texture(file, |file| {
+            let texture = load_texture(self.texture_creator, file, self.resource_loader)?;
+            let query = texture.query();
 
-        let loaded_texture = LoadedTexture::new(texture, query.width, query.height);
+            let loaded_texture = LoadedTexture::new(texture, query.width, query.height);
 
-        let current_id = self.generate_id();
-        self.texture_registry.insert(current_id, loaded_texture);
+            let current_id = generate_id(&mut self.id);
+            self.texture_registry.insert(current_id, loaded_texture);
 
-        TextureIndex::new(current_id)
+            Ok(TextureIndex::new(current_id))
+        })
     }
 
-    fn load_font(&mut self, filename: String) -> FontIndex {
-        let file = self.resource_loader.load_as_file(filename);
-        let font = self.ttf_context.load_font(file, 128).unwrap();
-        let current_id = self.generate_id();
-        self.font_registry.insert(current_id, font);
+    fn load_font(&mut self, filename: String) -> Result<FontIndex, EngineError> {
+        self.cache.font(filename, |filename| {
+            let file = self.resource_loader.load_as_file(filename.clone());
+            let font = self.ttf_context.load_font(file, 128)
+                .map_err(|e| EngineError::Resource(format!("'{}': {}", filename, e)))?;
+            let current_id = generate_id(&mut self.id);
+            self.font_registry.insert(current_id, font);
 
-        FontIndex::new(current_id)
+            Ok(FontIndex::new(current_id))
+        })
     }
+}
 
+fn generate_id(id: &mut u128) -> u128 {
+    let generated = *id;
+    *id += 1;
 
+    generated
 }
 
 impl<'s> LoadedTexture<'s> {
@@ -101,6 +106,10 @@ impl<'s> LoadedTexture<'s> {
         &self.data
     }
 
+    pub fn data_mut(&mut self) -> &mut Texture<'s> {
+        &mut self.data
+    }
+
     pub fn width(&self) -> u32 {
         self.width
     }
@@ -110,10 +119,9 @@ impl<'s> LoadedTexture<'s> {
     }
 }
 
-fn load_texture<'a>(texture_creator: &'a TextureCreator<WindowContext>, filename: String, resource_loader: &'a ResourceLoader) -> Texture<'a> {
-    let path = resource_loader.load_as_binary(filename);
-    let texture = texture_creator
+fn load_texture<'a>(texture_creator: &'a TextureCreator<WindowContext>, filename: String, resource_loader: &'a ResourceLoader) -> Result<Texture<'a>, EngineError> {
+    let path = resource_loader.load_as_binary(filename.clone())?;
+    texture_creator
         .load_texture_bytes(path.as_slice())
-        .expect("Unable to load texture");
-    texture
+        .map_err(|e| EngineError::Resource(format!("'{}': {}", filename, e)))
 }
\ No newline at end of file
@@ -1,28 +1,38 @@
-use std::time::Instant;
+use std::process;
+use std::thread;
+use std::time::{Duration, Instant};
 
 use sdl2::ttf;
 
-use wolfengate::domain::control::force::{Force, InputForce};
+use wolfengate::domain::control::force::Force;
 use wolfengate::domain::control::input::Input;
+use wolfengate::domain::error::EngineError;
+use wolfengate::domain::game::GameConfiguration;
 use wolfengate::domain::level::Level;
-use wolfengate::domain::maths::{ANGLE_0, ANGLE_90, ANGLE_RIGHT};
-use wolfengate::domain::resources::ResourceLoader;
+use wolfengate::domain::maths::{ANGLE_0, ANGLE_RIGHT};
+use wolfengate::domain::resources::{ResourceLoader, ResourceRegistryLoader};
+use wolfengate::domain::topology::index::FontIndex;
 use wolfengate::domain::topology::map::Map;
+use wolfengate::domain::ui::compass::Compass;
 use wolfengate::domain::ui::debug::DebugInfo;
 use wolfengate::domain::ui::view::ViewScreen;
-use wolfengate::infrastructure::fs::filesystem::{load_as_binary, load_as_file};
-use wolfengate::infrastructure::fs::json::load_configuration;
+use wolfengate::infrastructure::fs::filesystem::{load_as_binary, load_as_file, read_save, write_save};
+use wolfengate::infrastructure::fs::json::{load_configuration, load_game_configuration, load_level_state, save_level_state};
 use wolfengate::infrastructure::sdl::context::SdlContext;
 use wolfengate::infrastructure::sdl::drawer;
 use wolfengate::infrastructure::sdl::drawer::ask_display;
-use wolfengate::infrastructure::sdl::input::poll_input;
-use wolfengate::infrastructure::sdl::texture::{ResourceRegistryLoader, ResourceRegistry};
+use wolfengate::infrastructure::sdl::input::{configured_bindings, poll_input};
+use wolfengate::infrastructure::sdl::texture::ResourceRegistry;
+
+const QUICK_SAVE: &str = "quick.sav";
+const TICK_IN_MICROSECONDS: u128 = 1000000 / 120;
+const MAX_CATCH_UP_IN_MICROSECONDS: u128 = 250000;
 
 fn render(
     context: &mut SdlContext,
     level: &Level,
     debug_info: &DebugInfo,
-    registry: &ResourceRegistry,
+    registry: &mut ResourceRegistry,
 ) {
     let actions = level.generate_actions();
     drawer::draw(context, registry, actions);
@@ -30,62 +40,180 @@ fn render(
     ask_display(context);
 }
 
-fn main() -> Result<(), String> {
-    let view = ViewScreen::new(500, 800, ANGLE_90);
-    let mut sdl_context = SdlContext::new(view)?;
+fn main() {
+    if let Err(error) = run() {
+        eprintln!("{}", error);
+        process::exit(1);
+    }
+}
+
+fn run() -> Result<(), String> {
+    let resource_loader = ResourceLoader::new(load_as_binary, load_as_file).with_saves(read_save, write_save);
+    let game = resource_loader.load_as_string(String::from("game.json"))
+        .and_then(load_game_configuration)
+        .map_err(|e| e.to_string())?;
+
+    let mut view = game.view();
+    let mut sdl_context = SdlContext::new(view, game.vsync())?;
     let texture_creator = sdl_context.canvas().texture_creator();
     let ttf_creator = ttf::init().unwrap();
-    let resource_loader = ResourceLoader::new(load_as_binary, load_as_file);
 
     let mut registry = ResourceRegistry::new(&texture_creator, &ttf_creator, &resource_loader);
-    let debug_font = registry.load_font(String::from("MontserratAlternates-Medium.otf"));
+    let debug_font = registry.load_font(game.font()).map_err(|e| e.to_string())?;
 
-    let map = map_loader(&mut registry, resource_loader);
+    let map = map_loader(&mut registry, resource_loader, &game, game.map()).map_err(|e| e.to_string())?;
 
-    let input_force = InputForce::new(0.004, 0.005);
-    let mut level = Level::new(view, map);
+    let input_force = game.input_force();
+    let mut level = build_level(view, map, game.map(), &game, debug_font)?;
     let mut debug_info = DebugInfo::new(debug_font);
+    if let Some(smoothing) = game.fps_smoothing() {
+        debug_info = debug_info.with_fps_smoothing(smoothing);
+    }
+    let bindings = configured_bindings(&game.key_bindings())?;
 
     let mut start = Instant::now();
+    let mut accumulated = 0;
+    let mut pending_rotation = ANGLE_0;
     'running: loop {
         let elapsed = start.elapsed().as_micros();
         start = Instant::now();
         let mut current_force = Force::new(ANGLE_RIGHT, 0.0, ANGLE_0);
-        for input in poll_input(&mut sdl_context) {
+        let mut running = false;
+        let mut crouching = false;
+        let mut zooming = false;
+        let mut turn_rate = 0.0;
+        for input in poll_input(&mut sdl_context, &bindings) {
             match input {
                 Input::Quit => break 'running,
                 Input::Forward => current_force = current_force.add(input_force.forward()),
                 Input::Backward => current_force = current_force.add(input_force.backward()),
                 Input::StrafeLeft => current_force = current_force.add(input_force.strafe_left()),
-                Input::StrafeRight => current_force = current_force.add(input_force.state_right()),
+                Input::StrafeRight => current_force = current_force.add(input_force.strafe_right()),
+                Input::Run => running = true,
+                Input::Crouch => crouching = true,
+                Input::Zoom => zooming = true,
                 Input::Rotate(x) => current_force = current_force.add(input_force.rotate(x)),
+                Input::Turn(rate) => turn_rate = rate,
+                Input::Resize(width, height) => {
+                    view = view.resize(width.clamp(0, u16::MAX as i32) as u16, height.clamp(0, u16::MAX as i32) as u16, game.aspect_mode());
+                    level.set_view(view);
+                    sdl_context.set_logical_size(view);
+                }
                 Input::ToggleFullscreen => sdl_context.toggle_fullscreen(),
                 Input::Action => level.handle_action(),
                 Input::Shoot => level.handle_shoot(),
+                Input::SelectWeapon(index) => level.select_weapon(index),
                 Input::ShowFps => debug_info = debug_info.toggle_fps(),
+                Input::ToggleCompass => level.toggle_compass(),
+                Input::ToggleMinimap => level.toggle_minimap(),
+                Input::QuickSave => {
+                    let saved = save_level_state(&level.save_state())
+                        .and_then(|content| resource_loader.write_save(String::from(QUICK_SAVE), content));
+                    if let Err(error) = saved {
+                        eprintln!("Quick save failed: {}", error);
+                    }
+                }
+                Input::QuickLoad => {
+                    let loaded = resource_loader.read_save(String::from(QUICK_SAVE))
+                        .and_then(load_level_state)
+                        .and_then(|state| level.load_state(&state));
+                    if let Err(error) = loaded {
+                        eprintln!("Quick load ignored: {}", error);
+                    }
+                }
             }
         }
 
-        level.notify_elapsed(elapsed);
-        level.apply_forces(current_force, elapsed);
-        level.apply_shoots();
+        let update_start = Instant::now();
+        let force = current_force.clamp_power(input_force.movement_speed());
+        // Rotation comes from mouse deltas rather than a rate, so it is applied once on the next tick
+        pending_rotation = pending_rotation.add(force.rotation());
+        accumulated = (accumulated + elapsed).min(MAX_CATCH_UP_IN_MICROSECONDS);
+        while accumulated >= TICK_IN_MICROSECONDS {
+            if running {
+                level.run();
+            }
+            if crouching {
+                level.crouch();
+            }
+            if zooming {
+                level.zoom();
+            }
+            let turn = input_force.turn(turn_rate, TICK_IN_MICROSECONDS).rotation();
+            level.tick(force.with_rotation(pending_rotation.add(turn)), TICK_IN_MICROSECONDS);
+            pending_rotation = ANGLE_0;
+            accumulated -= TICK_IN_MICROSECONDS;
+        }
+        // There is no audio backend yet, the capped sounds of the frame are dropped once drained
+        level.drain_sounds();
+        if let Some(next_map) = level.poll_transition() {
+            let next_level = map_loader(&mut registry, resource_loader, &game, next_map.clone())
+                .map_err(|e| e.to_string())
+                .and_then(|map| build_level(view, map, next_map.clone(), &game, debug_font));
+            match next_level {
+                Ok(next_level) => level = next_level.with_player_state_of(&level),
+                Err(error) => eprintln!("Exit to '{}' ignored: {}", next_map, error),
+            }
+        }
+        let update_time = update_start.elapsed().as_micros();
 
         // Render
-        render(&mut sdl_context, &level, &debug_info, &registry);
-        debug_info = debug_info.with_another_frame_displayed(elapsed);
+        let render_start = Instant::now();
+        render(&mut sdl_context, &level, &debug_info, &mut registry);
+        let render_time = render_start.elapsed().as_micros();
+
+        debug_info = debug_info
+            .with_frame_timing(update_time, render_time)
+            .with_another_frame_displayed(elapsed);
+
+        let sleep = game.frame_sleep(start.elapsed().as_micros());
+        if sleep > 0 {
+            thread::sleep(Duration::from_micros(sleep as u64));
+        }
     }
 
     Ok(())
 }
 
-pub fn map_loader(registry: &mut ResourceRegistry, resource_loader: ResourceLoader) -> Map {
-    let configuration_content = resource_loader.load_as_string(String::from("conf.json"));
-    let configuration = load_configuration(configuration_content, registry);
+fn build_level(view: ViewScreen, map: Map, map_name: String, game: &GameConfiguration, font: FontIndex) -> Result<Level, String> {
+    let mut level = Level::new(view, map).map_err(|e| e.to_string())?
+        .with_map_name(map_name)
+        .with_render_scale(game.render_scale())
+        .with_compass(Compass::new(font))
+        .with_hud(font);
+    if let Some(maximum) = game.max_sounds_per_frame() {
+        level = level.with_max_sounds_per_frame(maximum);
+    }
+    if let Some(aim_assist) = game.aim_assist() {
+        level = level.with_aim_assist(aim_assist);
+    }
+    if game.turn_snap() {
+        level = level.with_turn_snap();
+    }
+    if let Some(distance) = game.animation_distance() {
+        level = level.with_animation_distance(distance);
+    }
+    if let Some(crosshair) = game.crosshair() {
+        level = level.with_crosshair(crosshair);
+    }
+    if let Some(wall_bump) = game.wall_bump() {
+        level = level.with_wall_bump(wall_bump);
+    }
+    if let Some(despawn) = game.despawn() {
+        level = level.with_despawn(despawn);
+    }
+
+    Ok(level)
+}
+
+pub fn map_loader(registry: &mut ResourceRegistry, resource_loader: ResourceLoader, game: &GameConfiguration, map_name: String) -> Result<Map, EngineError> {
+    let configuration_content = resource_loader.load_as_string(game.configuration())?;
+    let configuration = load_configuration(configuration_content, registry)?;
 
-    let map_content = resource_loader.load_as_string(String::from("1.map"));
+    let map_content = resource_loader.load_as_string(map_name)?;
 
     Map::new(
         &map_content,
         configuration)
-        .unwrap()
+        .map_err(EngineError::Map)
 }